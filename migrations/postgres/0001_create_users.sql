@@ -0,0 +1,25 @@
+-- Users, one row per user of a tenant
+--
+-- `data` holds the whole user; the other columns are copies of its fields
+-- that queries filter and sort on. Sort keys use the "C" collation so they
+-- order by code point, like the domain's cursors. `created_at` is RFC 3339
+-- text with nanoseconds in UTC, finer than TIMESTAMPTZ, so it sorts as text.
+CREATE TABLE users (
+    tenant_id    TEXT        NOT NULL,
+    id           UUID        NOT NULL,
+    email        TEXT        COLLATE "C" NOT NULL,
+    email_domain TEXT        NOT NULL,
+    name_key     TEXT        COLLATE "C" NOT NULL,
+    status       TEXT        NOT NULL,
+    created_at   TEXT        COLLATE "C" NOT NULL,
+    deleted_at   TIMESTAMPTZ,
+    version      BIGINT      NOT NULL,
+    data         JSONB       NOT NULL,
+    PRIMARY KEY (tenant_id, id)
+);
+
+-- Only live users own their email; deleted ones may share it
+CREATE UNIQUE INDEX users_email ON users (tenant_id, email) WHERE deleted_at IS NULL;
+
+CREATE INDEX users_created_at ON users (tenant_id, created_at, id);
+CREATE INDEX users_name_key ON users (tenant_id, name_key, id);
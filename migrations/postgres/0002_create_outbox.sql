@@ -0,0 +1,11 @@
+-- Transactional outbox, written in the same transaction as the users
+CREATE TABLE outbox (
+    seq             BIGSERIAL   PRIMARY KEY,
+    id              UUID        NOT NULL UNIQUE,
+    tenant_id       TEXT        NOT NULL,
+    status          TEXT        NOT NULL,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    data            JSONB       NOT NULL
+);
+
+CREATE INDEX outbox_due ON outbox (status, next_attempt_at);
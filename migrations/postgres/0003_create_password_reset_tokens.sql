@@ -0,0 +1,10 @@
+-- Password reset tokens, written in the same transaction as their emails'
+-- outbox messages
+--
+-- `data` holds the whole token; `used_at` is a copy of its field, so a token
+-- is consumed by one conditional update.
+CREATE TABLE password_reset_tokens (
+    token_hash TEXT        PRIMARY KEY,
+    used_at    TIMESTAMPTZ,
+    data       JSONB       NOT NULL
+);
@@ -0,0 +1,24 @@
+-- Users, one row per user of a tenant
+--
+-- `data` holds the whole user as JSON; the other columns are copies of its
+-- fields that queries filter and sort on. `created_at` is RFC 3339 with
+-- nanoseconds in UTC, so it sorts as text.
+CREATE TABLE users (
+    tenant_id    TEXT    NOT NULL,
+    id           TEXT    NOT NULL,
+    email        TEXT    NOT NULL,
+    email_domain TEXT    NOT NULL,
+    name_key     TEXT    NOT NULL,
+    status       TEXT    NOT NULL,
+    created_at   TEXT    NOT NULL,
+    deleted_at   TEXT,
+    version      INTEGER NOT NULL,
+    data         TEXT    NOT NULL,
+    PRIMARY KEY (tenant_id, id)
+);
+
+-- Only live users own their email; deleted ones may share it
+CREATE UNIQUE INDEX users_email ON users (tenant_id, email) WHERE deleted_at IS NULL;
+
+CREATE INDEX users_created_at ON users (tenant_id, created_at, id);
+CREATE INDEX users_name_key ON users (tenant_id, name_key, id);
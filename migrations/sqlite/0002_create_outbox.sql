@@ -0,0 +1,11 @@
+-- Transactional outbox, written in the same transaction as the users
+CREATE TABLE outbox (
+    seq             INTEGER PRIMARY KEY AUTOINCREMENT,
+    id              TEXT    NOT NULL UNIQUE,
+    tenant_id       TEXT    NOT NULL,
+    status          TEXT    NOT NULL,
+    next_attempt_at TEXT    NOT NULL,
+    data            TEXT    NOT NULL
+);
+
+CREATE INDEX outbox_due ON outbox (status, next_attempt_at);
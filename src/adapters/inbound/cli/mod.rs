@@ -23,7 +23,7 @@
 //!         #[arg(short, long)]
 //!         email: String,
 //!         #[arg(short, long)]
-//!         name: String,
+//!         name: DisplayName,
 //!     },
 //!     /// List all users
 //!     ListUsers,
@@ -37,12 +37,13 @@
 //!
 //! match cli.command {
 //!     Commands::CreateUser { email, name } => {
-//!         let user = service.register(&email, &name).await?;
+//!         let user = service.register(&tenant, &email, name).await?;
 //!         println!("Created user: {}", user.id);
 //!     }
-//!     Commands::ListUsers => {
-//!         let users = service.list().await?;
-//!         for user in users {
+//!     Commands::ListUsers { limit, cursor } => {
+//!         let query = UserQuery { limit, after: cursor, ..UserQuery::default() };
+//!         let page = service.list(&tenant, &Principal::System, &query).await?;
+//!         for user in page.items {
 //!             println!("{}: {}", user.id, user.name);
 //!         }
 //!     }
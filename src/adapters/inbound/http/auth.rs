@@ -0,0 +1,84 @@
+//! Request authentication
+//!
+//! Protected endpoints identify the caller with HTTP Basic credentials,
+//! checked against the user's password on every request. Credentials are
+//! only valid within the tenant the request resolves to.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::tenant::Tenant;
+use crate::domain::{
+    entities::Principal,
+    errors::DomainError,
+    ports::{EmailService, UserRepository},
+    services::UserService,
+};
+
+/// The authenticated caller of a request
+pub struct Actor(pub Principal);
+
+impl<R, E> FromRequestParts<Arc<UserService<R, E>>> for Actor
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        service: &Arc<UserService<R, E>>,
+    ) -> Result<Self, Self::Rejection> {
+        let Tenant(tenant) = Tenant::from_request_parts(parts, service).await?;
+        let (email, password) = basic_credentials(&parts.headers)
+            .ok_or_else(|| DomainError::unauthorized("Missing or malformed credentials"))?;
+        let user = service.authenticate(&tenant, &email, &password).await?;
+
+        Ok(Self(Principal::from_user(&user)))
+    }
+}
+
+/// Decode `Authorization: Basic base64(email:password)`
+fn basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, encoded) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (email, password) = decoded.split_once(':')?;
+    Some((email.to_string(), password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_basic_credentials() {
+        let encoded = STANDARD.encode("user@example.com:pass:word");
+
+        assert_eq!(
+            basic_credentials(&headers(&format!("Basic {}", encoded))),
+            Some(("user@example.com".to_string(), "pass:word".to_string()))
+        );
+        assert_eq!(
+            basic_credentials(&headers(&format!("Bearer {}", encoded))),
+            None
+        );
+        assert_eq!(basic_credentials(&headers("Basic !!!")), None);
+        assert_eq!(basic_credentials(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,280 @@
+//! Request and response DTOs
+//!
+//! These types define the wire format of the user API and keep it
+//! decoupled from the domain entities.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        AuditActor, AuditRecord, DisplayName, FieldChange, OutboxMessage, User, UserStatus,
+    },
+    ports::Page,
+};
+
+/// Body of `POST /users`
+#[derive(Serialize, Deserialize)]
+pub struct CreateUserRequest {
+    pub email: String,
+    pub name: DisplayName,
+    /// Optional initial password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl std::fmt::Debug for CreateUserRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CreateUserRequest")
+            .field("email", &self.email)
+            .field("name", &self.name)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Body of `POST /auth/login`
+#[derive(Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+impl std::fmt::Debug for LoginRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoginRequest")
+            .field("email", &self.email)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Body of `PATCH /users/{id}`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateUserRequest {
+    pub name: DisplayName,
+}
+
+/// Body of `POST /users/{id}/suspend`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuspendUserRequest {
+    pub reason: String,
+}
+
+/// Body of `POST /users/{id}/roles`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignRoleRequest {
+    pub role: String,
+}
+
+/// Body of `POST /auth/password-reset`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+/// Body of `POST /auth/password-reset/confirm`
+#[derive(Serialize, Deserialize)]
+pub struct ConfirmPasswordResetRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+impl std::fmt::Debug for ConfirmPasswordResetRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfirmPasswordResetRequest")
+            .field("token", &"<redacted>")
+            .field("new_password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Body of `POST /users/verify`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+/// User representation returned by the API
+///
+/// Credentials are deliberately absent.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub name: DisplayName,
+    pub email_verified: bool,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspension_reason: Option<String>,
+    pub roles: Vec<String>,
+    pub version: u64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        let suspension_reason = match &user.status {
+            UserStatus::Suspended { reason } => Some(reason.clone()),
+            _ => None,
+        };
+
+        Self {
+            id: user.id.0,
+            email: user.email.as_str().to_string(),
+            email_verified: user.is_email_verified(),
+            name: user.name,
+            status: user.status.as_str().to_string(),
+            suspension_reason,
+            roles: user.roles.iter().map(|r| r.as_str().to_string()).collect(),
+            version: user.version,
+            created_at: user.created_at.to_rfc3339(),
+            updated_at: user.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Query string of `GET /users`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserListQuery {
+    /// Only list users with an email at this domain
+    pub email_domain: Option<String>,
+    /// Only list users whose name starts with this, ignoring case
+    pub name_prefix: Option<String>,
+    /// Only list users registered at or after this RFC 3339 time
+    pub created_from: Option<String>,
+    /// Only list users registered before this RFC 3339 time
+    pub created_until: Option<String>,
+    /// Only list users in this status (`pending`, `active`, `suspended`, `deactivated`)
+    pub status: Option<String>,
+    /// Sort by `created_at` (the default), `email` or `name`
+    pub sort: Option<String>,
+    /// Sort `asc` (the default) or `desc`
+    pub order: Option<String>,
+    /// Users per page
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+/// A page of users returned by `GET /users`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserPageResponse {
+    pub items: Vec<UserResponse>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Number of users matching the filters across all pages
+    pub total: usize,
+}
+
+impl From<Page<User>> for UserPageResponse {
+    fn from(page: Page<User>) -> Self {
+        Self {
+            next_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
+            total: page.total,
+            items: page.items.into_iter().map(UserResponse::from).collect(),
+        }
+    }
+}
+
+/// Query string of `GET /outbox`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OutboxQuery {
+    /// Only list messages in this status (`pending`, `delivered`, `failed`)
+    pub status: Option<String>,
+}
+
+/// Outbox message representation returned by the API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutboxMessageResponse {
+    pub id: Uuid,
+    pub event: String,
+    pub user_id: Uuid,
+    pub status: String,
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub next_attempt_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<String>,
+}
+
+impl From<OutboxMessage> for OutboxMessageResponse {
+    fn from(message: OutboxMessage) -> Self {
+        Self {
+            id: message.id,
+            event: message.event.name().to_string(),
+            user_id: message.event.user_id().0,
+            status: message.status.as_str().to_string(),
+            attempts: message.attempts,
+            last_error: message.last_error,
+            created_at: message.created_at.to_rfc3339(),
+            next_attempt_at: message.next_attempt_at.to_rfc3339(),
+            delivered_at: message.delivered_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// Query string of `GET /users/{id}/audit`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditLogQuery {
+    /// Only list records made at or after this RFC 3339 time
+    pub from: Option<String>,
+    /// Only list records made before this RFC 3339 time
+    pub until: Option<String>,
+}
+
+/// Audit record representation returned by the API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditRecordResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// `system`, `user` or `anonymous`
+    pub actor: String,
+    /// ID of the acting user, if `actor` is `user`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub occurred_at: String,
+    pub changes: Vec<FieldChangeResponse>,
+}
+
+/// One changed field of an audit record
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldChangeResponse {
+    pub field: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+impl From<AuditRecord> for AuditRecordResponse {
+    fn from(record: AuditRecord) -> Self {
+        let (actor, actor_id) = match record.actor {
+            AuditActor::System => ("system", None),
+            AuditActor::User(id) => ("user", Some(id.0)),
+            AuditActor::Anonymous => ("anonymous", None),
+        };
+        Self {
+            id: record.id,
+            user_id: record.user_id.0,
+            actor: actor.to_string(),
+            actor_id,
+            action: record.action,
+            occurred_at: record.occurred_at.to_rfc3339(),
+            changes: record.changes.into_iter().map(|c| c.into()).collect(),
+        }
+    }
+}
+
+impl From<FieldChange> for FieldChangeResponse {
+    fn from(change: FieldChange) -> Self {
+        Self {
+            field: change.field,
+            before: change.before,
+            after: change.after,
+        }
+    }
+}
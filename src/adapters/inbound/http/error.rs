@@ -0,0 +1,116 @@
+//! HTTP error handling
+//!
+//! Maps domain errors to HTTP responses.
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::DomainError;
+
+/// Error response format
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let challenge = matches!(self, DomainError::Unauthorized(_));
+        let (status, error, message) = match self {
+            DomainError::NotFound { entity_type, id } => (
+                StatusCode::NOT_FOUND,
+                "Not found",
+                Some(format!("{} with id {} not found", entity_type, id)),
+            ),
+            DomainError::ValidationError(msg) => {
+                (StatusCode::BAD_REQUEST, "Validation error", Some(msg))
+            }
+            DomainError::BusinessRuleViolation(msg) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Business rule violation",
+                Some(msg),
+            ),
+            DomainError::Conflict(msg) => (StatusCode::CONFLICT, "Conflict", Some(msg)),
+            DomainError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "Unauthorized", Some(msg)),
+            DomainError::Forbidden(msg) => (StatusCode::FORBIDDEN, "Forbidden", Some(msg)),
+            DomainError::PreconditionFailed(msg) => (
+                StatusCode::PRECONDITION_FAILED,
+                "Precondition failed",
+                Some(msg),
+            ),
+            DomainError::Infrastructure(e) => {
+                // Never leak infrastructure details to clients
+                tracing::error!("Infrastructure error: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error",
+                    None,
+                )
+            }
+        };
+
+        let mut response = (
+            status,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                message,
+            }),
+        )
+            .into_response();
+
+        // Tell clients how to authenticate
+        if challenge {
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static("Basic realm=\"users\""),
+            );
+        }
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_status_codes() {
+        let cases = [
+            (
+                DomainError::NotFound {
+                    entity_type: "User",
+                    id: Uuid::nil(),
+                },
+                StatusCode::NOT_FOUND,
+            ),
+            (DomainError::validation("bad"), StatusCode::BAD_REQUEST),
+            (
+                DomainError::business_rule("nope"),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (DomainError::conflict("dup"), StatusCode::CONFLICT),
+            (DomainError::unauthorized("who?"), StatusCode::UNAUTHORIZED),
+            (DomainError::forbidden("no"), StatusCode::FORBIDDEN),
+            (
+                DomainError::precondition_failed("stale"),
+                StatusCode::PRECONDITION_FAILED,
+            ),
+            (
+                DomainError::Infrastructure(anyhow::anyhow!("db down")),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.into_response().status(), expected);
+        }
+    }
+}
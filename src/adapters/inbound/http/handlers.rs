@@ -0,0 +1,474 @@
+//! HTTP request handlers
+//!
+//! Each handler is a thin translation layer: it parses the request,
+//! delegates to [`UserService`] within the request's tenant and maps the
+//! result to a response.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::auth::Actor;
+use super::dto::{
+    AssignRoleRequest, AuditLogQuery, AuditRecordResponse, ConfirmPasswordResetRequest,
+    CreateUserRequest, LoginRequest, OutboxMessageResponse, OutboxQuery, PasswordResetRequest,
+    SuspendUserRequest, UpdateUserRequest, UserListQuery, UserPageResponse, UserResponse,
+    VerifyEmailRequest,
+};
+use super::precondition::{IfMatch, Tagged};
+use super::tenant::Tenant;
+use crate::domain::{
+    entities::{AuditQuery, OutboxStatus, Role, UserId},
+    errors::DomainError,
+    ports::{EmailService, UserQuery, UserRepository, DEFAULT_PAGE_SIZE},
+    services::UserService,
+};
+
+/// Parse an optional RFC 3339 time from a query string
+fn parse_time(value: Option<String>) -> Result<Option<DateTime<Utc>>, DomainError> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|time| time.with_timezone(&Utc))
+                .map_err(|_| DomainError::validation(format!("Invalid time: {}", value)))
+        })
+        .transpose()
+}
+
+/// The domain query for the query string of `GET /users`
+fn user_query(query: UserListQuery) -> Result<UserQuery, DomainError> {
+    Ok(UserQuery {
+        email_domain: query.email_domain,
+        name_prefix: query.name_prefix,
+        created_from: parse_time(query.created_from)?,
+        created_until: parse_time(query.created_until)?,
+        status: query.status.as_deref().map(str::parse).transpose()?,
+        sort: query
+            .sort
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or_default(),
+        direction: query
+            .order
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or_default(),
+        after: query.cursor.as_deref().map(str::parse).transpose()?,
+        limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    })
+}
+
+/// Create a new user
+pub async fn create_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Json(req): Json<CreateUserRequest>,
+) -> Result<(StatusCode, Tagged<UserResponse>), DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let CreateUserRequest {
+        email,
+        name,
+        password,
+    } = req;
+    let user = match password {
+        Some(password) => {
+            service
+                .register_with_password(&tenant, &email, name, &password)
+                .await?
+        }
+        None => service.register(&tenant, &email, name).await?,
+    };
+
+    tracing::info!("Created user: {}", user.id);
+
+    Ok((StatusCode::CREATED, user.into()))
+}
+
+/// Get a user by ID
+pub async fn get_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service.get_by_id(&tenant, &actor, &UserId(id)).await?;
+    Ok(user.into())
+}
+
+/// List a page of users, e.g. `GET /users?email_domain=example.com&sort=name&limit=20`
+pub async fn list_users<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Query(query): Query<UserListQuery>,
+) -> Result<Json<UserPageResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let query = user_query(query)?;
+    let page = service.list(&tenant, &actor, &query).await?;
+    Ok(Json(page.into()))
+}
+
+/// Export users as newline-delimited JSON, e.g. `GET /users/export?status=active`
+///
+/// Takes the filters and order of `GET /users`, with `limit` users read at
+/// a time. The body is sent as users are read and holds back reading while
+/// the client is slow; a failure after the first user ends it early.
+pub async fn export_users<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Query(query): Query<UserListQuery>,
+) -> Result<Response, DomainError>
+where
+    R: UserRepository + 'static,
+    E: EmailService + 'static,
+{
+    let query = user_query(query)?;
+
+    let (lines, mut received) = mpsc::channel::<Result<String, DomainError>>(1);
+    tokio::spawn(async move {
+        let users = match service.stream(&tenant, &actor, query) {
+            Ok(users) => users,
+            Err(e) => {
+                let _ = lines.send(Err(e)).await;
+                return;
+            }
+        };
+        let mut users = users.map_ok(|user| {
+            let mut line =
+                serde_json::to_string(&UserResponse::from(user)).expect("responses serialize");
+            line.push('\n');
+            line
+        });
+        while let Some(line) = users.next().await {
+            let failed = line.is_err();
+            if lines.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    // Fail with a status code while none has been sent yet
+    let first = received.recv().await.transpose()?;
+    let rest = stream::unfold(received, |mut received| async move {
+        received.recv().await.map(|line| (line, received))
+    });
+    let body = Body::from_stream(stream::iter(first.map(Ok)).chain(rest));
+
+    Ok(([(header::CONTENT_TYPE, "application/x-ndjson")], body).into_response())
+}
+
+/// Rename a user
+pub async fn update_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    Json(req): Json<UpdateUserRequest>,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .update_name(&tenant, &actor, &UserId(id), req.name, expected_version)
+        .await?;
+    Ok(user.into())
+}
+
+/// Activate a pending user
+pub async fn activate_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .activate(&tenant, &actor, &UserId(id), expected_version)
+        .await?;
+    Ok(user.into())
+}
+
+/// Suspend an active user
+pub async fn suspend_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    Json(req): Json<SuspendUserRequest>,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .suspend(&tenant, &actor, &UserId(id), &req.reason, expected_version)
+        .await?;
+
+    tracing::info!("Suspended user: {}", id);
+
+    Ok(user.into())
+}
+
+/// Lift a user's suspension
+pub async fn reactivate_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .reactivate(&tenant, &actor, &UserId(id), expected_version)
+        .await?;
+    Ok(user.into())
+}
+
+/// Permanently deactivate a user
+pub async fn deactivate_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .deactivate(&tenant, &actor, &UserId(id), expected_version)
+        .await?;
+
+    tracing::info!("Deactivated user: {}", id);
+
+    Ok(user.into())
+}
+
+/// Grant a role to a user
+pub async fn assign_role<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    Json(req): Json<AssignRoleRequest>,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let role: Role = req.role.parse()?;
+    let user = service
+        .assign_role(&tenant, &actor, &UserId(id), role, expected_version)
+        .await?;
+
+    tracing::info!("Assigned role {} to user: {}", role, id);
+
+    Ok(user.into())
+}
+
+/// Take a role away from a user
+pub async fn revoke_role<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path((id, role)): Path<(Uuid, String)>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let role: Role = role.parse()?;
+    let user = service
+        .revoke_role(&tenant, &actor, &UserId(id), role, expected_version)
+        .await?;
+
+    tracing::info!("Revoked role {} from user: {}", role, id);
+
+    Ok(user.into())
+}
+
+/// Delete a user
+pub async fn delete_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<StatusCode, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    service
+        .delete(&tenant, &actor, &UserId(id), expected_version)
+        .await?;
+
+    tracing::info!("Deleted user: {}", id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Restore a deleted user (admins only)
+pub async fn restore_user<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<Tagged<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .restore(&tenant, &actor, &UserId(id), expected_version)
+        .await?;
+
+    tracing::info!("Restored user: {}", id);
+
+    Ok(user.into())
+}
+
+/// List the tenant's outbox messages, e.g. `GET /outbox?status=failed`
+pub async fn list_outbox<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Query(query): Query<OutboxQuery>,
+) -> Result<Json<Vec<OutboxMessageResponse>>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<OutboxStatus>)
+        .transpose()?;
+    let messages = service.outbox_messages(&tenant, &actor, status).await?;
+    Ok(Json(messages.into_iter().map(|m| m.into()).collect()))
+}
+
+/// List the audit records of a user, e.g.
+/// `GET /users/{id}/audit?from=2024-01-01T00:00:00Z`
+pub async fn user_audit_log<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Actor(actor): Actor,
+    Path(id): Path<Uuid>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<Vec<AuditRecordResponse>>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let query = AuditQuery {
+        user_id: Some(UserId(id)),
+        from: parse_time(query.from)?,
+        until: parse_time(query.until)?,
+    };
+
+    let records = service.audit_log(&tenant, &actor, &query).await?;
+    Ok(Json(records.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Confirm an email address with a token from a verification link
+pub async fn verify_email<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Json(req): Json<VerifyEmailRequest>,
+) -> Result<Json<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service.verify_email(&tenant, &req.token).await?;
+
+    tracing::info!("Verified email for user: {}", user.id);
+
+    Ok(Json(user.into()))
+}
+
+/// Check an email and password
+pub async fn login<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Json(req): Json<LoginRequest>,
+) -> Result<Json<UserResponse>, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    let user = service
+        .authenticate(&tenant, &req.email, &req.password)
+        .await?;
+    Ok(Json(user.into()))
+}
+
+/// Email a password reset link
+///
+/// Always answers `202 Accepted` so callers cannot probe for accounts.
+pub async fn request_password_reset<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Json(req): Json<PasswordResetRequest>,
+) -> Result<StatusCode, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    service.request_password_reset(&tenant, &req.email).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Set a new password with a reset token
+pub async fn confirm_password_reset<R, E>(
+    State(service): State<Arc<UserService<R, E>>>,
+    Tenant(tenant): Tenant,
+    Json(req): Json<ConfirmPasswordResetRequest>,
+) -> Result<StatusCode, DomainError>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    service
+        .reset_password(&tenant, &req.token, &req.new_password)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,35 +1,88 @@
 //! HTTP adapter
 //!
-//! REST API handlers using axum.
-//! See `examples/web-api` for a complete implementation.
+//! REST API handlers using axum, available behind the `web-api` feature.
 //!
-//! ## Example Handler
+//! The adapter is generic over the domain [`UserService`], so any pair of
+//! repository and email adapters can be exposed over HTTP.
+//!
+//! [`UserService`]: crate::domain::services::UserService
+//!
+//! ## Mounting the User API
 //!
 //! ```rust,ignore
-//! use axum::{extract::State, Json};
 //! use std::sync::Arc;
+//! use axum::{routing::get, Router};
 //!
-//! pub async fn create_user(
-//!     State(service): State<Arc<UserService>>,
-//!     Json(req): Json<CreateUserRequest>,
-//! ) -> Result<Json<UserResponse>, AppError> {
-//!     let user = service.register(&req.email, &req.name).await?;
-//!     Ok(Json(user.into()))
-//! }
-//! ```
+//! let service = Arc::new(UserService::new(repo, email));
 //!
-//! ## Setting Up Routes
+//! let tenants = TenantResolver::new().with_base_domain("example.com");
 //!
-//! ```rust,ignore
-//! use axum::{routing::{get, post}, Router};
-//!
-//! pub fn create_router<S>(service: Arc<UserService>) -> Router {
-//!     Router::new()
-//!         .route("/users", post(create_user))
-//!         .route("/users/:id", get(get_user))
-//!         .with_state(service)
-//! }
+//! let app = Router::new()
+//!     .route("/health", get(health))
+//!     .merge(user_router(service, tenants));
 //! ```
+//!
+//! ## Endpoints
+//!
+//! - `POST /users` - Create a new user (with an optional password)
+//! - `GET /users` - List users a page at a time (admin and support only),
+//!   filtered by `?email_domain=`, `?name_prefix=`, `?created_from=`,
+//!   `?created_until=` and `?status=`, ordered by `?sort=` and `?order=`,
+//!   with `?limit=` users per page and `?cursor=` from the previous page
+//! - `GET /users/export` - Stream every user selected by the `GET /users`
+//!   filters as newline-delimited JSON (admin and support only)
+//! - `GET /users/{id}` - Get a user by ID
+//! - `PATCH /users/{id}` - Rename a user
+//! - `DELETE /users/{id}` - Delete a user, keeping it restorable
+//! - `POST /users/{id}/restore` - Restore a deleted user (admin only)
+//! - `GET /users/{id}/audit` - Audit records of a user, optionally `?from=`/`?until=`
+//! - `POST /users/{id}/activate` - Activate a pending user
+//! - `POST /users/{id}/suspend` - Suspend an active user
+//! - `POST /users/{id}/reactivate` - Lift a suspension
+//! - `POST /users/{id}/deactivate` - Permanently deactivate a user
+//! - `POST /users/{id}/roles` - Grant a role (admin only)
+//! - `DELETE /users/{id}/roles/{role}` - Revoke a role (admin only)
+//! - `POST /users/verify` - Confirm an email address with an emailed token
+//! - `GET /outbox?status=` - List outbox messages (admin and support only;
+//!   requires `UserService::with_outbox`)
+//! - `POST /auth/login` - Check an email and password
+//! - `POST /auth/password-reset` - Email a password reset link
+//! - `POST /auth/password-reset/confirm` - Set a new password with a reset token
+//!
+//! Every endpoint runs within the tenant named by the `X-Tenant-Id` header
+//! or the request's subdomain (see [`TenantResolver`]).
+//!
+//! Everything under `/users/{id}`, `GET /users`, `GET /users/export` and
+//! `GET /outbox` requires HTTP Basic
+//! credentials; users may read, rename and delete their own account, and
+//! roles grant access to other accounts. Missing or wrong credentials yield
+//! `401`, insufficient permissions `403`.
+//!
+//! Single-user responses carry the user's version as an `ETag`. Changes
+//! under `/users/{id}` honour `If-Match` and answer `412` when the user has
+//! changed since; a write racing another one yields `409`.
+//!
+//! Password endpoints require the service to be built with
+//! `UserService::with_password_hasher`, `/users/verify` requires
+//! `UserService::with_email_verification`, and the reset endpoints require
+//! `UserService::with_password_reset`.
+
+mod auth;
+mod dto;
+mod error;
+mod handlers;
+mod precondition;
+mod routes;
+mod tenant;
 
-// HTTP handlers will be implemented in examples/web-api
-// This module provides the structure and documentation
+pub use auth::Actor;
+pub use dto::{
+    AssignRoleRequest, AuditLogQuery, AuditRecordResponse, ConfirmPasswordResetRequest,
+    CreateUserRequest, FieldChangeResponse, LoginRequest, OutboxMessageResponse, OutboxQuery,
+    PasswordResetRequest, SuspendUserRequest, UpdateUserRequest, UserListQuery, UserPageResponse,
+    UserResponse, VerifyEmailRequest,
+};
+pub use error::ErrorResponse;
+pub use precondition::{etag, IfMatch, Tagged};
+pub use routes::user_router;
+pub use tenant::{Tenant, TenantResolver, TENANT_HEADER};
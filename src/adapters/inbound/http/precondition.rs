@@ -0,0 +1,106 @@
+//! Conditional requests
+//!
+//! Responses carry the user's version as a strong `ETag`; changes accept
+//! it back in `If-Match`, so clients cannot overwrite changes they have
+//! not seen.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use super::dto::UserResponse;
+use crate::domain::{entities::User, errors::DomainError};
+
+/// Format `version` as a strong entity tag
+pub fn etag(version: u64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Version required by the request's `If-Match` header
+///
+/// `None` when the header is absent or `*`. Anything but a single strong
+/// entity tag produced by [`etag`] is rejected with `412`, since it can
+/// never match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfMatch(pub Option<u64>);
+
+impl IfMatch {
+    fn parse(value: &HeaderValue) -> Result<Self, DomainError> {
+        let value = value.to_str().unwrap_or_default().trim();
+        if value == "*" {
+            return Ok(Self(None));
+        }
+
+        value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .and_then(|v| v.parse().ok())
+            .map(|version| Self(Some(version)))
+            .ok_or_else(|| {
+                DomainError::precondition_failed(format!("If-Match {} matches no version", value))
+            })
+    }
+}
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get(header::IF_MATCH) {
+            Some(value) => Self::parse(value),
+            None => Ok(Self(None)),
+        }
+    }
+}
+
+/// JSON body sent with an `ETag` for `version`
+#[derive(Debug)]
+pub struct Tagged<T> {
+    pub version: u64,
+    pub body: T,
+}
+
+impl<T: Serialize> IntoResponse for Tagged<T> {
+    fn into_response(self) -> Response {
+        ([(header::ETAG, etag(self.version))], Json(self.body)).into_response()
+    }
+}
+
+impl From<User> for Tagged<UserResponse> {
+    fn from(user: User) -> Self {
+        Self {
+            version: user.version,
+            body: user.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<IfMatch, DomainError> {
+        IfMatch::parse(&HeaderValue::from_str(value).unwrap())
+    }
+
+    #[test]
+    fn test_parse_if_match() {
+        assert_eq!(parse("\"3\"").unwrap(), IfMatch(Some(3)));
+        assert_eq!(parse(&etag(42)).unwrap(), IfMatch(Some(42)));
+        assert_eq!(parse("*").unwrap(), IfMatch(None));
+
+        for value in ["W/\"3\"", "3", "\"3\", \"4\"", "\"abc\""] {
+            assert!(matches!(
+                parse(value),
+                Err(DomainError::PreconditionFailed(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,844 @@
+//! Route definitions
+
+use std::sync::Arc;
+
+use axum::{
+    routing::{delete, get, post},
+    Extension, Router,
+};
+
+use super::handlers;
+use super::tenant::TenantResolver;
+use crate::domain::{
+    ports::{EmailService, UserRepository},
+    services::UserService,
+};
+
+/// Build a router exposing the user API
+///
+/// `tenants` decides which tenant each request runs in. The returned router
+/// has its state applied, so it can be merged or nested into any other
+/// `Router`.
+pub fn user_router<R, E>(service: Arc<UserService<R, E>>, tenants: TenantResolver) -> Router
+where
+    R: UserRepository + 'static,
+    E: EmailService + 'static,
+{
+    Router::new()
+        .route(
+            "/users",
+            post(handlers::create_user::<R, E>).get(handlers::list_users::<R, E>),
+        )
+        .route("/users/export", get(handlers::export_users::<R, E>))
+        .route(
+            "/users/{id}",
+            get(handlers::get_user::<R, E>)
+                .patch(handlers::update_user::<R, E>)
+                .delete(handlers::delete_user::<R, E>),
+        )
+        .route(
+            "/users/{id}/activate",
+            post(handlers::activate_user::<R, E>),
+        )
+        .route("/users/{id}/suspend", post(handlers::suspend_user::<R, E>))
+        .route(
+            "/users/{id}/reactivate",
+            post(handlers::reactivate_user::<R, E>),
+        )
+        .route(
+            "/users/{id}/deactivate",
+            post(handlers::deactivate_user::<R, E>),
+        )
+        .route("/users/{id}/restore", post(handlers::restore_user::<R, E>))
+        .route("/users/{id}/audit", get(handlers::user_audit_log::<R, E>))
+        .route("/users/{id}/roles", post(handlers::assign_role::<R, E>))
+        .route(
+            "/users/{id}/roles/{role}",
+            delete(handlers::revoke_role::<R, E>),
+        )
+        .route("/users/verify", post(handlers::verify_email::<R, E>))
+        .route("/outbox", get(handlers::list_outbox::<R, E>))
+        .route("/auth/login", post(handlers::login::<R, E>))
+        .route(
+            "/auth/password-reset",
+            post(handlers::request_password_reset::<R, E>),
+        )
+        .route(
+            "/auth/password-reset/confirm",
+            post(handlers::confirm_password_reset::<R, E>),
+        )
+        .layer(Extension(tenants))
+        .with_state(service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::inbound::http::{
+        AuditRecordResponse, ErrorResponse, OutboxMessageResponse, UserPageResponse, UserResponse,
+        TENANT_HEADER,
+    };
+    use crate::adapters::outbound::{
+        external::ConsoleEmailService,
+        persistence::InMemoryUserRepository,
+        persistence::{InMemoryAuditLog, InMemoryVerificationTokenRepository},
+        security::{FakePasswordHasher, HmacTokenSigner},
+    };
+    use crate::domain::entities::{DisplayName, Principal, Role, TenantId};
+    use crate::domain::services::{EmailVerification, PasswordReset};
+    use axum::{
+        body::{Body, Bytes},
+        http::{header, Method, Request, StatusCode},
+    };
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::de::DeserializeOwned;
+    use tower::ServiceExt;
+
+    const ADMIN: (&str, &str) = ("admin@example.com", "admin password");
+
+    /// Router over fresh in-memory storage, with [`ADMIN`] already registered
+    /// in the default tenant
+    async fn router() -> Router {
+        let repository = Arc::new(InMemoryUserRepository::new());
+        let service = UserService::new(repository.clone(), Arc::new(ConsoleEmailService::new()))
+            .with_outbox(repository.clone())
+            .with_audit_log(Arc::new(InMemoryAuditLog::new()))
+            .with_password_hasher(Arc::new(FakePasswordHasher::new()))
+            .with_email_verification(EmailVerification {
+                tokens: Arc::new(InMemoryVerificationTokenRepository::new()),
+                signer: Arc::new(HmacTokenSigner::new("secret")),
+                link_base_url: "http://localhost/verify".to_string(),
+                token_ttl: chrono::Duration::hours(24),
+            })
+            .with_password_reset(PasswordReset {
+                tokens: repository,
+                signer: Arc::new(HmacTokenSigner::new("secret")),
+                token_ttl: chrono::Duration::minutes(30),
+            });
+
+        let admin = service
+            .register_with_password(
+                &TenantId::default(),
+                ADMIN.0,
+                DisplayName::new("Admin").unwrap(),
+                ADMIN.1,
+            )
+            .await
+            .unwrap();
+        service
+            .assign_role(
+                &TenantId::default(),
+                &Principal::System,
+                &admin.id,
+                Role::Admin,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let tenants = TenantResolver::new()
+            .with_base_domain("example.com")
+            .with_fallback(TenantId::default());
+        user_router(Arc::new(service), tenants)
+    }
+
+    /// Send a request authenticated as [`ADMIN`]
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, Bytes) {
+        send_as(app, Some(ADMIN), method, uri, body).await
+    }
+
+    /// Send a request in the default tenant
+    async fn send_as(
+        app: &Router,
+        credentials: Option<(&str, &str)>,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, Bytes) {
+        send_request(app, None, credentials, method, uri, body).await
+    }
+
+    /// Send a request naming `tenant` in the tenant header
+    async fn send_in(
+        app: &Router,
+        tenant: &str,
+        credentials: Option<(&str, &str)>,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, Bytes) {
+        send_request(app, Some(tenant), credentials, method, uri, body).await
+    }
+
+    async fn send_request(
+        app: &Router,
+        tenant: Option<&str>,
+        credentials: Option<(&str, &str)>,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, Bytes) {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(tenant) = tenant {
+            builder = builder.header(TENANT_HEADER, tenant);
+        }
+        if let Some((email, password)) = credentials {
+            let encoded = STANDARD.encode(format!("{}:{}", email, password));
+            builder = builder.header(header::AUTHORIZATION, format!("Basic {}", encoded));
+        }
+        let body = match body {
+            Some(json) => {
+                builder = builder.header(header::CONTENT_TYPE, "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+
+        let response = app
+            .clone()
+            .oneshot(builder.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, bytes)
+    }
+
+    fn parse<T: DeserializeOwned>(bytes: &[u8]) -> T {
+        serde_json::from_slice(bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_user() {
+        let app = router().await;
+
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "test@example.com", "name": "Test User"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let created: UserResponse = parse(&body);
+
+        let (status, body) = send(&app, Method::GET, &format!("/users/{}", created.id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let found: UserResponse = parse(&body);
+        assert_eq!(found.email, "test@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_list_users_filters_and_pages() {
+        let app = router().await;
+        for (email, name) in [
+            ("carol@acme.test", "Carol"),
+            ("alice@acme.test", "Alice"),
+            ("bob@acme.test", "Bob"),
+            ("dave@other.test", "Dave"),
+        ] {
+            send(
+                &app,
+                Method::POST,
+                "/users",
+                Some(serde_json::json!({"email": email, "name": name})),
+            )
+            .await;
+        }
+
+        let (status, body) = send(
+            &app,
+            Method::GET,
+            "/users?email_domain=acme.test&sort=name&limit=2",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let first: UserPageResponse = parse(&body);
+        let names: Vec<_> = first.items.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!((names, first.total), (vec!["Alice", "Bob"], 3));
+
+        let uri = format!(
+            "/users?email_domain=acme.test&sort=name&limit=2&cursor={}",
+            first.next_cursor.unwrap()
+        );
+        let (_, body) = send(&app, Method::GET, &uri, None).await;
+        let second: UserPageResponse = parse(&body);
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].name, "Carol");
+        assert!(second.next_cursor.is_none());
+
+        let (status, _) = send(&app, Method::GET, "/users?sort=age", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_export_users_as_ndjson() {
+        let app = router().await;
+        for i in 0..3 {
+            send(
+                &app,
+                Method::POST,
+                "/users",
+                Some(serde_json::json!({"email": format!("u{}@acme.test", i), "name": "U"})),
+            )
+            .await;
+        }
+
+        let (status, body) = send(
+            &app,
+            Method::GET,
+            "/users/export?email_domain=acme.test&sort=email&limit=2",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let emails: Vec<String> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| parse::<UserResponse>(line.as_bytes()).email)
+            .collect();
+        assert_eq!(emails, ["u0@acme.test", "u1@acme.test", "u2@acme.test"]);
+
+        let (status, _) = send(&app, Method::GET, "/users/export?limit=0", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_duplicate_returns_conflict() {
+        let app = router().await;
+        let body = serde_json::json!({"email": "test@example.com", "name": "Test User"});
+
+        send(&app, Method::POST, "/users", Some(body.clone())).await;
+        let (status, body) = send(&app, Method::POST, "/users", Some(body)).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(parse::<ErrorResponse>(&body).error, "Conflict");
+    }
+
+    #[tokio::test]
+    async fn test_create_invalid_email_returns_bad_request() {
+        let app = router().await;
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "invalid", "name": "Test User"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_invalid_name_is_rejected() {
+        let app = router().await;
+
+        // Display names are validated while the body is deserialized
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "test@example.com", "name": "Ada\u{202E}"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_user() {
+        let app = router().await;
+
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "test@example.com", "name": "Old Name"})),
+        )
+        .await;
+        let created: UserResponse = parse(&body);
+        let uri = format!("/users/{}", created.id);
+
+        let (status, body) = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(serde_json::json!({"name": "New Name"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(parse::<UserResponse>(&body).name, "New Name");
+
+        let (status, _) = send(&app, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, _) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = send(&app, Method::POST, &format!("{}/restore", uri), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(parse::<UserResponse>(&body).name, "New Name");
+
+        let (status, _) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(&app, Method::POST, &format!("{}/restore", uri), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_status_transitions() {
+        let app = router().await;
+
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "test@example.com", "name": "Test User"})),
+        )
+        .await;
+        let created: UserResponse = parse(&body);
+        assert_eq!(created.status, "pending");
+        let uri = format!("/users/{}", created.id);
+
+        let suspend = serde_json::json!({"reason": "Spam"});
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            &format!("{}/suspend", uri),
+            Some(suspend.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            parse::<ErrorResponse>(&body).error,
+            "Business rule violation"
+        );
+
+        let (status, _) = send(&app, Method::POST, &format!("{}/activate", uri), None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            &format!("{}/suspend", uri),
+            Some(suspend),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let suspended: UserResponse = parse(&body);
+        assert_eq!(suspended.status, "suspended");
+        assert_eq!(suspended.suspension_reason.as_deref(), Some("Spam"));
+
+        let (_, body) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(parse::<UserResponse>(&body).status, "suspended");
+    }
+
+    #[tokio::test]
+    async fn test_register_with_password_and_login() {
+        let app = router().await;
+
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({
+                "email": "test@example.com",
+                "name": "Test User",
+                "password": "correct horse"
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!text.contains("password"));
+        assert!(!text.contains("correct horse"));
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/auth/login",
+            Some(serde_json::json!({"email": "test@example.com", "password": "correct horse"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/auth/login",
+            Some(serde_json::json!({"email": "test@example.com", "password": "wrong horse"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_invalid_token() {
+        let app = router().await;
+
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            "/users/verify",
+            Some(serde_json::json!({"token": "not-a-token"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(parse::<ErrorResponse>(&body).error, "Validation error");
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_does_not_reveal_accounts() {
+        let app = router().await;
+
+        for email in [ADMIN.0, "nobody@example.com"] {
+            let (status, _) = send(
+                &app,
+                Method::POST,
+                "/auth/password-reset",
+                Some(serde_json::json!({ "email": email })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::ACCEPTED);
+        }
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/auth/password-reset/confirm",
+            Some(serde_json::json!({"token": "bogus", "new_password": "new password"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_protected_routes_require_credentials() {
+        let app = router().await;
+
+        let (status, _) = send_as(&app, None, Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = send_as(
+            &app,
+            Some((ADMIN.0, "wrong password")),
+            Method::GET,
+            "/users",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_members_act_only_on_themselves() {
+        let app = router().await;
+        let member = ("member@example.com", "member password");
+
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": member.0, "name": "Member", "password": member.1})),
+        )
+        .await;
+        let own: UserResponse = parse(&body);
+        assert_eq!(own.roles, vec!["member"]);
+
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "other@example.com", "name": "Other"})),
+        )
+        .await;
+        let other: UserResponse = parse(&body);
+
+        let (status, _) = send_as(&app, Some(member), Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let other_uri = format!("/users/{}", other.id);
+        let (status, body) = send_as(&app, Some(member), Method::DELETE, &other_uri, None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(parse::<ErrorResponse>(&body).error, "Forbidden");
+
+        let own_uri = format!("/users/{}", own.id);
+        let (status, _) = send_as(&app, Some(member), Method::GET, &own_uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Once promoted, the member may list everyone
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            &format!("{}/roles", own_uri),
+            Some(serde_json::json!({"role": "support"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(parse::<UserResponse>(&body)
+            .roles
+            .contains(&"support".to_string()));
+
+        let (status, _) = send_as(&app, Some(member), Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(
+            &app,
+            Method::DELETE,
+            &format!("{}/roles/support", own_uri),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send_as(&app, Some(member), Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tenants_are_isolated() {
+        let app = router().await;
+        let body = serde_json::json!({
+            "email": "test@example.com",
+            "name": "Test User",
+            "password": "tenant password"
+        });
+
+        // The same email may register once per tenant
+        let (status, _) = send_in(
+            &app,
+            "acme",
+            None,
+            Method::POST,
+            "/users",
+            Some(body.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send_in(
+            &app,
+            "globex",
+            None,
+            Method::POST,
+            "/users",
+            Some(body.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send_in(&app, "acme", None, Method::POST, "/users", Some(body)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // Credentials only work in their own tenant
+        let credentials = Some(("test@example.com", "tenant password"));
+        let (status, _) = send_in(
+            &app,
+            "acme",
+            credentials,
+            Method::POST,
+            "/auth/login",
+            Some(serde_json::json!({"email": "test@example.com", "password": "tenant password"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send_as(&app, Some(ADMIN), Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send_in(&app, "acme", Some(ADMIN), Method::GET, "/users", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // The tenant may also come from the subdomain
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/auth/login")
+            .header(header::HOST, "globex.example.com")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::json!({"email": "test@example.com", "password": "tenant password"})
+                    .to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (status, body) = send_in(
+            &app,
+            "not a tenant",
+            None,
+            Method::POST,
+            "/auth/login",
+            Some(serde_json::json!({"email": "a@b.c", "password": "x"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(parse::<ErrorResponse>(&body).error, "Validation error");
+    }
+
+    #[tokio::test]
+    async fn test_list_outbox() {
+        let app = router().await;
+        let member = ("member@example.com", "member password");
+        send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": member.0, "name": "Member", "password": member.1})),
+        )
+        .await;
+
+        let (status, body) = send(&app, Method::GET, "/outbox?status=pending", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let messages: Vec<OutboxMessageResponse> = parse(&body);
+        assert!(messages.iter().any(|m| m.event == "UserRegistered"));
+        assert!(messages.iter().all(|m| m.status == "pending"));
+
+        let (status, body) = send(&app, Method::GET, "/outbox?status=failed", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(parse::<Vec<OutboxMessageResponse>>(&body).is_empty());
+
+        let (status, _) = send(&app, Method::GET, "/outbox?status=stuck", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send_as(&app, Some(member), Method::GET, "/outbox", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    /// Send a request as [`ADMIN`] with an optional `If-Match` header,
+    /// returning the status and the `ETag` of the response
+    async fn send_if_match(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        if_match: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, Option<String>) {
+        let encoded = STANDARD.encode(format!("{}:{}", ADMIN.0, ADMIN.1));
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Basic {}", encoded))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(tag) = if_match {
+            builder = builder.header(header::IF_MATCH, tag);
+        }
+        let body = body.map_or_else(Body::empty, |json| Body::from(json.to_string()));
+
+        let response = app
+            .clone()
+            .oneshot(builder.body(body).unwrap())
+            .await
+            .unwrap();
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .map(|value| value.to_str().unwrap().to_string());
+        (response.status(), etag)
+    }
+
+    #[tokio::test]
+    async fn test_conditional_updates() {
+        let app = router().await;
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": "test@example.com", "name": "Old Name"})),
+        )
+        .await;
+        let created: UserResponse = parse(&body);
+        assert_eq!(created.version, 1);
+        let uri = format!("/users/{}", created.id);
+
+        let (status, tag) = send_if_match(&app, Method::GET, &uri, None, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(tag.as_deref(), Some("\"1\""));
+
+        let rename = serde_json::json!({"name": "New Name"});
+        let (status, _) = send_if_match(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some("\"0\""),
+            Some(rename.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+        let (status, tag) =
+            send_if_match(&app, Method::PATCH, &uri, Some("\"1\""), Some(rename)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(tag.as_deref(), Some("\"2\""));
+
+        let (status, _) = send_if_match(&app, Method::DELETE, &uri, Some("\"1\""), None).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let (status, _) = send_if_match(&app, Method::DELETE, &uri, Some("*"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_user_audit_log() {
+        let app = router().await;
+        let member = ("member@example.com", "member password");
+        let (_, body) = send(
+            &app,
+            Method::POST,
+            "/users",
+            Some(serde_json::json!({"email": member.0, "name": "Old Name", "password": member.1})),
+        )
+        .await;
+        let created: UserResponse = parse(&body);
+        let uri = format!("/users/{}", created.id);
+        send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(serde_json::json!({"name": "New Name"})),
+        )
+        .await;
+
+        let (status, body) = send(&app, Method::GET, &format!("{}/audit", uri), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let records: Vec<AuditRecordResponse> = parse(&body);
+        let actions: Vec<_> = records.iter().map(|r| r.action.as_str()).collect();
+        assert_eq!(actions, ["register", "update_name"]);
+        assert_eq!(records[0].actor, "anonymous");
+        assert_eq!(records[1].actor, "user");
+        let rename = &records[1].changes[0];
+        assert_eq!(rename.field, "name");
+        assert_eq!(rename.after, Some(serde_json::json!("New Name")));
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!text.contains("$argon2") && !text.contains(member.1));
+
+        let since = format!("{}/audit?from={}", uri, "2999-01-01T00:00:00Z");
+        let (_, body) = send(&app, Method::GET, &since, None).await;
+        assert!(parse::<Vec<AuditRecordResponse>>(&body).is_empty());
+
+        let (status, _) = send(
+            &app,
+            Method::GET,
+            &format!("{}/audit?from=yesterday", uri),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send_as(
+            &app,
+            Some(member),
+            Method::GET,
+            &format!("{}/audit", uri),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
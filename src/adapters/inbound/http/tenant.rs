@@ -0,0 +1,182 @@
+//! Tenant resolution
+//!
+//! Every user endpoint runs within a tenant taken from the request: an
+//! explicit header wins, then the subdomain of the `Host` header.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderName},
+};
+
+use crate::domain::{entities::TenantId, errors::DomainError};
+
+/// Default header naming the tenant
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Maps requests to tenants
+///
+/// ```rust,ignore
+/// // `acme.example.com` → tenant `acme`; `X-Tenant-Id: acme` also works
+/// let tenants = TenantResolver::new()
+///     .with_base_domain("example.com")
+///     .with_fallback(TenantId::default());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TenantResolver {
+    header: HeaderName,
+    base_domain: Option<String>,
+    fallback: Option<TenantId>,
+}
+
+impl TenantResolver {
+    /// Resolve tenants from the [`TENANT_HEADER`] header only
+    pub fn new() -> Self {
+        Self {
+            header: HeaderName::from_static(TENANT_HEADER),
+            base_domain: None,
+            fallback: None,
+        }
+    }
+
+    /// Read the tenant from `header` instead of [`TENANT_HEADER`]
+    pub fn with_header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Also accept `<tenant>.<base_domain>` hosts
+    pub fn with_base_domain(mut self, base_domain: impl Into<String>) -> Self {
+        self.base_domain = Some(base_domain.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Use `tenant` for requests that name none, instead of rejecting them
+    pub fn with_fallback(mut self, tenant: TenantId) -> Self {
+        self.fallback = Some(tenant);
+        self
+    }
+
+    /// Determine the tenant of a request from its headers
+    ///
+    /// # Errors
+    ///
+    /// `ValidationError` if the named tenant is malformed, or no tenant is
+    /// named and there is no fallback.
+    pub fn resolve(&self, headers: &HeaderMap) -> Result<TenantId, DomainError> {
+        if let Some(value) = headers.get(&self.header) {
+            let value = value
+                .to_str()
+                .map_err(|_| DomainError::validation("Malformed tenant header"))?;
+            return TenantId::new(value);
+        }
+
+        if let Some(subdomain) = self.subdomain(headers) {
+            return TenantId::new(subdomain);
+        }
+
+        self.fallback
+            .clone()
+            .ok_or_else(|| DomainError::validation("Request does not name a tenant"))
+    }
+
+    /// Leftmost label of a `<label>.<base_domain>` host
+    fn subdomain(&self, headers: &HeaderMap) -> Option<String> {
+        let base_domain = self.base_domain.as_deref()?;
+        let host = headers.get(header::HOST)?.to_str().ok()?;
+        let host = host.split(':').next()?.to_ascii_lowercase();
+
+        let label = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+        (!label.is_empty() && !label.contains('.')).then(|| label.to_string())
+    }
+}
+
+impl Default for TenantResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The tenant a request runs in
+///
+/// Requires a [`TenantResolver`] request extension, which
+/// [`user_router`](super::user_router) installs.
+pub struct Tenant(pub TenantId);
+
+impl<S> FromRequestParts<S> for Tenant
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let resolver = parts.extensions.get::<TenantResolver>().ok_or_else(|| {
+            DomainError::Infrastructure(anyhow::anyhow!("No tenant resolver installed"))
+        })?;
+
+        resolver.resolve(&parts.headers).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn test_header_takes_precedence() {
+        let resolver = TenantResolver::new().with_base_domain("example.com");
+
+        let tenant = resolver
+            .resolve(&headers(&[
+                ("x-tenant-id", "acme"),
+                ("host", "globex.example.com"),
+            ]))
+            .unwrap();
+        assert_eq!(tenant.as_str(), "acme");
+    }
+
+    #[test]
+    fn test_subdomain() {
+        let resolver = TenantResolver::new().with_base_domain("example.com");
+
+        let tenant = resolver
+            .resolve(&headers(&[("host", "Globex.example.com:3000")]))
+            .unwrap();
+        assert_eq!(tenant.as_str(), "globex");
+
+        assert!(resolver
+            .resolve(&headers(&[("host", "example.com")]))
+            .is_err());
+        assert!(resolver
+            .resolve(&headers(&[("host", "a.b.example.com")]))
+            .is_err());
+        assert!(resolver
+            .resolve(&headers(&[("host", "globexexample.com")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fallback() {
+        let strict = TenantResolver::new();
+        assert!(matches!(
+            strict.resolve(&HeaderMap::new()),
+            Err(DomainError::ValidationError(_))
+        ));
+
+        let lenient = TenantResolver::new().with_fallback(TenantId::default());
+        assert_eq!(
+            lenient.resolve(&HeaderMap::new()).unwrap(),
+            TenantId::default()
+        );
+    }
+}
@@ -5,10 +5,11 @@
 //!
 //! ## Examples
 //!
-//! - **HTTP**: REST API handlers (see `examples/web-api`)
+//! - **HTTP**: REST API handlers (axum, behind the `web-api` feature)
 //! - **CLI**: Command-line interface (see `examples/cli-tool`)
 //! - **gRPC**: RPC service handlers
 //! - **Message Queue**: Event consumers
 
 pub mod cli;
+#[cfg(feature = "web-api")]
 pub mod http;
@@ -0,0 +1,279 @@
+//! In-process event bus
+
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::domain::{
+    errors::DomainError,
+    events::{DomainEvent, Event},
+    ports::{EventHandler, EventPublisher},
+};
+
+/// A subscriber with its event type erased
+#[async_trait]
+trait Subscriber: Send + Sync {
+    async fn deliver(&self, event: &DomainEvent) -> Result<(), DomainError>;
+}
+
+/// Forwards matching events to a typed handler
+struct Typed<E: Event> {
+    handler: Arc<dyn EventHandler<E>>,
+    _event: PhantomData<fn() -> E>,
+}
+
+#[async_trait]
+impl<E: Event> Subscriber for Typed<E> {
+    async fn deliver(&self, event: &DomainEvent) -> Result<(), DomainError> {
+        match E::from_domain(event) {
+            Some(event) => self.handler.handle(event).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Collects subscribers for an [`InProcessEventBus`]
+#[derive(Default)]
+pub struct EventBusBuilder {
+    subscribers: Vec<Arc<dyn Subscriber>>,
+}
+
+impl EventBusBuilder {
+    /// Deliver every event of type `E` to `handler`
+    pub fn subscribe<E: Event>(mut self, handler: Arc<dyn EventHandler<E>>) -> Self {
+        self.subscribers.push(Arc::new(Typed {
+            handler,
+            _event: PhantomData,
+        }));
+        self
+    }
+
+    /// Build a dispatcher delivering on the caller's task instead
+    pub fn dispatcher(self) -> EventDispatcher {
+        EventDispatcher {
+            subscribers: self.subscribers,
+        }
+    }
+
+    /// Spawn the delivery task and return the running bus
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn start(self) -> InProcessEventBus {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Vec<DomainEvent>>();
+        let subscribers = self.subscribers;
+
+        let worker = tokio::spawn(async move {
+            while let Some(events) = receiver.recv().await {
+                for event in &events {
+                    for subscriber in &subscribers {
+                        if let Err(e) = subscriber.deliver(event).await {
+                            tracing::warn!("Subscriber failed to handle {}: {}", event.name(), e);
+                        }
+                    }
+                }
+            }
+        });
+
+        InProcessEventBus {
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+        }
+    }
+}
+
+/// Event bus delivering to subscribers on a background task
+///
+/// Publishing only enqueues, so slow subscribers never hold up the caller.
+/// Events are delivered in publication order, one subscriber at a time;
+/// a failing subscriber is logged and does not affect the others.
+pub struct InProcessEventBus {
+    sender: Mutex<Option<mpsc::UnboundedSender<Vec<DomainEvent>>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl InProcessEventBus {
+    /// Start describing a bus
+    pub fn builder() -> EventBusBuilder {
+        EventBusBuilder::default()
+    }
+
+    /// Stop accepting events and wait until queued ones are delivered
+    pub async fn shutdown(&self) {
+        drop(self.sender.lock().ok().and_then(|mut s| s.take()));
+
+        let worker = self.worker.lock().ok().and_then(|mut w| w.take());
+        if let Some(worker) = worker {
+            if let Err(e) = worker.await {
+                tracing::error!("Event bus worker failed: {}", e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for InProcessEventBus {
+    async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), DomainError> {
+        let sender = self
+            .sender
+            .lock()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+
+        sender
+            .as_ref()
+            .and_then(|sender| sender.send(events).ok())
+            .ok_or_else(|| DomainError::Infrastructure(anyhow::anyhow!("Event bus is shut down")))
+    }
+}
+
+/// Delivers events to subscribers before `publish` returns
+///
+/// Every subscriber receives every event even if another fails; `publish`
+/// then reports the failure, so callers such as an outbox relay can retry.
+/// A retry reaches the successful subscribers again.
+pub struct EventDispatcher {
+    subscribers: Vec<Arc<dyn Subscriber>>,
+}
+
+#[async_trait]
+impl EventPublisher for EventDispatcher {
+    async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), DomainError> {
+        let mut failures = Vec::new();
+        for event in &events {
+            for subscriber in &self.subscribers {
+                if let Err(e) = subscriber.deliver(event).await {
+                    failures.push(format!("{}: {}", event.name(), e));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::Infrastructure(anyhow::anyhow!(
+                "Subscribers failed: {}",
+                failures.join("; ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DisplayName, TenantId, User};
+    use crate::domain::events::{UserRegistered, UserRenamed};
+    use chrono::Utc;
+
+    /// Records the names of handled events
+    struct Recorder {
+        seen: Mutex<Vec<String>>,
+        fail: bool,
+    }
+
+    impl Recorder {
+        fn new(fail: bool) -> Arc<Self> {
+            Arc::new(Self {
+                seen: Mutex::new(Vec::new()),
+                fail,
+            })
+        }
+
+        fn seen(&self) -> Vec<String> {
+            self.seen.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EventHandler<UserRegistered> for Recorder {
+        async fn handle(&self, event: &UserRegistered) -> Result<(), DomainError> {
+            self.seen.lock().unwrap().push(event.name.to_string());
+            if self.fail {
+                return Err(DomainError::validation("boom"));
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl EventHandler<DomainEvent> for Recorder {
+        async fn handle(&self, event: &DomainEvent) -> Result<(), DomainError> {
+            self.seen.lock().unwrap().push(event.name().to_string());
+            Ok(())
+        }
+    }
+
+    fn events() -> Vec<DomainEvent> {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.update_name(DisplayName::new("Grace").unwrap(), Utc::now());
+        user.take_events()
+    }
+
+    #[tokio::test]
+    async fn test_delivers_to_typed_subscribers() {
+        let registrations = Recorder::new(false);
+        let everything = Recorder::new(false);
+
+        let bus = InProcessEventBus::builder()
+            .subscribe::<UserRegistered>(registrations.clone())
+            .subscribe::<DomainEvent>(everything.clone())
+            .start();
+
+        bus.publish(events()).await.unwrap();
+        bus.shutdown().await;
+
+        assert_eq!(registrations.seen(), ["Test User"]);
+        assert_eq!(everything.seen(), ["UserRegistered", "UserRenamed"]);
+    }
+
+    #[tokio::test]
+    async fn test_failing_subscriber_does_not_block_others() {
+        let failing = Recorder::new(true);
+        let others = Recorder::new(false);
+
+        let bus = InProcessEventBus::builder()
+            .subscribe::<UserRegistered>(failing.clone())
+            .subscribe::<UserRegistered>(others.clone())
+            .start();
+
+        bus.publish(events()).await.unwrap();
+        bus.publish(events()).await.unwrap();
+        bus.shutdown().await;
+
+        assert_eq!(failing.seen().len(), 2);
+        assert_eq!(others.seen().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_after_shutdown_fails() {
+        let bus = InProcessEventBus::builder().start();
+        bus.shutdown().await;
+
+        let event: DomainEvent = UserRenamed {
+            user_id: crate::domain::entities::UserId::new(),
+            tenant_id: TenantId::default(),
+            old_name: DisplayName::new("a").unwrap(),
+            new_name: DisplayName::new("b").unwrap(),
+            occurred_at: chrono::Utc::now(),
+        }
+        .into();
+        assert!(bus.publish(vec![event]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_reports_failures_after_delivering_to_all() {
+        let failing = Recorder::new(true);
+        let others = Recorder::new(false);
+
+        let dispatcher = InProcessEventBus::builder()
+            .subscribe::<UserRegistered>(failing.clone())
+            .subscribe::<UserRegistered>(others.clone())
+            .dispatcher();
+
+        assert!(dispatcher.publish(events()).await.is_err());
+        assert_eq!(failing.seen(), ["Test User"]);
+        assert_eq!(others.seen(), ["Test User"]);
+    }
+}
@@ -0,0 +1,28 @@
+//! Event publishing adapters
+//!
+//! Implementations of the `EventPublisher` port.
+//!
+//! ## Bundled Implementations
+//!
+//! - [`InProcessEventBus`]: delivers events to typed subscribers on a
+//!   background task within the same process
+//! - [`EventDispatcher`]: delivers to the same subscribers before `publish`
+//!   returns and reports their failures, for use with an `OutboxRelay`
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! let bus = Arc::new(
+//!     InProcessEventBus::builder()
+//!         .subscribe::<UserRegistered>(Arc::new(WelcomeEmail::new(email.clone())))
+//!         .start(),
+//! );
+//! let service = UserService::new(repo, email).with_event_publisher(bus.clone());
+//!
+//! // ... on shutdown, deliver whatever is still queued
+//! bus.shutdown().await;
+//! ```
+
+mod in_process;
+
+pub use in_process::{EventBusBuilder, EventDispatcher, InProcessEventBus};
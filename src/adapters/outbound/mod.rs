@@ -9,6 +9,10 @@
 //! - **external**: External API clients (payment gateways, etc.)
 //! - **cache**: Caching implementations (Redis, in-memory)
 //! - **email**: Email service implementations (SendGrid, SMTP)
+//! - **security**: Password hashing implementations (Argon2)
+//! - **events**: Domain event publishing (in-process bus)
 
+pub mod events;
 pub mod external;
 pub mod persistence;
+pub mod security;
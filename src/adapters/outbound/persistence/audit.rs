@@ -0,0 +1,221 @@
+//! Audit log implementations
+//!
+//! Back the `AuditLog` port in memory or with an append-only JSONL file.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::{AuditQuery, AuditRecord, TenantId},
+    errors::DomainError,
+    ports::AuditLog,
+};
+
+/// Records of `tenant` selected by `query`
+fn select<'a>(
+    records: impl IntoIterator<Item = &'a AuditRecord>,
+    tenant: &TenantId,
+    query: &AuditQuery,
+) -> Vec<AuditRecord> {
+    records
+        .into_iter()
+        .filter(|record| &record.tenant_id == tenant && query.matches(record))
+        .cloned()
+        .collect()
+}
+
+/// In-memory audit log for testing and development
+#[derive(Default)]
+pub struct InMemoryAuditLog {
+    records: RwLock<Vec<AuditRecord>>,
+}
+
+impl InMemoryAuditLog {
+    /// Create a new empty audit log
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuditLog for InMemoryAuditLog {
+    async fn append(&self, record: &AuditRecord) -> Result<(), DomainError> {
+        let mut records = self
+            .records
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        records.push(record.clone());
+        Ok(())
+    }
+
+    async fn query(
+        &self,
+        tenant: &TenantId,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditRecord>, DomainError> {
+        let records = self
+            .records
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(select(records.iter(), tenant, query))
+    }
+}
+
+/// File-based audit log
+///
+/// Appends one JSON record per line and never rewrites the file, so
+/// earlier records survive crashes and stay untouched. Queries read the
+/// whole file.
+pub struct FileAuditLog {
+    file_path: PathBuf,
+    append_lock: Mutex<()>,
+}
+
+impl FileAuditLog {
+    /// Open an audit log at `file_path`; the file is created on first append
+    pub fn new(file_path: impl Into<PathBuf>) -> Self {
+        Self {
+            file_path: file_path.into(),
+            append_lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// Every record, naming the offending line on parse errors
+    fn load(&self) -> Result<Vec<AuditRecord>, DomainError> {
+        let path = &self.file_path;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!("Failed to read {}: {}", path.display(), e))
+        })?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    DomainError::Infrastructure(anyhow::anyhow!(
+                        "Failed to parse {} line {}: {}",
+                        path.display(),
+                        number + 1,
+                        e
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AuditLog for FileAuditLog {
+    async fn append(&self, record: &AuditRecord) -> Result<(), DomainError> {
+        let mut line =
+            serde_json::to_string(record).map_err(|e| DomainError::Infrastructure(e.into()))?;
+        line.push('\n');
+
+        let _guard = self
+            .append_lock
+            .lock()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!(
+                    "Failed to write {}: {}",
+                    self.file_path.display(),
+                    e
+                ))
+            })
+    }
+
+    async fn query(
+        &self,
+        tenant: &TenantId,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditRecord>, DomainError> {
+        Ok(select(&self.load()?, tenant, query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{AuditActor, FieldChange, User};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn record(tenant: &TenantId, action: &str) -> AuditRecord {
+        let user = User::fixture(tenant.clone(), "test@example.com");
+        AuditRecord::new(
+            Uuid::now_v7(),
+            &user,
+            AuditActor::System,
+            action,
+            FieldChange::between(None, Some(&user)),
+            Utc::now(),
+        )
+    }
+
+    async fn check_log(log: &dyn AuditLog) {
+        let tenant = TenantId::default();
+        let first = record(&tenant, "register");
+        let second = record(&tenant, "register");
+        let foreign = record(&TenantId::new("other").unwrap(), "register");
+        for record in [&first, &second, &foreign] {
+            log.append(record).await.unwrap();
+        }
+
+        let all = log.query(&tenant, &AuditQuery::default()).await.unwrap();
+        assert_eq!(all, [first.clone(), second.clone()]);
+
+        let by_user = log
+            .query(&tenant, &AuditQuery::for_user(second.user_id))
+            .await
+            .unwrap();
+        assert_eq!(by_user.len(), 1);
+        assert_eq!(by_user[0], second);
+
+        let since_second = AuditQuery {
+            from: Some(second.occurred_at),
+            ..AuditQuery::default()
+        };
+        let found = log.query(&tenant, &since_second).await.unwrap();
+        assert_eq!(found.last(), Some(&second));
+        assert!(found.iter().all(|r| r.occurred_at >= second.occurred_at));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_log() {
+        check_log(&InMemoryAuditLog::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_log() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", Uuid::new_v4()));
+        check_log(&FileAuditLog::new(&path)).await;
+
+        // A reopened log sees the same records
+        let reopened = FileAuditLog::new(&path);
+        let records = reopened
+            .query(&TenantId::default(), &AuditQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 2);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,402 @@
+//! Tests every user repository must pass
+//!
+//! [`user_repository_tests!`] expands to one test per function below, each
+//! run against a fresh repository built by the given expression.
+
+use chrono::{Duration, Utc};
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        DisplayName, Email, OutboxMessage, OutboxStatus, PasswordResetToken, TenantId, User,
+        UserId, UserStatus,
+    },
+    errors::DomainError,
+    events::{DomainEvent, PasswordResetRequested},
+    ports::{
+        OutboxRepository, PasswordResetTokenRepository, Repository, UserQuery, UserRepository,
+    },
+};
+
+/// Generate the shared user repository tests for the repository `$repo`,
+/// giving each test the attributes that follow it
+///
+/// `$repo` may also be a guard that owns the repository and cleans up
+/// after it, through [`Fixture`]. Prefix it with `outbox:` to also run the
+/// tests of repositories that are an [`OutboxRepository`] too.
+macro_rules! user_repository_tests {
+    (outbox: $repo:expr $(, #[$attr:meta])* $(,)?) => {
+        $crate::adapters::outbound::persistence::conformance::user_repository_tests!(
+            $repo $(, #[$attr])*
+        );
+        $crate::adapters::outbound::persistence::conformance::user_repository_tests!(
+            @tests [$(#[$attr])*] $repo;
+            test_outbox_written_with_user
+        );
+    };
+    ($repo:expr $(, #[$attr:meta])* $(,)?) => {
+        $crate::adapters::outbound::persistence::conformance::user_repository_tests!(
+            @tests [$(#[$attr])*] $repo;
+            test_save_and_find_by_id,
+            test_find_by_email,
+            test_delete,
+            test_deleted_users_are_hidden,
+            test_list,
+            test_status_is_persisted,
+            test_tenants_are_isolated,
+            test_pending_events_are_not_stored,
+            test_stale_save_is_rejected,
+            test_query_pages_live_users_of_tenant,
+            test_stream_yields_every_user_in_chunks,
+            test_batch_operations
+        );
+    };
+    (@tests $attrs:tt $repo:expr; $($name:ident),* $(,)?) => {
+        $(
+            $crate::adapters::outbound::persistence::conformance::user_repository_tests!(
+                @test $attrs $repo; $name
+            );
+        )*
+    };
+    (@test [$($attr:tt)*] $repo:expr; $name:ident) => {
+        #[tokio::test]
+        $($attr)*
+        async fn $name() {
+            use $crate::adapters::outbound::persistence::conformance::Fixture;
+            let fixture = $repo;
+            $crate::adapters::outbound::persistence::conformance::$name(fixture.repository())
+                .await;
+        }
+    };
+}
+
+pub(crate) use user_repository_tests;
+
+/// A repository under test, or a guard owning one that cleans up when dropped
+pub trait Fixture {
+    type Repository;
+
+    fn repository(&self) -> &Self::Repository;
+}
+
+impl<R: UserRepository> Fixture for R {
+    type Repository = R;
+
+    fn repository(&self) -> &R {
+        self
+    }
+}
+
+pub async fn test_save_and_find_by_id<R: UserRepository>(repo: &R) {
+    let user = User::fixture(TenantId::default(), "test@example.com");
+    let user_id = user.id;
+
+    repo.save(&user).await.unwrap();
+
+    let found = repo
+        .find_by_id(&TenantId::default(), &user_id)
+        .await
+        .unwrap();
+    assert!(found.is_some());
+    assert_eq!(found.unwrap().name, "Test User");
+}
+
+pub async fn test_find_by_email<R: UserRepository>(repo: &R) {
+    let email = Email::new("test@example.com").unwrap();
+    let user = User::fixture(TenantId::default(), email.as_str());
+
+    repo.save(&user).await.unwrap();
+
+    let found = repo
+        .find_by_email(&TenantId::default(), &email)
+        .await
+        .unwrap();
+    assert!(found.is_some());
+    assert_eq!(found.unwrap().name, "Test User");
+}
+
+pub async fn test_delete<R: UserRepository>(repo: &R) {
+    let user = User::fixture(TenantId::default(), "test@example.com");
+    let user_id = user.id;
+
+    repo.save(&user).await.unwrap();
+    repo.delete(&TenantId::default(), &user_id).await.unwrap();
+
+    let found = repo
+        .find_by_id(&TenantId::default(), &user_id)
+        .await
+        .unwrap();
+    assert!(found.is_none());
+}
+
+pub async fn test_deleted_users_are_hidden<R: UserRepository>(repo: &R) {
+    let tenant = TenantId::default();
+    let email = Email::new("test@example.com").unwrap();
+    let mut user = User::fixture(tenant.clone(), email.as_str());
+    user.delete(Utc::now()).unwrap();
+    repo.save(&user).await.unwrap();
+
+    assert!(repo.find_by_id(&tenant, &user.id).await.unwrap().is_none());
+    assert!(repo.find_by_email(&tenant, &email).await.unwrap().is_none());
+    assert!(Repository::list(repo, &tenant).await.unwrap().is_empty());
+    assert!(repo
+        .find_deleted(&tenant, &user.id)
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(repo.list_deleted(&tenant).await.unwrap().len(), 1);
+}
+
+pub async fn test_list<R: UserRepository>(repo: &R) {
+    let user1 = User::fixture(TenantId::default(), "user1@example.com");
+    let user2 = User::fixture(TenantId::default(), "user2@example.com");
+
+    repo.save(&user1).await.unwrap();
+    repo.save(&user2).await.unwrap();
+
+    let users = Repository::list(repo, &TenantId::default()).await.unwrap();
+    assert_eq!(users.len(), 2);
+}
+
+pub async fn test_status_is_persisted<R: UserRepository>(repo: &R) {
+    let mut user = User::fixture(TenantId::default(), "test@example.com");
+    user.activate(Utc::now()).unwrap();
+    user.suspend("Spam", Utc::now()).unwrap();
+
+    repo.save(&user).await.unwrap();
+
+    let found = repo
+        .find_by_id(&TenantId::default(), &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        found.status,
+        UserStatus::Suspended {
+            reason: "Spam".to_string()
+        }
+    );
+}
+
+pub async fn test_tenants_are_isolated<R: UserRepository>(repo: &R) {
+    let acme = TenantId::new("acme").unwrap();
+    let globex = TenantId::new("globex").unwrap();
+    let email = Email::new("test@example.com").unwrap();
+
+    let acme_user = User::fixture(acme.clone(), email.as_str());
+    let globex_user = User::fixture(globex.clone(), email.as_str());
+    repo.save(&acme_user).await.unwrap();
+    repo.save(&globex_user).await.unwrap();
+
+    let found = repo.find_by_email(&acme, &email).await.unwrap().unwrap();
+    assert_eq!(found.id, acme_user.id);
+    assert!(repo
+        .find_by_id(&globex, &acme_user.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(Repository::list(repo, &acme).await.unwrap().len(), 1);
+
+    repo.delete(&globex, &acme_user.id).await.unwrap();
+    assert!(repo
+        .find_by_id(&acme, &acme_user.id)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+pub async fn test_outbox_written_with_user<R: UserRepository + OutboxRepository>(repo: &R) {
+    let user = User::fixture(TenantId::default(), "test@example.com");
+    let messages: Vec<_> = user
+        .pending_events()
+        .iter()
+        .cloned()
+        .map(|event| OutboxMessage::new(Uuid::new_v4(), event))
+        .collect();
+
+    repo.save_with_outbox(&user, &messages).await.unwrap();
+    assert!(repo
+        .find_by_id(&TenantId::default(), &user.id)
+        .await
+        .unwrap()
+        .is_some());
+
+    let mut due = repo.find_due(Utc::now(), 10).await.unwrap();
+    assert_eq!(due.len(), 1);
+
+    due[0].mark_delivered(Utc::now());
+    repo.update(&due[0]).await.unwrap();
+    assert!(repo.find_due(Utc::now(), 10).await.unwrap().is_empty());
+
+    let delivered =
+        OutboxRepository::list(repo, &TenantId::default(), Some(OutboxStatus::Delivered))
+            .await
+            .unwrap();
+    assert_eq!(delivered.len(), 1);
+    assert!(
+        OutboxRepository::list(repo, &TenantId::new("other").unwrap(), None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+pub async fn test_reset_token_written_with_outbox<R>(repo: &R)
+where
+    R: UserRepository + OutboxRepository + PasswordResetTokenRepository,
+{
+    let now = Utc::now();
+    let token = PasswordResetToken::new("digest", UserId::new(), now, Duration::minutes(30));
+    let event = DomainEvent::PasswordResetRequested(PasswordResetRequested {
+        user_id: token.user_id,
+        tenant_id: TenantId::default(),
+        email: Email::new("test@example.com").unwrap(),
+        token_id: Uuid::new_v4(),
+        expires_at: token.expires_at,
+        occurred_at: now,
+    });
+    let messages = [OutboxMessage::new(Uuid::new_v4(), event)];
+
+    PasswordResetTokenRepository::save_with_outbox(repo, &token, &messages)
+        .await
+        .unwrap();
+    assert_eq!(repo.find_due(Utc::now(), 10).await.unwrap().len(), 1);
+
+    assert!(repo.consume("digest", Utc::now()).await.unwrap());
+    assert!(!repo.consume("digest", Utc::now()).await.unwrap());
+    let stored = repo.find_by_hash("digest").await.unwrap().unwrap();
+    assert_eq!(stored.user_id, token.user_id);
+    assert!(stored.is_used());
+    assert!(repo.find_by_hash("other").await.unwrap().is_none());
+}
+
+pub async fn test_pending_events_are_not_stored<R: UserRepository>(repo: &R) {
+    let user = User::fixture(TenantId::default(), "test@example.com");
+    assert!(!user.pending_events().is_empty());
+
+    repo.save(&user).await.unwrap();
+
+    let found = repo
+        .find_by_id(&TenantId::default(), &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.pending_events().is_empty());
+}
+
+pub async fn test_stale_save_is_rejected<R: UserRepository>(repo: &R) {
+    let mut user = User::fixture(TenantId::default(), "test@example.com");
+    repo.save(&user).await.unwrap();
+    user.take_events();
+
+    let mut first = user.clone();
+    let mut second = user.clone();
+    first.update_name(DisplayName::new("First").unwrap(), Utc::now());
+    repo.save(&first).await.unwrap();
+    second.update_name(DisplayName::new("Second").unwrap(), Utc::now());
+
+    let result = repo.save(&second).await;
+    assert!(matches!(result, Err(DomainError::Conflict(_))));
+
+    let stored = repo
+        .find_by_id(&TenantId::default(), &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((stored.name.as_str(), stored.version), ("First", 2));
+}
+
+pub async fn test_query_pages_live_users_of_tenant<R: UserRepository>(repo: &R) {
+    let tenant = TenantId::default();
+    for i in 0..5 {
+        let mut user = User::fixture(tenant.clone(), &format!("user{}@example.com", i));
+        if i == 0 {
+            user.delete(Utc::now()).unwrap();
+        }
+        repo.save(&user).await.unwrap();
+    }
+    let foreign = User::fixture(TenantId::new("other").unwrap(), "user9@example.com");
+    repo.save(&foreign).await.unwrap();
+
+    let query = UserQuery {
+        sort: crate::domain::ports::UserSortKey::Email,
+        limit: 3,
+        ..UserQuery::default()
+    };
+    let first = repo.query(&tenant, &query).await.unwrap();
+    assert_eq!(first.total, 4);
+    assert_eq!(first.items[0].email.as_str(), "user1@example.com");
+
+    let rest = UserQuery {
+        after: first.next_cursor,
+        ..query
+    };
+    let second = repo.query(&tenant, &rest).await.unwrap();
+    let emails: Vec<_> = second.items.iter().map(|u| u.email.as_str()).collect();
+    assert_eq!(emails, ["user4@example.com"]);
+    assert!(second.next_cursor.is_none());
+}
+
+pub async fn test_stream_yields_every_user_in_chunks<R: UserRepository>(repo: &R) {
+    let tenant = TenantId::default();
+    for i in 0..7 {
+        let user = User::fixture(tenant.clone(), &format!("user{}@example.com", i));
+        repo.save(&user).await.unwrap();
+    }
+
+    let query = UserQuery {
+        sort: crate::domain::ports::UserSortKey::Email,
+        limit: 3,
+        ..UserQuery::default()
+    };
+    let users: Vec<User> = repo.stream(&tenant, query).try_collect().await.unwrap();
+    let emails: Vec<_> = users.iter().map(|u| u.email.as_str()).collect();
+    let expected: Vec<_> = (0..7).map(|i| format!("user{}@example.com", i)).collect();
+    assert_eq!(emails, expected);
+}
+
+pub async fn test_batch_operations<R: UserRepository>(repo: &R) {
+    let tenant = TenantId::default();
+    let users: Vec<User> = (0..3)
+        .map(|i| User::fixture(tenant.clone(), &format!("user{}@example.com", i)))
+        .collect();
+    repo.save(&users[1]).await.unwrap();
+
+    // The already stored user is stale; the others are saved regardless
+    let results = repo.save_many(&users).await;
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(DomainError::Conflict(_))));
+    assert!(results[2].is_ok());
+
+    let ids = [users[2].id, UserId::new(), users[0].id];
+    let found = repo.find_many_by_ids(&tenant, &ids).await.unwrap();
+    let found: Vec<_> = found.iter().map(|u| u.id).collect();
+    assert_eq!(found, [users[2].id, users[0].id]);
+
+    let emails = [
+        users[0].email.clone(),
+        Email::new("unknown@example.com").unwrap(),
+        users[2].email.clone(),
+    ];
+    let mut found: Vec<_> = repo
+        .find_many_by_emails(&tenant, &emails)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|u| u.email.as_str().to_string())
+        .collect();
+    found.sort();
+    assert_eq!(found, ["user0@example.com", "user2@example.com"]);
+
+    repo.delete_many(&tenant, &[users[0].id, users[1].id])
+        .await
+        .unwrap();
+    let left: Vec<_> = Repository::list(repo, &tenant)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|u| u.id)
+        .collect();
+    assert_eq!(left, [users[2].id]);
+}
@@ -0,0 +1,366 @@
+//! Event-sourced user repository
+//!
+//! Stores users as their event streams in an `EventStore` and rebuilds
+//! them by replay, keeping a full history of every change.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::{Email, TenantId, User, UserId},
+    errors::DomainError,
+    events::DomainEvent,
+    ports::{
+        Clock, EventStore, Page, Repository, Snapshot, SystemClock, UserQuery, UserRepository,
+    },
+};
+
+/// A stream as loaded from the store
+struct Loaded {
+    /// The rebuilt user, unless the stream is empty or ends in a purge
+    user: Option<User>,
+    /// Version of the snapshot the user was rebuilt from, or 0
+    snapshot_version: u64,
+}
+
+/// User repository backed by an [`EventStore`]
+///
+/// Saving appends the user's pending events to its stream; loading replays
+/// them, starting from the latest snapshot if there is one. Purged users
+/// keep their stream, ending in `UserPurged`.
+///
+/// Lookups by email and listing replay every stream, so this suits
+/// deployments that value a complete history over query speed.
+pub struct EventSourcedUserRepository<S>
+where
+    S: EventStore + ?Sized,
+{
+    store: Arc<S>,
+    snapshot_every: Option<u64>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S> EventSourcedUserRepository<S>
+where
+    S: EventStore + ?Sized,
+{
+    /// Create a repository over `store`, without snapshots
+    pub fn new(store: Arc<S>) -> Self {
+        Self {
+            store,
+            snapshot_every: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Time the events recorded by [`delete`](Repository::delete) with
+    /// `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Snapshot a user once `every` events were appended since the last
+    /// snapshot, bounding how many events a load replays
+    pub fn with_snapshots(mut self, every: u64) -> Self {
+        self.snapshot_every = Some(every.max(1));
+        self
+    }
+
+    async fn load(&self, id: &UserId) -> Result<Loaded, DomainError> {
+        let snapshot = self.store.load_snapshot(id).await?;
+        let snapshot_version = snapshot.as_ref().map_or(0, |s| s.version);
+        let events = self.store.read(id, snapshot_version).await?;
+
+        let user = match (snapshot, events.last()) {
+            (_, Some(DomainEvent::UserPurged(_))) => None,
+            (Some(snapshot), _) => {
+                let mut user = snapshot.user;
+                user.apply_history(&events)?;
+                Some(user)
+            }
+            (None, None) => None,
+            (None, Some(_)) => Some(User::from_history(&events)?),
+        };
+
+        Ok(Loaded {
+            user,
+            snapshot_version,
+        })
+    }
+
+    /// A user of `tenant`, whether deleted or not
+    async fn find_any(&self, tenant: &TenantId, id: &UserId) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .load(id)
+            .await?
+            .user
+            .filter(|user| &user.tenant_id == tenant))
+    }
+
+    /// Every user of `tenant`, whether deleted or not
+    async fn list_any(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        let mut users = Vec::new();
+        for id in self.store.stream_ids().await? {
+            if let Some(user) = self.find_any(tenant, &id).await? {
+                users.push(user);
+            }
+        }
+        Ok(users)
+    }
+
+    /// Append `user`'s pending events to its stream
+    async fn append(&self, user: &User, loaded: &Loaded) -> Result<(), DomainError> {
+        let events = user.pending_events();
+        if events.is_empty() {
+            return Ok(());
+        }
+        let expected_version = user.expected_version();
+        if expected_version == 0 && !matches!(events[0], DomainEvent::UserRegistered(_)) {
+            return Err(DomainError::validation(format!(
+                "Stream of user {} must start with UserRegistered",
+                user.id
+            )));
+        }
+
+        let version = self
+            .store
+            .append(&user.id, expected_version, events)
+            .await?;
+
+        if let Some(every) = self.snapshot_every {
+            if version - loaded.snapshot_version >= every {
+                let user = user.without_events();
+                self.store
+                    .save_snapshot(&Snapshot { version, user })
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S> Repository<User> for EventSourcedUserRepository<S>
+where
+    S: EventStore + ?Sized,
+{
+    async fn find_by_id(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .find_any(tenant, id)
+            .await?
+            .filter(|user| !user.is_deleted()))
+    }
+
+    async fn save(&self, user: &User) -> Result<(), DomainError> {
+        let loaded = self.load(&user.id).await?;
+        self.append(user, &loaded).await
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &UserId) -> Result<(), DomainError> {
+        let loaded = self.load(id).await?;
+        let Some(mut user) = loaded.user.clone() else {
+            return Ok(());
+        };
+        if &user.tenant_id != tenant {
+            return Ok(());
+        }
+
+        let now = self.clock.now();
+        if !user.is_deleted() {
+            user.delete(now)?;
+        }
+        user.purge(now)?;
+        self.append(&user, &loaded).await
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        let mut users = self.list_any(tenant).await?;
+        users.retain(|user| !user.is_deleted());
+        Ok(users)
+    }
+
+    async fn find_deleted(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .find_any(tenant, id)
+            .await?
+            .filter(|user| user.is_deleted()))
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        let mut users = self.list_any(tenant).await?;
+        users.retain(|user| user.is_deleted());
+        Ok(users)
+    }
+}
+
+#[async_trait]
+impl<S> UserRepository for EventSourcedUserRepository<S>
+where
+    S: EventStore + ?Sized,
+{
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .list(tenant)
+            .await?
+            .into_iter()
+            .find(|user| &user.email == email))
+    }
+
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        query.page(&self.list(tenant).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::InMemoryEventStore;
+    use crate::domain::entities::{DisplayName, Role};
+    use chrono::Utc;
+
+    fn repository() -> (
+        Arc<InMemoryEventStore>,
+        EventSourcedUserRepository<InMemoryEventStore>,
+    ) {
+        let store = Arc::new(InMemoryEventStore::new());
+        (store.clone(), EventSourcedUserRepository::new(store))
+    }
+
+    fn new_user() -> User {
+        User::fixture(TenantId::default(), "test@example.com")
+    }
+
+    #[tokio::test]
+    async fn test_save_and_replay() {
+        let (store, repo) = repository();
+        let mut user = new_user();
+        repo.save(&user).await.unwrap();
+        user.take_events();
+
+        let mut loaded = repo
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        loaded.update_name(DisplayName::new("Renamed").unwrap(), Utc::now());
+        loaded.assign_role(Role::Support, Utc::now()).unwrap();
+        repo.save(&loaded).await.unwrap();
+
+        let found = repo
+            .find_by_email(&TenantId::default(), &user.email)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.name, "Renamed");
+        assert!(found.has_role(Role::Support));
+        assert!(found.pending_events().is_empty());
+        assert_eq!(store.read(&user.id, 0).await.unwrap().len(), 3);
+
+        let other = TenantId::new("other").unwrap();
+        assert!(repo.find_by_id(&other, &user.id).await.unwrap().is_none());
+        assert!(repo.list(&other).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_and_purge() {
+        let (store, repo) = repository();
+        let tenant = TenantId::default();
+        let mut user = new_user();
+        user.delete(Utc::now()).unwrap();
+        repo.save(&user).await.unwrap();
+
+        assert!(repo.find_by_id(&tenant, &user.id).await.unwrap().is_none());
+        assert!(repo.list(&tenant).await.unwrap().is_empty());
+        assert!(repo
+            .find_deleted(&tenant, &user.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(repo.list_deleted(&tenant).await.unwrap().len(), 1);
+
+        repo.delete(&tenant, &user.id).await.unwrap();
+
+        assert!(repo
+            .find_deleted(&tenant, &user.id)
+            .await
+            .unwrap()
+            .is_none());
+        let history = store.read(&user.id, 0).await.unwrap();
+        assert_eq!(history.last().unwrap().name(), "UserPurged");
+    }
+
+    #[tokio::test]
+    async fn test_save_without_stored_registration_is_rejected() {
+        let (_, repo) = repository();
+        let mut user = new_user();
+        user.take_events();
+        user.update_name(DisplayName::new("Renamed").unwrap(), Utc::now());
+
+        let result = repo.save(&user).await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_bound_replay() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repo = EventSourcedUserRepository::new(store.clone()).with_snapshots(3);
+        let mut user = new_user();
+        repo.save(&user).await.unwrap();
+        user.take_events();
+
+        for name in ["A", "B", "C", "D"] {
+            user.update_name(DisplayName::new(name).unwrap(), Utc::now());
+            repo.save(&user).await.unwrap();
+            user.take_events();
+        }
+
+        let snapshot = store.load_snapshot(&user.id).await.unwrap().unwrap();
+        assert_eq!(snapshot.version, 3);
+        assert_eq!(snapshot.user.name, "B");
+
+        let loaded = repo
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.name, "D");
+    }
+
+    #[tokio::test]
+    async fn test_stale_save_is_rejected() {
+        let (_, repo) = repository();
+        let user = new_user();
+        repo.save(&user).await.unwrap();
+
+        let load = || async {
+            repo.find_by_id(&TenantId::default(), &user.id)
+                .await
+                .unwrap()
+                .unwrap()
+        };
+        let mut first = load().await;
+        let mut second = load().await;
+
+        first.update_name(DisplayName::new("First").unwrap(), Utc::now());
+        repo.save(&first).await.unwrap();
+        second.update_name(DisplayName::new("Second").unwrap(), Utc::now());
+        let result = repo.save(&second).await;
+
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+        assert_eq!(load().await.name, "First");
+    }
+}
@@ -0,0 +1,335 @@
+//! Event store implementations
+//!
+//! Back the `EventStore` port in memory or with one JSONL file per stream.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::UserId,
+    errors::DomainError,
+    events::DomainEvent,
+    ports::{EventStore, Snapshot},
+};
+
+/// Error for an append whose expected version is out of date
+fn version_conflict(id: &UserId, expected: u64, actual: u64) -> DomainError {
+    DomainError::conflict(format!(
+        "Stream of user {} is at version {}, expected {}",
+        id, actual, expected
+    ))
+}
+
+/// In-memory event store for testing and development
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    streams: RwLock<HashMap<UserId, Vec<DomainEvent>>>,
+    snapshots: RwLock<HashMap<UserId, Snapshot>>,
+}
+
+impl InMemoryEventStore {
+    /// Create a new empty event store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        events: &[DomainEvent],
+    ) -> Result<u64, DomainError> {
+        let mut streams = self
+            .streams
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let stream = streams.entry(*id).or_default();
+        let version = stream.len() as u64;
+        if version != expected_version {
+            return Err(version_conflict(id, expected_version, version));
+        }
+
+        stream.extend_from_slice(events);
+        Ok(stream.len() as u64)
+    }
+
+    async fn read(&self, id: &UserId, after: u64) -> Result<Vec<DomainEvent>, DomainError> {
+        let streams = self
+            .streams
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(streams
+            .get(id)
+            .map(|stream| stream.iter().skip(after as usize).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn stream_ids(&self) -> Result<Vec<UserId>, DomainError> {
+        let streams = self
+            .streams
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(streams
+            .iter()
+            .filter(|(_, stream)| !stream.is_empty())
+            .map(|(id, _)| *id)
+            .collect())
+    }
+
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), DomainError> {
+        let mut snapshots = self
+            .snapshots
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        snapshots.insert(snapshot.user.id, snapshot.clone());
+        Ok(())
+    }
+
+    async fn load_snapshot(&self, id: &UserId) -> Result<Option<Snapshot>, DomainError> {
+        let snapshots = self
+            .snapshots
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(snapshots.get(id).cloned())
+    }
+}
+
+/// File-based event store
+///
+/// Keeps each stream in `<dir>/<user id>.jsonl`, one event per line, and
+/// its latest snapshot in `<dir>/<user id>.snapshot.json`. Appends are
+/// serialized within the process; use one store per directory.
+pub struct FileEventStore {
+    dir: PathBuf,
+    append_lock: Mutex<()>,
+}
+
+impl FileEventStore {
+    /// Open a store in `dir`, creating the directory if needed
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!(
+                "Failed to create {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            dir,
+            append_lock: Mutex::new(()),
+        })
+    }
+
+    /// Directory holding the streams
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn stream_path(&self, id: &UserId) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", id))
+    }
+
+    fn snapshot_path(&self, id: &UserId) -> PathBuf {
+        self.dir.join(format!("{}.snapshot.json", id))
+    }
+
+    /// Every event of a stream, naming the offending line on parse errors
+    fn load_stream(&self, id: &UserId) -> Result<Vec<DomainEvent>, DomainError> {
+        let path = self.stream_path(id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!("Failed to read {}: {}", path.display(), e))
+        })?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    DomainError::Infrastructure(anyhow::anyhow!(
+                        "Failed to parse {} line {}: {}",
+                        path.display(),
+                        number + 1,
+                        e
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventStore for FileEventStore {
+    async fn append(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        events: &[DomainEvent],
+    ) -> Result<u64, DomainError> {
+        let _guard = self
+            .append_lock
+            .lock()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let version = self.load_stream(id)?.len() as u64;
+        if version != expected_version {
+            return Err(version_conflict(id, expected_version, version));
+        }
+
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(
+                &serde_json::to_string(event).map_err(|e| DomainError::Infrastructure(e.into()))?,
+            );
+            lines.push('\n');
+        }
+
+        let path = self.stream_path(id);
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!(
+                    "Failed to write {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(version + events.len() as u64)
+    }
+
+    async fn read(&self, id: &UserId, after: u64) -> Result<Vec<DomainEvent>, DomainError> {
+        let mut events = self.load_stream(id)?;
+        Ok(events.split_off((after as usize).min(events.len())))
+    }
+
+    async fn stream_ids(&self) -> Result<Vec<UserId>, DomainError> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!(
+                "Failed to read {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| DomainError::Infrastructure(e.into()))?;
+            let name = entry.file_name();
+            let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".jsonl")) else {
+                continue;
+            };
+            if let Ok(uuid) = Uuid::parse_str(stem) {
+                ids.push(UserId::from_uuid(uuid));
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), DomainError> {
+        let path = self.snapshot_path(&snapshot.user.id);
+        let content =
+            serde_json::to_string(snapshot).map_err(|e| DomainError::Infrastructure(e.into()))?;
+
+        // Write then rename, so readers never see a half-written snapshot
+        let partial = path.with_extension("json.tmp");
+        std::fs::write(&partial, content)
+            .and_then(|()| std::fs::rename(&partial, &path))
+            .map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!(
+                    "Failed to write {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    async fn load_snapshot(&self, id: &UserId) -> Result<Option<Snapshot>, DomainError> {
+        let path = self.snapshot_path(id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!(
+                "Failed to parse {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DisplayName, TenantId, User};
+    use chrono::Utc;
+
+    fn history() -> (UserId, Vec<DomainEvent>) {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.update_name(DisplayName::new("New").unwrap(), Utc::now());
+        (user.id, user.take_events())
+    }
+
+    async fn check_store(store: &dyn EventStore) {
+        let (id, events) = history();
+
+        assert_eq!(store.append(&id, 0, &events[..1]).await.unwrap(), 1);
+        assert!(matches!(
+            store.append(&id, 0, &events[1..]).await,
+            Err(DomainError::Conflict(_))
+        ));
+        assert_eq!(store.append(&id, 1, &events[1..]).await.unwrap(), 2);
+
+        assert_eq!(store.read(&id, 0).await.unwrap(), events);
+        assert_eq!(store.read(&id, 1).await.unwrap(), events[1..]);
+        assert!(store.read(&id, 5).await.unwrap().is_empty());
+        assert!(store.read(&UserId::new(), 0).await.unwrap().is_empty());
+        assert_eq!(store.stream_ids().await.unwrap(), [id]);
+
+        let user = User::from_history(&events).unwrap();
+        store
+            .save_snapshot(&Snapshot { version: 2, user })
+            .await
+            .unwrap();
+        let snapshot = store.load_snapshot(&id).await.unwrap().unwrap();
+        assert_eq!(snapshot.version, 2);
+        assert_eq!(snapshot.user.name, "New");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        check_store(&InMemoryEventStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = std::env::temp_dir().join(format!("event-store-{}", Uuid::new_v4()));
+        check_store(&FileEventStore::new(&dir).unwrap()).await;
+
+        // A reopened store sees the same streams
+        let reopened = FileEventStore::new(&dir).unwrap();
+        assert_eq!(reopened.stream_ids().await.unwrap().len(), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
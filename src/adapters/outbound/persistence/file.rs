@@ -0,0 +1,718 @@
+//! File-based repository implementation
+//!
+//! Stores users in a JSON file for simplicity. Suitable for CLI tools
+//! and single-process use cases.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{find, insert, remove, select, stream_by_ids, Partitions};
+use crate::domain::{
+    entities::{Email, PasswordResetToken, TenantId, User, UserId, VerificationToken},
+    errors::DomainError,
+    ports::{
+        AggregateRoot, Page, PasswordResetTokenRepository, Repository, UserQuery, UserRepository,
+        UserStream, VerificationTokenRepository,
+    },
+};
+
+/// Load a JSON array from `path`, or nothing if the file does not exist
+///
+/// Records are decoded one by one, so an invalid one is reported by its
+/// position and ID instead of as an offset into the file.
+fn load<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, DomainError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        DomainError::Infrastructure(anyhow::anyhow!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let records: Vec<Value> = serde_json::from_str(&content).map_err(|e| {
+        DomainError::Infrastructure(anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+    })?;
+
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let name = describe(index, &record);
+            serde_json::from_value(record).map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!(
+                    "Invalid record {} in {}: {}",
+                    name,
+                    path.display(),
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Name of a record in errors: its position and, if it has one, its ID
+fn describe(index: usize, record: &Value) -> String {
+    match record.get("id").and_then(Value::as_str) {
+        Some(id) => format!("#{} (id {})", index + 1, id),
+        None => format!("#{}", index + 1),
+    }
+}
+
+/// Write `items` to `path` as a pretty-printed JSON array
+fn store<T: Serialize>(path: &Path, items: &[&T]) -> Result<(), DomainError> {
+    let content =
+        serde_json::to_string_pretty(items).map_err(|e| DomainError::Infrastructure(e.into()))?;
+    std::fs::write(path, content).map_err(|e| {
+        DomainError::Infrastructure(anyhow::anyhow!("Failed to write {}: {}", path.display(), e))
+    })
+}
+
+/// File-based repository of any aggregate
+///
+/// Keeps every aggregate in memory and rewrites the whole file on each
+/// change. All tenants share one file; each record carries its tenant ID.
+pub struct FileRepository<A: AggregateRoot> {
+    file_path: PathBuf,
+    cache: RwLock<Partitions<A>>,
+}
+
+/// File-based user repository
+pub type FileUserRepository = FileRepository<User>;
+
+impl<A> FileRepository<A>
+where
+    A: AggregateRoot + Serialize + DeserializeOwned,
+{
+    /// Open a file repository, loading existing aggregates if the file exists
+    pub fn new(file_path: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let file_path = file_path.into();
+        let aggregates: Vec<A> = load(&file_path)?;
+        let mut cache: Partitions<A> = HashMap::new();
+        for aggregate in aggregates {
+            cache
+                .entry(aggregate.tenant_id().clone())
+                .or_default()
+                .insert(aggregate.id(), aggregate);
+        }
+
+        Ok(Self {
+            file_path,
+            cache: RwLock::new(cache),
+        })
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// Write `aggregates` to disk
+    ///
+    /// Changes are applied to a copy of the cache and persisted before the
+    /// copy replaces it, so a failed write leaves the cache as on disk.
+    fn persist(&self, aggregates: &Partitions<A>) -> Result<(), DomainError> {
+        store(
+            &self.file_path,
+            &aggregates
+                .values()
+                .flat_map(|a| a.values())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[async_trait]
+impl<A> Repository<A> for FileRepository<A>
+where
+    A: AggregateRoot + Serialize + DeserializeOwned,
+{
+    async fn find_by_id(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(find(&cache, tenant, id, false))
+    }
+
+    async fn save(&self, aggregate: &A) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        insert(&mut next, aggregate)?;
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &A::Id) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        remove(&mut next, tenant, id);
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(select(&cache, tenant, false))
+    }
+
+    async fn find_deleted(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(find(&cache, tenant, id, true))
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(select(&cache, tenant, true))
+    }
+}
+
+#[async_trait]
+impl UserRepository for FileRepository<User> {
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(cache
+            .get(tenant)
+            .and_then(|users| {
+                users
+                    .values()
+                    .find(|u| !u.is_deleted() && &u.email == email)
+            })
+            .cloned())
+    }
+
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let live = cache
+            .get(tenant)
+            .into_iter()
+            .flat_map(|users| users.values())
+            .filter(|u| !u.is_deleted());
+        query.page(live)
+    }
+
+    fn stream<'a>(&'a self, tenant: &'a TenantId, query: UserQuery) -> UserStream<'a> {
+        let ids = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))
+            .and_then(|cache| {
+                query.ordered_ids(
+                    cache
+                        .get(tenant)
+                        .into_iter()
+                        .flat_map(|users| users.values())
+                        .filter(|u| !u.is_deleted()),
+                )
+            });
+        stream_by_ids(self, tenant, ids, query.limit)
+    }
+
+    async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>> {
+        let mut cache = match self.cache.write() {
+            Ok(cache) => cache,
+            Err(e) => {
+                return users
+                    .iter()
+                    .map(|_| {
+                        Err(DomainError::Infrastructure(anyhow::anyhow!(
+                            "Lock poisoned: {}",
+                            e
+                        )))
+                    })
+                    .collect()
+            }
+        };
+        let mut next = cache.clone();
+        let mut results: Vec<_> = users.iter().map(|u| insert(&mut next, u)).collect();
+
+        // Rewrite the file once for the whole batch
+        if results.iter().any(Result::is_ok) {
+            match self.persist(&next) {
+                Ok(()) => *cache = next,
+                Err(e) => {
+                    for result in results.iter_mut().filter(|r| r.is_ok()) {
+                        *result = Err(DomainError::Infrastructure(anyhow::anyhow!("{}", e)));
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    async fn find_many_by_ids(
+        &self,
+        tenant: &TenantId,
+        ids: &[UserId],
+    ) -> Result<Vec<User>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| find(&cache, tenant, id, false))
+            .collect())
+    }
+
+    async fn find_many_by_emails(
+        &self,
+        tenant: &TenantId,
+        emails: &[Email],
+    ) -> Result<Vec<User>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(cache
+            .get(tenant)
+            .map(|users| {
+                users
+                    .values()
+                    .filter(|u| !u.is_deleted() && emails.contains(&u.email))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        for id in ids {
+            remove(&mut next, tenant, id);
+        }
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+}
+
+/// File-based verification token repository
+pub struct FileVerificationTokenRepository {
+    file_path: PathBuf,
+    cache: RwLock<HashMap<Uuid, VerificationToken>>,
+}
+
+impl FileVerificationTokenRepository {
+    /// Open a token file, loading existing tokens if the file exists
+    pub fn new(file_path: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let file_path = file_path.into();
+        let tokens: Vec<VerificationToken> = load(&file_path)?;
+        let cache = tokens.into_iter().map(|t| (t.id, t)).collect();
+
+        Ok(Self {
+            file_path,
+            cache: RwLock::new(cache),
+        })
+    }
+
+    /// Write `tokens` to disk, before they replace the cache
+    fn persist(&self, tokens: &HashMap<Uuid, VerificationToken>) -> Result<(), DomainError> {
+        store(&self.file_path, &tokens.values().collect::<Vec<_>>())
+    }
+}
+
+#[async_trait]
+impl VerificationTokenRepository for FileVerificationTokenRepository {
+    async fn save(&self, token: &VerificationToken) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        next.insert(token.id, token.clone());
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+
+    async fn save_many(&self, tokens: &[VerificationToken]) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        for token in tokens {
+            next.insert(token.id, token.clone());
+        }
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<VerificationToken>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(cache.get(id).cloned())
+    }
+
+    async fn consume(&self, id: &Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        match next.get_mut(id) {
+            Some(token) if !token.is_used() => {
+                token.used_at = Some(used_at);
+                self.persist(&next)?;
+                *cache = next;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// File-based password reset token repository
+pub struct FilePasswordResetTokenRepository {
+    file_path: PathBuf,
+    cache: RwLock<HashMap<String, PasswordResetToken>>,
+}
+
+impl FilePasswordResetTokenRepository {
+    /// Open a token file, loading existing tokens if the file exists
+    pub fn new(file_path: impl Into<PathBuf>) -> Result<Self, DomainError> {
+        let file_path = file_path.into();
+        let tokens: Vec<PasswordResetToken> = load(&file_path)?;
+        let cache = tokens
+            .into_iter()
+            .map(|t| (t.token_hash.clone(), t))
+            .collect();
+
+        Ok(Self {
+            file_path,
+            cache: RwLock::new(cache),
+        })
+    }
+
+    /// Write `tokens` to disk, before they replace the cache
+    fn persist(&self, tokens: &HashMap<String, PasswordResetToken>) -> Result<(), DomainError> {
+        store(&self.file_path, &tokens.values().collect::<Vec<_>>())
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for FilePasswordResetTokenRepository {
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        next.insert(token.token_hash.clone(), token.clone());
+        self.persist(&next)?;
+        *cache = next;
+        Ok(())
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(cache.get(token_hash).cloned())
+    }
+
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let mut cache = self
+            .cache
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let mut next = cache.clone();
+        match next.get_mut(token_hash) {
+            Some(token) if !token.is_used() => {
+                token.used_at = Some(used_at);
+                self.persist(&next)?;
+                *cache = next;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::conformance::Fixture;
+    use super::*;
+    use crate::domain::entities::{DisplayName, UserId};
+
+    fn temp_file() -> PathBuf {
+        std::env::temp_dir().join(format!("users-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    /// A repository in a fresh file, removed when dropped
+    struct TempRepository(FileUserRepository);
+
+    impl TempRepository {
+        fn new() -> Self {
+            Self(FileUserRepository::new(temp_file()).unwrap())
+        }
+    }
+
+    impl Drop for TempRepository {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(self.0.path());
+        }
+    }
+
+    impl Fixture for TempRepository {
+        type Repository = FileUserRepository;
+
+        fn repository(&self) -> &FileUserRepository {
+            &self.0
+        }
+    }
+
+    super::super::conformance::user_repository_tests!(TempRepository::new());
+
+    #[tokio::test]
+    async fn test_save_and_reload() {
+        let path = temp_file();
+        let user = User::fixture(TenantId::default(), "test@example.com");
+
+        let repo = FileUserRepository::new(&path).unwrap();
+        repo.save(&user).await.unwrap();
+
+        let reopened = FileUserRepository::new(&path).unwrap();
+        let found = reopened
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().name, "Test User");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_record_is_named() {
+        let path = temp_file();
+        let user = User::fixture(TenantId::default(), "test@example.com");
+        let mut record = serde_json::to_value(&user).unwrap();
+        record["email"] = Value::from("not-an-email");
+        std::fs::write(&path, Value::Array(vec![record]).to_string()).unwrap();
+
+        let error = FileUserRepository::new(&path).err().unwrap().to_string();
+        assert!(error.contains(&format!("#1 (id {})", user.id)), "{}", error);
+        assert!(error.contains("Email must contain @"), "{}", error);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_persists() {
+        let path = temp_file();
+        let user = User::fixture(TenantId::default(), "test@example.com");
+
+        let repo = FileUserRepository::new(&path).unwrap();
+        repo.save(&user).await.unwrap();
+        repo.delete(&TenantId::default(), &user.id).await.unwrap();
+
+        let reopened = FileUserRepository::new(&path).unwrap();
+        assert!(reopened
+            .list(&TenantId::default())
+            .await
+            .unwrap()
+            .is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_write_leaves_cache_unchanged() {
+        let dir = std::env::temp_dir().join(format!("users-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("users.json");
+        let user = User::fixture(TenantId::default(), "test@example.com");
+
+        let repo = FileUserRepository::new(&path).unwrap();
+        repo.save(&user).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut renamed = repo
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        renamed.update_name(DisplayName::new("Renamed").unwrap(), Utc::now());
+        assert!(repo.save(&renamed).await.is_err());
+        assert!(repo.delete(&TenantId::default(), &user.id).await.is_err());
+
+        let found = repo
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.name, "Test User");
+        assert_eq!(found.version, user.version);
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_write_leaves_cache_unchanged() {
+        let dir = std::env::temp_dir().join(format!("users-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("users.json");
+        let kept = User::fixture(TenantId::default(), "kept@example.com");
+        let unsaved = User::fixture(TenantId::default(), "unsaved@example.com");
+
+        let repo = FileUserRepository::new(&path).unwrap();
+        repo.save(&kept).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let results = repo.save_many(std::slice::from_ref(&unsaved)).await;
+        assert!(results[0].is_err());
+        assert!(repo
+            .delete_many(&TenantId::default(), &[kept.id])
+            .await
+            .is_err());
+
+        let found = repo
+            .find_many_by_ids(&TenantId::default(), &[kept.id, unsaved.id])
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, kept.id);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_file_is_rejected() {
+        let path = temp_file();
+        std::fs::write(&path, "not json").unwrap();
+
+        let result = FileUserRepository::new(&path);
+        assert!(matches!(result, Err(DomainError::Infrastructure(_))));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verification_token_consumption_persists() {
+        let path = temp_file();
+        let token = VerificationToken::new(
+            Uuid::new_v4(),
+            UserId::new(),
+            Email::new("test@example.com").unwrap(),
+            Utc::now(),
+            chrono::Duration::hours(1),
+        );
+
+        let repo = FileVerificationTokenRepository::new(&path).unwrap();
+        repo.save(&token).await.unwrap();
+        assert!(repo.consume(&token.id, Utc::now()).await.unwrap());
+
+        let reopened = FileVerificationTokenRepository::new(&path).unwrap();
+        assert!(!reopened.consume(&token.id, Utc::now()).await.unwrap());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_verification_token_write_leaves_it_unused() {
+        let dir = std::env::temp_dir().join(format!("users-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let token = VerificationToken::new(
+            Uuid::new_v4(),
+            UserId::new(),
+            Email::new("test@example.com").unwrap(),
+            Utc::now(),
+            chrono::Duration::hours(1),
+        );
+
+        let repo = FileVerificationTokenRepository::new(dir.join("tokens.json")).unwrap();
+        repo.save(&token).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(repo.consume(&token.id, Utc::now()).await.is_err());
+        assert!(!repo.find_by_id(&token.id).await.unwrap().unwrap().is_used());
+    }
+
+    #[tokio::test]
+    async fn test_failed_reset_token_write_leaves_it_unused() {
+        let dir = std::env::temp_dir().join(format!("users-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let token = PasswordResetToken::new(
+            "digest",
+            UserId::new(),
+            Utc::now(),
+            chrono::Duration::minutes(30),
+        );
+
+        let repo = FilePasswordResetTokenRepository::new(dir.join("tokens.json")).unwrap();
+        repo.save(&token).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(repo.consume("digest", Utc::now()).await.is_err());
+        assert!(!repo
+            .find_by_hash("digest")
+            .await
+            .unwrap()
+            .unwrap()
+            .is_used());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_survives_reload() {
+        let path = temp_file();
+        let acme = TenantId::new("acme").unwrap();
+        let user = User::fixture(acme.clone(), "test@example.com");
+
+        let repo = FileUserRepository::new(&path).unwrap();
+        repo.save(&user).await.unwrap();
+
+        let reopened = FileUserRepository::new(&path).unwrap();
+        assert!(reopened
+            .find_by_id(&acme, &user.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(reopened
+            .find_by_id(&TenantId::default(), &user.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -6,75 +6,510 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
+use super::{find, insert, remove, select, stream_by_ids, Partitions};
 use crate::domain::{
-    entities::{Email, User, UserId},
+    entities::{
+        Email, OutboxMessage, OutboxStatus, PasswordResetToken, TenantId, User, UserId,
+        VerificationToken,
+    },
     errors::DomainError,
-    ports::UserRepository,
+    ports::{
+        AggregateRoot, OutboxRepository, Page, PasswordResetTokenRepository, Repository, UserQuery,
+        UserRepository, UserStream, VerificationTokenRepository,
+    },
 };
 
-/// In-memory user repository for testing and development
-pub struct InMemoryUserRepository {
-    users: RwLock<HashMap<UserId, User>>,
+/// In-memory repository of any aggregate, for testing and development
+///
+/// Aggregates are partitioned by tenant.
+pub struct InMemoryRepository<A: AggregateRoot> {
+    aggregates: RwLock<Partitions<A>>,
 }
 
-impl InMemoryUserRepository {
+impl<A: AggregateRoot> InMemoryRepository<A> {
     /// Create a new empty in-memory repository
     pub fn new() -> Self {
         Self {
-            users: RwLock::new(HashMap::new()),
+            aggregates: RwLock::new(HashMap::new()),
         }
     }
 }
 
-impl Default for InMemoryUserRepository {
+impl<A: AggregateRoot> Default for InMemoryRepository<A> {
     fn default() -> Self {
         Self::new()
     }
 }
 
 #[async_trait]
-impl UserRepository for InMemoryUserRepository {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-        let users = self
-            .users
+impl<A: AggregateRoot> Repository<A> for InMemoryRepository<A> {
+    async fn find_by_id(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError> {
+        let aggregates = self
+            .aggregates
             .read()
             .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
-        Ok(users.get(id).cloned())
+        Ok(find(&aggregates, tenant, id, false))
     }
 
-    async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
-        let users = self
-            .users
+    async fn save(&self, aggregate: &A) -> Result<(), DomainError> {
+        let mut aggregates = self
+            .aggregates
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        insert(&mut aggregates, aggregate)
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &A::Id) -> Result<(), DomainError> {
+        let mut aggregates = self
+            .aggregates
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        remove(&mut aggregates, tenant, id);
+        Ok(())
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError> {
+        let aggregates = self
+            .aggregates
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(select(&aggregates, tenant, false))
+    }
+
+    async fn find_deleted(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError> {
+        let aggregates = self
+            .aggregates
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(find(&aggregates, tenant, id, true))
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError> {
+        let aggregates = self
+            .aggregates
             .read()
             .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
-        Ok(users.values().find(|u| &u.email == email).cloned())
+        Ok(select(&aggregates, tenant, true))
+    }
+}
+
+/// In-memory user repository for testing and development
+///
+/// Stores users in an [`InMemoryRepository`] next to a transactional
+/// outbox, exposed through [`OutboxRepository`], and the password reset
+/// tokens whose emails go through it.
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: InMemoryRepository<User>,
+    outbox: RwLock<Vec<OutboxMessage>>,
+    reset_tokens: InMemoryPasswordResetTokenRepository,
+}
+
+impl InMemoryUserRepository {
+    /// Create a new empty in-memory repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Repository<User> for InMemoryUserRepository {
+    async fn find_by_id(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.users.find_by_id(tenant, id).await
     }
 
     async fn save(&self, user: &User) -> Result<(), DomainError> {
-        let mut users = self
+        self.users.save(user).await
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &UserId) -> Result<(), DomainError> {
+        self.users.delete(tenant, id).await
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.users.list(tenant).await
+    }
+
+    async fn find_deleted(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.users.find_deleted(tenant, id).await
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.users.list_deleted(tenant).await
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError> {
+        let users =
+            self.users.aggregates.read().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        Ok(users
+            .get(tenant)
+            .and_then(|users| {
+                users
+                    .values()
+                    .find(|u| !u.is_deleted() && &u.email == email)
+            })
+            .cloned())
+    }
+
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        let users =
+            self.users.aggregates.read().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        let live = users
+            .get(tenant)
+            .into_iter()
+            .flat_map(|users| users.values())
+            .filter(|u| !u.is_deleted());
+        query.page(live)
+    }
+
+    fn stream<'a>(&'a self, tenant: &'a TenantId, query: UserQuery) -> UserStream<'a> {
+        let ids = self
             .users
+            .aggregates
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))
+            .and_then(|users| {
+                query.ordered_ids(
+                    users
+                        .get(tenant)
+                        .into_iter()
+                        .flat_map(|users| users.values())
+                        .filter(|u| !u.is_deleted()),
+                )
+            });
+        stream_by_ids(self, tenant, ids, query.limit)
+    }
+
+    async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>> {
+        match self.users.aggregates.write() {
+            Ok(mut aggregates) => users
+                .iter()
+                .map(|user| insert(&mut aggregates, user))
+                .collect(),
+            Err(e) => users
+                .iter()
+                .map(|_| {
+                    Err(DomainError::Infrastructure(anyhow::anyhow!(
+                        "Lock poisoned: {}",
+                        e
+                    )))
+                })
+                .collect(),
+        }
+    }
+
+    async fn find_many_by_ids(
+        &self,
+        tenant: &TenantId,
+        ids: &[UserId],
+    ) -> Result<Vec<User>, DomainError> {
+        let users =
+            self.users.aggregates.read().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| find(&users, tenant, id, false))
+            .collect())
+    }
+
+    async fn find_many_by_emails(
+        &self,
+        tenant: &TenantId,
+        emails: &[Email],
+    ) -> Result<Vec<User>, DomainError> {
+        let users =
+            self.users.aggregates.read().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        Ok(users
+            .get(tenant)
+            .map(|users| {
+                users
+                    .values()
+                    .filter(|u| !u.is_deleted() && emails.contains(&u.email))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError> {
+        let mut users =
+            self.users.aggregates.write().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        for id in ids {
+            remove(&mut users, tenant, id);
+        }
+        Ok(())
+    }
+
+    async fn save_with_outbox(
+        &self,
+        user: &User,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        // Hold both locks so nobody observes one write without the other
+        let mut users =
+            self.users.aggregates.write().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        let mut outbox = self
+            .outbox
             .write()
             .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
-        users.insert(user.id, user.clone());
+        insert(&mut users, user)?;
+        outbox.extend_from_slice(messages);
         Ok(())
     }
 
-    async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        let mut users = self
-            .users
+    async fn delete_with_outbox(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut users =
+            self.users.aggregates.write().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        let mut outbox = self
+            .outbox
             .write()
             .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
-        users.remove(id);
+        remove(&mut users, tenant, id);
+        outbox.extend_from_slice(messages);
         Ok(())
     }
+}
 
-    async fn list(&self) -> Result<Vec<User>, DomainError> {
-        let users = self
-            .users
+#[async_trait]
+impl OutboxRepository for InMemoryUserRepository {
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let outbox = self
+            .outbox
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(outbox
+            .iter()
+            .filter(|m| m.is_due(now))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        let mut outbox = self
+            .outbox
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        let stored = outbox
+            .iter_mut()
+            .find(|m| m.id == message.id)
+            .ok_or_else(|| DomainError::not_found::<OutboxMessage>(message.id))?;
+        *stored = message.clone();
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        tenant: &TenantId,
+        status: Option<OutboxStatus>,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let outbox = self
+            .outbox
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(outbox
+            .iter()
+            .filter(|m| &m.tenant_id == tenant && status.map_or(true, |s| m.status == s))
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for InMemoryUserRepository {
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError> {
+        self.reset_tokens.save(token).await
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError> {
+        self.reset_tokens.find_by_hash(token_hash).await
+    }
+
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        self.reset_tokens.consume(token_hash, used_at).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        token: &PasswordResetToken,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tokens =
+            self.reset_tokens.tokens.write().map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e))
+            })?;
+        let mut outbox = self
+            .outbox
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        tokens.insert(token.token_hash.clone(), token.clone());
+        outbox.extend_from_slice(messages);
+        Ok(())
+    }
+}
+
+/// In-memory verification token repository for testing and development
+pub struct InMemoryVerificationTokenRepository {
+    tokens: RwLock<HashMap<Uuid, VerificationToken>>,
+}
+
+impl InMemoryVerificationTokenRepository {
+    /// Create a new empty in-memory repository
+    pub fn new() -> Self {
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryVerificationTokenRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VerificationTokenRepository for InMemoryVerificationTokenRepository {
+    async fn save(&self, token: &VerificationToken) -> Result<(), DomainError> {
+        let mut tokens = self
+            .tokens
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        tokens.insert(token.id, token.clone());
+        Ok(())
+    }
+
+    async fn save_many(&self, tokens: &[VerificationToken]) -> Result<(), DomainError> {
+        let mut stored = self
+            .tokens
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        for token in tokens {
+            stored.insert(token.id, token.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<VerificationToken>, DomainError> {
+        let tokens = self
+            .tokens
             .read()
             .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
-        Ok(users.values().cloned().collect())
+        Ok(tokens.get(id).cloned())
+    }
+
+    async fn consume(&self, id: &Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let mut tokens = self
+            .tokens
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        match tokens.get_mut(id) {
+            Some(token) if !token.is_used() => {
+                token.used_at = Some(used_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// In-memory password reset token repository for testing and development
+pub struct InMemoryPasswordResetTokenRepository {
+    tokens: RwLock<HashMap<String, PasswordResetToken>>,
+}
+
+impl InMemoryPasswordResetTokenRepository {
+    /// Create a new empty in-memory repository
+    pub fn new() -> Self {
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryPasswordResetTokenRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for InMemoryPasswordResetTokenRepository {
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError> {
+        let mut tokens = self
+            .tokens
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        tokens.insert(token.token_hash.clone(), token.clone());
+        Ok(())
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError> {
+        let tokens = self
+            .tokens
+            .read()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        Ok(tokens.get(token_hash).cloned())
+    }
+
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let mut tokens = self
+            .tokens
+            .write()
+            .map_err(|e| DomainError::Infrastructure(anyhow::anyhow!("Lock poisoned: {}", e)))?;
+        match tokens.get_mut(token_hash) {
+            Some(token) if !token.is_used() => {
+                token.used_at = Some(used_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 }
 
@@ -82,58 +517,110 @@ impl UserRepository for InMemoryUserRepository {
 mod tests {
     use super::*;
 
+    super::super::conformance::user_repository_tests!(outbox: InMemoryUserRepository::new());
+
     #[tokio::test]
-    async fn test_save_and_find_by_id() {
+    async fn test_reset_token_written_with_outbox() {
         let repo = InMemoryUserRepository::new();
-        let email = Email::new("test@example.com").unwrap();
-        let user = User::new(email, "Test User");
-        let user_id = user.id;
+        super::super::conformance::test_reset_token_written_with_outbox(&repo).await;
+    }
 
-        repo.save(&user).await.unwrap();
+    #[tokio::test]
+    async fn test_verification_token_consumed_once() {
+        let repo = InMemoryVerificationTokenRepository::new();
+        let token = VerificationToken::new(
+            Uuid::new_v4(),
+            UserId::new(),
+            Email::new("test@example.com").unwrap(),
+            Utc::now(),
+            chrono::Duration::hours(1),
+        );
+        repo.save(&token).await.unwrap();
 
-        let found = repo.find_by_id(&user_id).await.unwrap();
-        assert!(found.is_some());
-        assert_eq!(found.unwrap().name, "Test User");
+        assert!(repo.consume(&token.id, Utc::now()).await.unwrap());
+        assert!(!repo.consume(&token.id, Utc::now()).await.unwrap());
+        assert!(repo.find_by_id(&token.id).await.unwrap().unwrap().is_used());
     }
 
     #[tokio::test]
-    async fn test_find_by_email() {
-        let repo = InMemoryUserRepository::new();
-        let email = Email::new("test@example.com").unwrap();
-        let user = User::new(email.clone(), "Test User");
+    async fn test_password_reset_token_consumed_once() {
+        let repo = InMemoryPasswordResetTokenRepository::new();
+        let token = PasswordResetToken::new(
+            "digest",
+            UserId::new(),
+            Utc::now(),
+            chrono::Duration::hours(1),
+        );
+        repo.save(&token).await.unwrap();
 
-        repo.save(&user).await.unwrap();
+        assert!(repo.find_by_hash("digest").await.unwrap().is_some());
+        assert!(repo.consume("digest", Utc::now()).await.unwrap());
+        assert!(!repo.consume("digest", Utc::now()).await.unwrap());
+    }
 
-        let found = repo.find_by_email(&email).await.unwrap();
-        assert!(found.is_some());
-        assert_eq!(found.unwrap().name, "Test User");
+    /// A minimal aggregate, standing in for entities other than users
+    #[derive(Debug, Clone, PartialEq)]
+    struct Note {
+        id: Uuid,
+        tenant_id: TenantId,
+        text: String,
+        version: u64,
     }
 
-    #[tokio::test]
-    async fn test_delete() {
-        let repo = InMemoryUserRepository::new();
-        let email = Email::new("test@example.com").unwrap();
-        let user = User::new(email, "Test User");
-        let user_id = user.id;
+    impl AggregateRoot for Note {
+        type Id = Uuid;
+
+        const NAME: &'static str = "Note";
+
+        fn id(&self) -> Uuid {
+            self.id
+        }
 
-        repo.save(&user).await.unwrap();
-        repo.delete(&user_id).await.unwrap();
+        fn tenant_id(&self) -> &TenantId {
+            &self.tenant_id
+        }
 
-        let found = repo.find_by_id(&user_id).await.unwrap();
-        assert!(found.is_none());
+        fn version(&self) -> u64 {
+            self.version
+        }
     }
 
     #[tokio::test]
-    async fn test_list() {
-        let repo = InMemoryUserRepository::new();
+    async fn test_generic_repository_stores_any_aggregate() {
+        let repo = InMemoryRepository::<Note>::new();
+        let tenant = TenantId::default();
+        let mut note = Note {
+            id: Uuid::new_v4(),
+            tenant_id: tenant.clone(),
+            text: "first".to_string(),
+            version: 1,
+        };
+        repo.save(&note).await.unwrap();
+
+        let mut stale = note.clone();
+        note.text = "second".to_string();
+        note.version = 2;
+        repo.save(&note).await.unwrap();
 
-        let user1 = User::new(Email::new("user1@example.com").unwrap(), "User 1");
-        let user2 = User::new(Email::new("user2@example.com").unwrap(), "User 2");
+        stale.text = "stale".to_string();
+        stale.version = 2;
+        assert!(matches!(
+            repo.save(&stale).await,
+            Err(DomainError::Conflict(_))
+        ));
 
-        repo.save(&user1).await.unwrap();
-        repo.save(&user2).await.unwrap();
+        assert_eq!(
+            repo.find_by_id(&tenant, &note.id).await.unwrap(),
+            Some(note.clone())
+        );
+        assert!(repo
+            .find_by_id(&TenantId::new("other").unwrap(), &note.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(repo.list(&tenant).await.unwrap().len(), 1);
 
-        let users = repo.list().await.unwrap();
-        assert_eq!(users.len(), 2);
+        repo.delete(&tenant, &note.id).await.unwrap();
+        assert!(repo.find_by_id(&tenant, &note.id).await.unwrap().is_none());
     }
 }
@@ -0,0 +1,809 @@
+//! PostgreSQL repository implementation
+//!
+//! Stores users, their transactional outbox and password reset tokens in
+//! PostgreSQL, through a connection pool sized from [`DatabaseConfig::max_connections`]. The
+//! schema is created by versioned migrations embedded in the binary and
+//! applied on connect.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::types::Json;
+use sqlx::{Acquire, PgConnection, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::ensure_current;
+use crate::config::DatabaseConfig;
+use crate::domain::{
+    entities::{Email, OutboxMessage, OutboxStatus, PasswordResetToken, TenantId, User, UserId},
+    errors::DomainError,
+    ports::{
+        AggregateRoot, Cursor, OutboxRepository, Page, PasswordResetTokenRepository, Repository,
+        SortDirection, UserQuery, UserRepository, UserSortKey,
+    },
+};
+
+/// Schema migrations, applied in version order
+static MIGRATOR: Migrator = sqlx::migrate!("migrations/postgres");
+
+/// Unique index on the email of live users
+const EMAIL_INDEX: &str = "users_email";
+
+/// Primary key of the users table
+const USERS_KEY: &str = "users_pkey";
+
+/// PostgreSQL user repository
+///
+/// Email addresses are unique among the live users of a tenant, enforced
+/// by the database as well as by [`UserService`](crate::domain::services::UserService);
+/// violations are reported as [`DomainError::Conflict`]. Also
+/// implements [`OutboxRepository`], writing messages in the same
+/// transaction as the user, and [`PasswordResetTokenRepository`].
+pub struct PostgresUserRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserRepository {
+    /// Connect to the database at `config.url` and apply pending migrations
+    ///
+    /// Accepts `postgres://` and `postgresql://` URLs. The pool holds up to
+    /// `config.max_connections` connections.
+    pub async fn connect(config: &DatabaseConfig) -> Result<Self, DomainError> {
+        if !(config.url.starts_with("postgres://") || config.url.starts_with("postgresql://")) {
+            // Not echoed, since URLs may carry credentials
+            return Err(DomainError::validation("Not a PostgreSQL database URL"));
+        }
+
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(&config.url)
+            .await
+            .map_err(database_error)?;
+        Self::new(pool).await
+    }
+
+    /// Use an existing pool, applying pending migrations
+    pub async fn new(pool: PgPool) -> Result<Self, DomainError> {
+        MIGRATOR
+            .run(&pool)
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        Ok(Self { pool })
+    }
+
+    /// The underlying connection pool
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// The live or deleted user of `tenant` with `id`
+    async fn find(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        deleted: bool,
+    ) -> Result<Option<User>, DomainError> {
+        let user: Option<Json<User>> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = $1 AND id = $2 AND (deleted_at IS NOT NULL) = $3",
+        )
+        .bind(tenant.as_str())
+        .bind(id.0)
+        .bind(deleted)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(user.map(|Json(user)| user))
+    }
+
+    /// The live or deleted users of `tenant`, oldest first
+    async fn select(&self, tenant: &TenantId, deleted: bool) -> Result<Vec<User>, DomainError> {
+        let users: Vec<Json<User>> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = $1 AND (deleted_at IS NOT NULL) = $2 \
+             ORDER BY created_at, id",
+        )
+        .bind(tenant.as_str())
+        .bind(deleted)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(users.into_iter().map(|Json(user)| user).collect())
+    }
+}
+
+/// Insert or update `user`, unless it is stale or its email is taken
+///
+/// Runs in a savepoint, so a rejected write leaves an enclosing
+/// transaction usable.
+async fn write(conn: &mut PgConnection, user: &User) -> Result<(), DomainError> {
+    let email = user.email.canonical();
+    let email_domain = email.rsplit_once('@').map_or("", |(_, domain)| domain);
+    let statement = if user.expected_version() == 0 {
+        "INSERT INTO users \
+         (email, email_domain, name_key, status, created_at, deleted_at, version, data, \
+          tenant_id, id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+    } else {
+        "UPDATE users SET \
+         email = $1, email_domain = $2, name_key = $3, status = $4, created_at = $5, \
+         deleted_at = $6, version = $7, data = $8 \
+         WHERE tenant_id = $9 AND id = $10 AND version = $11"
+    };
+
+    let mut savepoint = conn.begin().await.map_err(database_error)?;
+    let mut query = sqlx::query(statement)
+        .bind(email)
+        .bind(email_domain)
+        .bind(UserSortKey::Name.value(user))
+        .bind(user.status.kind().as_str())
+        .bind(UserSortKey::CreatedAt.value(user))
+        .bind(user.deleted_at)
+        .bind(user.version as i64)
+        .bind(Json(user.stored()))
+        .bind(user.tenant_id.as_str())
+        .bind(user.id.0);
+    if user.expected_version() != 0 {
+        query = query.bind(user.expected_version() as i64);
+    }
+    let result = query.execute(&mut *savepoint).await;
+
+    match result {
+        Ok(done) if done.rows_affected() == 1 => savepoint.commit().await.map_err(database_error),
+        Ok(_) => {
+            savepoint.rollback().await.map_err(database_error)?;
+            Err(stale(conn, user).await)
+        }
+        Err(e) => {
+            savepoint.rollback().await.map_err(database_error)?;
+            match constraint(&e) {
+                Some(EMAIL_INDEX) => Err(DomainError::conflict(format!(
+                    "User with email {} already exists",
+                    user.email
+                ))),
+                Some(USERS_KEY) => Err(stale(conn, user).await),
+                _ => Err(database_error(e)),
+            }
+        }
+    }
+}
+
+/// The conflict of saving `user` over a version it was not loaded at
+async fn stale(conn: &mut PgConnection, user: &User) -> DomainError {
+    let stored: Result<Option<Json<User>>, _> =
+        sqlx::query_scalar("SELECT data FROM users WHERE tenant_id = $1 AND id = $2")
+            .bind(user.tenant_id.as_str())
+            .bind(user.id.0)
+            .fetch_optional(&mut *conn)
+            .await;
+    match stored {
+        Ok(stored) => ensure_current(stored.as_ref().map(|Json(stored)| stored), user)
+            .err()
+            .unwrap_or_else(|| {
+                DomainError::conflict(format!("User {} was modified concurrently", user.id))
+            }),
+        Err(e) => database_error(e),
+    }
+}
+
+/// Remove the users of `tenant` with `ids`
+async fn remove(
+    conn: &mut PgConnection,
+    tenant: &TenantId,
+    ids: &[UserId],
+) -> Result<(), DomainError> {
+    let ids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
+    sqlx::query("DELETE FROM users WHERE tenant_id = $1 AND id = ANY($2)")
+        .bind(tenant.as_str())
+        .bind(ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(database_error)?;
+    Ok(())
+}
+
+/// Append `messages` to the outbox
+async fn enqueue(conn: &mut PgConnection, messages: &[OutboxMessage]) -> Result<(), DomainError> {
+    for message in messages {
+        sqlx::query(
+            "INSERT INTO outbox (id, tenant_id, status, next_attempt_at, data) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(message.id)
+        .bind(message.tenant_id.as_str())
+        .bind(message.status.as_str())
+        .bind(message.next_attempt_at)
+        .bind(Json(message))
+        .execute(&mut *conn)
+        .await
+        .map_err(database_error)?;
+    }
+    Ok(())
+}
+
+/// Store a newly issued password reset token
+async fn insert_reset_token(
+    conn: &mut PgConnection,
+    token: &PasswordResetToken,
+) -> Result<(), DomainError> {
+    sqlx::query(
+        "INSERT INTO password_reset_tokens (token_hash, used_at, data) VALUES ($1, $2, $3)",
+    )
+    .bind(&token.token_hash)
+    .bind(token.used_at)
+    .bind(Json(token))
+    .execute(&mut *conn)
+    .await
+    .map_err(database_error)?;
+    Ok(())
+}
+
+/// Restrict `builder` to the live users of `tenant` matching `query`
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, tenant: &TenantId, query: &UserQuery) {
+    builder
+        .push(" WHERE tenant_id = ")
+        .push_bind(tenant.as_str().to_string())
+        .push(" AND deleted_at IS NULL");
+    if let Some(domain) = query.canonical_email_domain() {
+        builder.push(" AND email_domain = ").push_bind(domain);
+    }
+    if let Some(prefix) = query.canonical_name_prefix() {
+        builder
+            .push(" AND starts_with(name_key, ")
+            .push_bind(prefix)
+            .push(")");
+    }
+    if let Some(from) = query.created_from {
+        builder
+            .push(" AND created_at >= ")
+            .push_bind(timestamp(from));
+    }
+    if let Some(until) = query.created_until {
+        builder
+            .push(" AND created_at < ")
+            .push_bind(timestamp(until));
+    }
+    if let Some(status) = query.status {
+        builder.push(" AND status = ").push_bind(status.as_str());
+    }
+}
+
+/// Column holding the values of `sort`
+fn sort_column(sort: UserSortKey) -> &'static str {
+    match sort {
+        UserSortKey::CreatedAt => "created_at",
+        UserSortKey::Email => "email",
+        UserSortKey::Name => "name_key",
+    }
+}
+
+/// `time` as a `created_at` key: RFC 3339 in UTC with nanoseconds
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// Name of the unique constraint `e` violated, if it violated one
+fn constraint(e: &sqlx::Error) -> Option<&str> {
+    e.as_database_error()
+        .filter(|e| e.is_unique_violation())
+        .and_then(|e| e.constraint())
+}
+
+/// Violations of the users' unique constraints are conflicts; anything
+/// else is an infrastructure error, since database messages name tables
+/// and constraints that callers must not see
+fn database_error(e: sqlx::Error) -> DomainError {
+    match constraint(&e) {
+        Some(EMAIL_INDEX) => DomainError::conflict("A user with this email already exists"),
+        Some(USERS_KEY) => DomainError::conflict("User was modified concurrently"),
+        _ => DomainError::Infrastructure(e.into()),
+    }
+}
+
+#[async_trait]
+impl Repository<User> for PostgresUserRepository {
+    async fn find_by_id(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.find(tenant, id, false).await
+    }
+
+    async fn save(&self, user: &User) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        write(&mut tx, user).await?;
+        tx.commit().await.map_err(database_error)
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &UserId) -> Result<(), DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(database_error)?;
+        remove(&mut conn, tenant, std::slice::from_ref(id)).await
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.select(tenant, false).await
+    }
+
+    async fn find_deleted(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.find(tenant, id, true).await
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.select(tenant, true).await
+    }
+}
+
+#[async_trait]
+impl UserRepository for PostgresUserRepository {
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError> {
+        let user: Option<Json<User>> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = $1 AND email = $2 AND deleted_at IS NULL",
+        )
+        .bind(tenant.as_str())
+        .bind(email.canonical())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(user.map(|Json(user)| user))
+    }
+
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        let start = query.start()?;
+        let column = sort_column(query.sort);
+        let (after, order) = match query.direction {
+            SortDirection::Ascending => (">", "ASC"),
+            SortDirection::Descending => ("<", "DESC"),
+        };
+
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM users");
+        push_filters(&mut count, tenant, query);
+        let total: i64 = count
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(database_error)?;
+
+        let mut select = QueryBuilder::new("SELECT data FROM users");
+        push_filters(&mut select, tenant, query);
+        if let Some(start) = start {
+            select
+                .push(format!(" AND ({}, id) {} (", column, after))
+                .push_bind(start.value)
+                .push(", ")
+                .push_bind(start.id)
+                .push(")");
+        }
+        // One extra row tells whether another page follows
+        select
+            .push(format!(
+                " ORDER BY {} {}, id {} LIMIT ",
+                column, order, order
+            ))
+            .push_bind(query.limit as i64 + 1);
+        let rows: Vec<Json<User>> = select
+            .build_query_scalar()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(database_error)?;
+
+        let mut items: Vec<User> = rows.into_iter().map(|Json(user)| user).collect();
+        let more = items.len() > query.limit;
+        items.truncate(query.limit);
+        let next_cursor = match items.last() {
+            Some(last) if more => Some(Cursor::new(&query.position_of(last))),
+            _ => None,
+        };
+
+        Ok(Page {
+            items,
+            next_cursor,
+            total: total as usize,
+        })
+    }
+
+    async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>> {
+        let mut tx = match self.pool.begin().await {
+            Ok(tx) => tx,
+            Err(e) => {
+                let message = e.to_string();
+                return users
+                    .iter()
+                    .map(|_| Err(DomainError::Infrastructure(anyhow::anyhow!("{}", message))))
+                    .collect();
+            }
+        };
+
+        // Each write has its own savepoint, so the others still commit
+        let mut results = Vec::with_capacity(users.len());
+        for user in users {
+            results.push(write(&mut tx, user).await);
+        }
+
+        if let Err(e) = tx.commit().await {
+            for result in results.iter_mut().filter(|r| r.is_ok()) {
+                *result = Err(DomainError::Infrastructure(anyhow::anyhow!("{}", e)));
+            }
+        }
+        results
+    }
+
+    async fn find_many_by_ids(
+        &self,
+        tenant: &TenantId,
+        ids: &[UserId],
+    ) -> Result<Vec<User>, DomainError> {
+        let uuids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
+        let rows: Vec<Json<User>> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = $1 AND deleted_at IS NULL AND id = ANY($2)",
+        )
+        .bind(tenant.as_str())
+        .bind(uuids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+
+        let mut found: HashMap<UserId, User> =
+            rows.into_iter().map(|Json(user)| (user.id, user)).collect();
+        Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
+    async fn find_many_by_emails(
+        &self,
+        tenant: &TenantId,
+        emails: &[Email],
+    ) -> Result<Vec<User>, DomainError> {
+        let canonical: Vec<&str> = emails.iter().map(Email::canonical).collect();
+        let rows: Vec<Json<User>> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = $1 AND deleted_at IS NULL AND email = ANY($2)",
+        )
+        .bind(tenant.as_str())
+        .bind(canonical)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(rows.into_iter().map(|Json(user)| user).collect())
+    }
+
+    async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(database_error)?;
+        remove(&mut conn, tenant, ids).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        user: &User,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        write(&mut tx, user).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+
+    async fn delete_with_outbox(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        remove(&mut tx, tenant, std::slice::from_ref(id)).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for PostgresUserRepository {
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let rows: Vec<Json<OutboxMessage>> = sqlx::query_scalar(
+            "SELECT data FROM outbox \
+             WHERE status = $1 AND next_attempt_at <= $2 \
+             ORDER BY seq LIMIT $3",
+        )
+        .bind(OutboxStatus::Pending.as_str())
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(rows.into_iter().map(|Json(message)| message).collect())
+    }
+
+    async fn update(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        sqlx::query("UPDATE outbox SET status = $1, next_attempt_at = $2, data = $3 WHERE id = $4")
+            .bind(message.status.as_str())
+            .bind(message.next_attempt_at)
+            .bind(Json(message))
+            .bind(message.id)
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        tenant: &TenantId,
+        status: Option<OutboxStatus>,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let rows: Vec<Json<OutboxMessage>> = sqlx::query_scalar(
+            "SELECT data FROM outbox \
+             WHERE tenant_id = $1 AND ($2::TEXT IS NULL OR status = $2) \
+             ORDER BY seq",
+        )
+        .bind(tenant.as_str())
+        .bind(status.map(OutboxStatus::as_str))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(rows.into_iter().map(|Json(message)| message).collect())
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for PostgresUserRepository {
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(database_error)?;
+        insert_reset_token(&mut conn, token).await
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError> {
+        let token: Option<Json<PasswordResetToken>> =
+            sqlx::query_scalar("SELECT data FROM password_reset_tokens WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(database_error)?;
+        Ok(token.map(|Json(token)| token))
+    }
+
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let done = sqlx::query(
+            "UPDATE password_reset_tokens \
+             SET used_at = $1, data = jsonb_set(data, '{used_at}', $2) \
+             WHERE token_hash = $3 AND used_at IS NULL",
+        )
+        .bind(used_at)
+        .bind(Json(used_at))
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(done.rows_affected() == 1)
+    }
+
+    async fn save_with_outbox(
+        &self,
+        token: &PasswordResetToken,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        insert_reset_token(&mut tx, token).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! These tests need a PostgreSQL database they may create schemas in,
+    //! named by `TEST_DATABASE_URL`; run them with `just test-postgres`.
+    //! Each test works in a schema of its own, dropped when it ends.
+
+    use std::str::FromStr;
+
+    use super::super::conformance::Fixture;
+    use super::*;
+
+    use sqlx::postgres::PgConnectOptions;
+    use sqlx::Connection;
+
+    /// Variable naming the database the tests run against
+    const TEST_DATABASE_URL_VAR: &str = "TEST_DATABASE_URL";
+
+    fn test_database_url() -> String {
+        std::env::var(TEST_DATABASE_URL_VAR)
+            .unwrap_or_else(|_| panic!("{} must name a PostgreSQL database", TEST_DATABASE_URL_VAR))
+    }
+
+    /// A fresh schema in the test database, dropped with the guard
+    struct TestSchema {
+        name: String,
+        options: PgConnectOptions,
+    }
+
+    impl TestSchema {
+        async fn create() -> Self {
+            let options = PgConnectOptions::from_str(&test_database_url()).unwrap();
+            let name = format!("test_{}", Uuid::new_v4().simple());
+            let mut conn = PgConnection::connect_with(&options).await.unwrap();
+            sqlx::query(&format!("CREATE SCHEMA {}", name))
+                .execute(&mut conn)
+                .await
+                .unwrap();
+            conn.close().await.unwrap();
+            Self { name, options }
+        }
+
+        /// The test database URL, connecting to this schema under its name
+        fn url(&self) -> String {
+            let url = test_database_url();
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!(
+                "{}{}options[search_path]={}&application_name={}",
+                url, separator, self.name, self.name
+            )
+        }
+    }
+
+    impl Drop for TestSchema {
+        fn drop(&mut self) {
+            // Drop cannot await, so the schema is dropped from a thread with
+            // its own runtime. The test's connections are closed first: one
+            // may still hold locks, waiting on the blocked test runtime to
+            // roll back a failed transaction.
+            let options = self.options.clone();
+            let name = self.name.clone();
+            let dropped = std::thread::spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+                runtime.block_on(async {
+                    let mut conn = PgConnection::connect_with(&options).await?;
+                    sqlx::query(
+                        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+                         WHERE application_name = $1",
+                    )
+                    .bind(&name)
+                    .execute(&mut conn)
+                    .await?;
+                    sqlx::query(&format!("DROP SCHEMA {} CASCADE", name))
+                        .execute(&mut conn)
+                        .await?;
+                    conn.close().await?;
+                    Ok::<_, anyhow::Error>(())
+                })
+            })
+            .join();
+            if let Ok(Err(e)) = dropped {
+                tracing::warn!("Failed to drop test schema {}: {}", self.name, e);
+            }
+        }
+    }
+
+    /// A repository in its own test schema
+    struct TestRepository {
+        repo: PostgresUserRepository,
+        _schema: TestSchema,
+    }
+
+    impl Fixture for TestRepository {
+        type Repository = PostgresUserRepository;
+
+        fn repository(&self) -> &PostgresUserRepository {
+            &self.repo
+        }
+    }
+
+    async fn repository() -> TestRepository {
+        let schema = TestSchema::create().await;
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect(&schema.url())
+            .await
+            .unwrap();
+        TestRepository {
+            repo: PostgresUserRepository::new(pool).await.unwrap(),
+            _schema: schema,
+        }
+    }
+
+    super::super::conformance::user_repository_tests!(
+        outbox: repository().await,
+        #[ignore = "needs a local PostgreSQL; see `just test-postgres`"]
+    );
+
+    #[tokio::test]
+    #[ignore = "needs a local PostgreSQL; see `just test-postgres`"]
+    async fn test_reset_token_written_with_outbox() {
+        let TestRepository { repo, _schema } = repository().await;
+        super::super::conformance::test_reset_token_written_with_outbox(&repo).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a local PostgreSQL; see `just test-postgres`"]
+    async fn test_email_is_unique_among_live_users() {
+        let TestRepository { repo, _schema } = repository().await;
+        let mut first = User::fixture(TenantId::default(), "test@example.com");
+        Repository::save(&repo, &first).await.unwrap();
+
+        let taken = Repository::save(
+            &repo,
+            &User::fixture(TenantId::default(), "TEST@example.com"),
+        )
+        .await;
+        assert!(matches!(taken, Err(DomainError::Conflict(_))));
+
+        // Once deleted, the address is free again
+        first.take_events();
+        first.delete(Utc::now()).unwrap();
+        Repository::save(&repo, &first).await.unwrap();
+        Repository::save(
+            &repo,
+            &User::fixture(TenantId::default(), "test@example.com"),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a local PostgreSQL; see `just test-postgres`"]
+    async fn test_other_unique_violations_are_not_conflicts() {
+        let TestRepository { repo, _schema } = repository().await;
+        let first = User::fixture(TenantId::default(), "first@example.com");
+        let messages = [OutboxMessage::new(
+            Uuid::new_v4(),
+            first.pending_events()[0].clone(),
+        )];
+        UserRepository::save_with_outbox(&repo, &first, &messages)
+            .await
+            .unwrap();
+
+        // The message ID is taken, which is no fault of the caller
+        let result = UserRepository::save_with_outbox(
+            &repo,
+            &User::fixture(TenantId::default(), "second@example.com"),
+            &messages,
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::Infrastructure(_))));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a local PostgreSQL; see `just test-postgres`"]
+    async fn test_connect_sizes_pool_and_migrates_once() {
+        let schema = TestSchema::create().await;
+        let config = DatabaseConfig {
+            url: schema.url(),
+            max_connections: 3,
+        };
+        let repo = PostgresUserRepository::connect(&config).await.unwrap();
+        assert_eq!(repo.pool().options().get_max_connections(), 3);
+
+        // Reconnecting finds the migrations already applied
+        PostgresUserRepository::connect(&config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_other_urls_are_rejected() {
+        let result = PostgresUserRepository::connect(&DatabaseConfig {
+            url: "sqlite://users.db".to_string(),
+            max_connections: 1,
+        })
+        .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
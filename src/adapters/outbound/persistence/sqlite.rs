@@ -0,0 +1,736 @@
+//! SQLite repository implementation
+//!
+//! Stores users, their transactional outbox and password reset tokens in a
+//! SQLite database. The
+//! schema is created by versioned migrations embedded in the binary and
+//! applied on connect.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+
+use super::ensure_current;
+use crate::config::DatabaseConfig;
+use crate::domain::{
+    entities::{Email, OutboxMessage, OutboxStatus, PasswordResetToken, TenantId, User, UserId},
+    errors::DomainError,
+    ports::{
+        AggregateRoot, Cursor, OutboxRepository, Page, PasswordResetTokenRepository, Repository,
+        SortDirection, UserQuery, UserRepository, UserSortKey,
+    },
+};
+
+/// Schema migrations, applied in version order
+static MIGRATOR: Migrator = sqlx::migrate!("migrations/sqlite");
+
+/// Most IDs bound in one statement, well below SQLite's variable limit
+const MAX_BATCH: usize = 500;
+
+/// SQLite user repository
+///
+/// Email addresses are unique among the live users of a tenant, enforced
+/// by the database as well as by [`UserService`](crate::domain::services::UserService).
+/// Also implements [`OutboxRepository`], writing messages in the same
+/// transaction as the user, and [`PasswordResetTokenRepository`].
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUserRepository {
+    /// Connect to the database at `config.url` and apply pending migrations
+    ///
+    /// Accepts `sqlite://path`, creating the file if it is missing, and
+    /// `sqlite::memory:`. The pool holds up to `config.max_connections`
+    /// connections.
+    pub async fn connect(config: &DatabaseConfig) -> Result<Self, DomainError> {
+        if !config.url.starts_with("sqlite:") {
+            return Err(DomainError::validation(format!(
+                "Not a SQLite database URL: {}",
+                config.url
+            )));
+        }
+
+        let options = SqliteConnectOptions::from_str(&config.url)
+            .map_err(database_error)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect_with(options)
+            .await
+            .map_err(database_error)?;
+        Self::new(pool).await
+    }
+
+    /// Use an existing pool, applying pending migrations
+    pub async fn new(pool: SqlitePool) -> Result<Self, DomainError> {
+        MIGRATOR
+            .run(&pool)
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        Ok(Self { pool })
+    }
+
+    /// The underlying connection pool
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// The live or deleted user of `tenant` with `id`
+    async fn find(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        deleted: bool,
+    ) -> Result<Option<User>, DomainError> {
+        let data: Option<String> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = ? AND id = ? AND (deleted_at IS NOT NULL) = ?",
+        )
+        .bind(tenant.as_str())
+        .bind(id.0.to_string())
+        .bind(deleted)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(database_error)?;
+        data.as_deref().map(decode).transpose()
+    }
+
+    /// The live or deleted users of `tenant`, oldest first
+    async fn select(&self, tenant: &TenantId, deleted: bool) -> Result<Vec<User>, DomainError> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = ? AND (deleted_at IS NOT NULL) = ? \
+             ORDER BY created_at, id",
+        )
+        .bind(tenant.as_str())
+        .bind(deleted)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        rows.iter().map(|data| decode(data)).collect()
+    }
+}
+
+/// Insert or update `user`, unless it is stale or its email is taken
+async fn write(conn: &mut SqliteConnection, user: &User) -> Result<(), DomainError> {
+    let email = user.email.canonical();
+    let email_domain = email.rsplit_once('@').map_or("", |(_, domain)| domain);
+    let data = encode(&user.stored())?;
+    let statement = if user.expected_version() == 0 {
+        "INSERT INTO users \
+         (email, email_domain, name_key, status, created_at, deleted_at, version, data, \
+          tenant_id, id) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    } else {
+        "UPDATE users SET \
+         email = ?, email_domain = ?, name_key = ?, status = ?, created_at = ?, \
+         deleted_at = ?, version = ?, data = ? \
+         WHERE tenant_id = ? AND id = ? AND version = ?"
+    };
+
+    let result = sqlx::query(statement)
+        .bind(email)
+        .bind(email_domain)
+        .bind(UserSortKey::Name.value(user))
+        .bind(user.status.kind().as_str())
+        .bind(UserSortKey::CreatedAt.value(user))
+        .bind(user.deleted_at.map(timestamp))
+        .bind(user.version as i64)
+        .bind(data)
+        .bind(user.tenant_id.as_str())
+        .bind(user.id.0.to_string())
+        .bind(user.expected_version() as i64)
+        .execute(&mut *conn)
+        .await;
+
+    match result {
+        Ok(done) if done.rows_affected() == 1 => Ok(()),
+        Ok(_) => Err(stale(conn, user).await),
+        Err(e) if is_unique_violation(&e) => {
+            if stored(conn, user).await?.is_some() {
+                Err(stale(conn, user).await)
+            } else {
+                Err(DomainError::conflict(format!(
+                    "User with email {} already exists",
+                    user.email
+                )))
+            }
+        }
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/// The stored row of `user`, live or deleted
+async fn stored(conn: &mut SqliteConnection, user: &User) -> Result<Option<User>, DomainError> {
+    let data: Option<String> =
+        sqlx::query_scalar("SELECT data FROM users WHERE tenant_id = ? AND id = ?")
+            .bind(user.tenant_id.as_str())
+            .bind(user.id.0.to_string())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(database_error)?;
+    data.as_deref().map(decode).transpose()
+}
+
+/// The conflict of saving `user` over a version it was not loaded at
+async fn stale(conn: &mut SqliteConnection, user: &User) -> DomainError {
+    match stored(conn, user).await {
+        Ok(stored) => ensure_current(stored.as_ref(), user)
+            .err()
+            .unwrap_or_else(|| {
+                DomainError::conflict(format!("User {} was modified concurrently", user.id))
+            }),
+        Err(e) => e,
+    }
+}
+
+/// Remove the users of `tenant` with `ids`
+async fn remove(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    ids: &[UserId],
+) -> Result<(), DomainError> {
+    for chunk in ids.chunks(MAX_BATCH) {
+        let mut delete = QueryBuilder::<Sqlite>::new("DELETE FROM users WHERE tenant_id = ");
+        delete.push_bind(tenant.as_str()).push(" AND id IN (");
+        let mut list = delete.separated(", ");
+        for id in chunk {
+            list.push_bind(id.0.to_string());
+        }
+        delete.push(")");
+        delete
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(database_error)?;
+    }
+    Ok(())
+}
+
+/// Append `messages` to the outbox
+async fn enqueue(
+    conn: &mut SqliteConnection,
+    messages: &[OutboxMessage],
+) -> Result<(), DomainError> {
+    for message in messages {
+        sqlx::query(
+            "INSERT INTO outbox (id, tenant_id, status, next_attempt_at, data) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(message.id.to_string())
+        .bind(message.tenant_id.as_str())
+        .bind(message.status.as_str())
+        .bind(timestamp(message.next_attempt_at))
+        .bind(encode(message)?)
+        .execute(&mut *conn)
+        .await
+        .map_err(database_error)?;
+    }
+    Ok(())
+}
+
+/// Store a newly issued password reset token
+async fn insert_reset_token(
+    conn: &mut SqliteConnection,
+    token: &PasswordResetToken,
+) -> Result<(), DomainError> {
+    sqlx::query("INSERT INTO password_reset_tokens (token_hash, used_at, data) VALUES (?, ?, ?)")
+        .bind(&token.token_hash)
+        .bind(token.used_at.map(timestamp))
+        .bind(encode(token)?)
+        .execute(&mut *conn)
+        .await
+        .map_err(database_error)?;
+    Ok(())
+}
+
+/// Restrict `builder` to the live users of `tenant` matching `query`
+fn push_filters(builder: &mut QueryBuilder<'_, Sqlite>, tenant: &TenantId, query: &UserQuery) {
+    builder
+        .push(" WHERE tenant_id = ")
+        .push_bind(tenant.as_str().to_string())
+        .push(" AND deleted_at IS NULL");
+    if let Some(domain) = query.canonical_email_domain() {
+        builder.push(" AND email_domain = ").push_bind(domain);
+    }
+    if let Some(prefix) = query.canonical_name_prefix() {
+        builder
+            .push(" AND substr(name_key, 1, ")
+            .push_bind(prefix.chars().count() as i64)
+            .push(") = ")
+            .push_bind(prefix);
+    }
+    if let Some(from) = query.created_from {
+        builder
+            .push(" AND created_at >= ")
+            .push_bind(timestamp(from));
+    }
+    if let Some(until) = query.created_until {
+        builder
+            .push(" AND created_at < ")
+            .push_bind(timestamp(until));
+    }
+    if let Some(status) = query.status {
+        builder.push(" AND status = ").push_bind(status.as_str());
+    }
+}
+
+/// Column holding the values of `sort`
+fn sort_column(sort: UserSortKey) -> &'static str {
+    match sort {
+        UserSortKey::CreatedAt => "created_at",
+        UserSortKey::Email => "email",
+        UserSortKey::Name => "name_key",
+    }
+}
+
+/// `time` as stored: RFC 3339 in UTC with nanoseconds, so it sorts as text
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<String, DomainError> {
+    serde_json::to_string(value).map_err(|e| DomainError::Infrastructure(e.into()))
+}
+
+fn decode<T: DeserializeOwned>(data: &str) -> Result<T, DomainError> {
+    serde_json::from_str(data).map_err(|e| {
+        DomainError::Infrastructure(anyhow::anyhow!("Invalid record in database: {}", e))
+    })
+}
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .is_some_and(|e| e.is_unique_violation())
+}
+
+fn database_error(e: sqlx::Error) -> DomainError {
+    DomainError::Infrastructure(e.into())
+}
+
+#[async_trait]
+impl Repository<User> for SqliteUserRepository {
+    async fn find_by_id(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.find(tenant, id, false).await
+    }
+
+    async fn save(&self, user: &User) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        write(&mut tx, user).await?;
+        tx.commit().await.map_err(database_error)
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: &UserId) -> Result<(), DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(database_error)?;
+        remove(&mut conn, tenant, std::slice::from_ref(id)).await
+    }
+
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.select(tenant, false).await
+    }
+
+    async fn find_deleted(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+    ) -> Result<Option<User>, DomainError> {
+        self.find(tenant, id, true).await
+    }
+
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError> {
+        self.select(tenant, true).await
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError> {
+        let data: Option<String> = sqlx::query_scalar(
+            "SELECT data FROM users \
+             WHERE tenant_id = ? AND email = ? AND deleted_at IS NULL",
+        )
+        .bind(tenant.as_str())
+        .bind(email.canonical())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(database_error)?;
+        data.as_deref().map(decode).transpose()
+    }
+
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError> {
+        let start = query.start()?;
+        let column = sort_column(query.sort);
+        let (after, order) = match query.direction {
+            SortDirection::Ascending => (">", "ASC"),
+            SortDirection::Descending => ("<", "DESC"),
+        };
+
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM users");
+        push_filters(&mut count, tenant, query);
+        let total: i64 = count
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(database_error)?;
+
+        let mut select = QueryBuilder::new("SELECT data FROM users");
+        push_filters(&mut select, tenant, query);
+        if let Some(start) = start {
+            select
+                .push(format!(" AND ({}, id) {} (", column, after))
+                .push_bind(start.value)
+                .push(", ")
+                .push_bind(start.id.to_string())
+                .push(")");
+        }
+        // One extra row tells whether another page follows
+        select
+            .push(format!(
+                " ORDER BY {} {}, id {} LIMIT ",
+                column, order, order
+            ))
+            .push_bind(query.limit as i64 + 1);
+        let rows: Vec<String> = select
+            .build_query_scalar()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(database_error)?;
+
+        let mut items = rows
+            .iter()
+            .map(|data| decode(data))
+            .collect::<Result<Vec<User>, _>>()?;
+        let more = items.len() > query.limit;
+        items.truncate(query.limit);
+        let next_cursor = match items.last() {
+            Some(last) if more => Some(Cursor::new(&query.position_of(last))),
+            _ => None,
+        };
+
+        Ok(Page {
+            items,
+            next_cursor,
+            total: total as usize,
+        })
+    }
+
+    async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>> {
+        let mut tx = match self.pool.begin().await {
+            Ok(tx) => tx,
+            Err(e) => {
+                let message = e.to_string();
+                return users
+                    .iter()
+                    .map(|_| Err(DomainError::Infrastructure(anyhow::anyhow!("{}", message))))
+                    .collect();
+            }
+        };
+
+        // A failed statement only undoes itself, so the others still commit
+        let mut results = Vec::with_capacity(users.len());
+        for user in users {
+            results.push(write(&mut tx, user).await);
+        }
+
+        if let Err(e) = tx.commit().await {
+            for result in results.iter_mut().filter(|r| r.is_ok()) {
+                *result = Err(DomainError::Infrastructure(anyhow::anyhow!("{}", e)));
+            }
+        }
+        results
+    }
+
+    async fn find_many_by_ids(
+        &self,
+        tenant: &TenantId,
+        ids: &[UserId],
+    ) -> Result<Vec<User>, DomainError> {
+        let mut found = HashMap::with_capacity(ids.len());
+        for chunk in ids.chunks(MAX_BATCH) {
+            let mut select =
+                QueryBuilder::<Sqlite>::new("SELECT data FROM users WHERE tenant_id = ");
+            select
+                .push_bind(tenant.as_str())
+                .push(" AND deleted_at IS NULL AND id IN (");
+            let mut list = select.separated(", ");
+            for id in chunk {
+                list.push_bind(id.0.to_string());
+            }
+            select.push(")");
+
+            let rows: Vec<String> = select
+                .build_query_scalar()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(database_error)?;
+            for data in rows {
+                let user: User = decode(&data)?;
+                found.insert(user.id, user);
+            }
+        }
+        Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
+    async fn find_many_by_emails(
+        &self,
+        tenant: &TenantId,
+        emails: &[Email],
+    ) -> Result<Vec<User>, DomainError> {
+        let mut users = Vec::with_capacity(emails.len());
+        for chunk in emails.chunks(MAX_BATCH) {
+            let mut select =
+                QueryBuilder::<Sqlite>::new("SELECT data FROM users WHERE tenant_id = ");
+            select
+                .push_bind(tenant.as_str())
+                .push(" AND deleted_at IS NULL AND email IN (");
+            let mut list = select.separated(", ");
+            for email in chunk {
+                list.push_bind(email.canonical());
+            }
+            select.push(")");
+
+            let rows: Vec<String> = select
+                .build_query_scalar()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(database_error)?;
+            for data in rows {
+                users.push(decode(&data)?);
+            }
+        }
+        Ok(users)
+    }
+
+    async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        remove(&mut tx, tenant, ids).await?;
+        tx.commit().await.map_err(database_error)
+    }
+
+    async fn save_with_outbox(
+        &self,
+        user: &User,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        write(&mut tx, user).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+
+    async fn delete_with_outbox(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        remove(&mut tx, tenant, std::slice::from_ref(id)).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for SqliteUserRepository {
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT data FROM outbox \
+             WHERE status = ? AND next_attempt_at <= ? \
+             ORDER BY seq LIMIT ?",
+        )
+        .bind(OutboxStatus::Pending.as_str())
+        .bind(timestamp(now))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        rows.iter().map(|data| decode(data)).collect()
+    }
+
+    async fn update(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        sqlx::query("UPDATE outbox SET status = ?, next_attempt_at = ?, data = ? WHERE id = ?")
+            .bind(message.status.as_str())
+            .bind(timestamp(message.next_attempt_at))
+            .bind(encode(message)?)
+            .bind(message.id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        tenant: &TenantId,
+        status: Option<OutboxStatus>,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        let status = status.map(OutboxStatus::as_str);
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT data FROM outbox \
+             WHERE tenant_id = ? AND (? IS NULL OR status = ?) \
+             ORDER BY seq",
+        )
+        .bind(tenant.as_str())
+        .bind(status)
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        rows.iter().map(|data| decode(data)).collect()
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for SqliteUserRepository {
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(database_error)?;
+        insert_reset_token(&mut conn, token).await
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError> {
+        let data: Option<String> =
+            sqlx::query_scalar("SELECT data FROM password_reset_tokens WHERE token_hash = ?")
+                .bind(token_hash)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(database_error)?;
+        data.as_deref().map(decode).transpose()
+    }
+
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let used_at = timestamp(used_at);
+        let done = sqlx::query(
+            "UPDATE password_reset_tokens \
+             SET used_at = ?, data = json_set(data, '$.used_at', ?) \
+             WHERE token_hash = ? AND used_at IS NULL",
+        )
+        .bind(&used_at)
+        .bind(&used_at)
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(done.rows_affected() == 1)
+    }
+
+    async fn save_with_outbox(
+        &self,
+        token: &PasswordResetToken,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        insert_reset_token(&mut tx, token).await?;
+        enqueue(&mut tx, messages).await?;
+        tx.commit().await.map_err(database_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use uuid::Uuid;
+
+    /// A repository over a fresh in-memory database
+    async fn repository() -> SqliteUserRepository {
+        SqliteUserRepository::connect(&DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+        })
+        .await
+        .unwrap()
+    }
+
+    super::super::conformance::user_repository_tests!(outbox: repository().await);
+
+    #[tokio::test]
+    async fn test_reset_token_written_with_outbox() {
+        let repo = repository().await;
+        super::super::conformance::test_reset_token_written_with_outbox(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn test_email_is_unique_among_live_users() {
+        let repo = repository().await;
+        let mut first = User::fixture(TenantId::default(), "test@example.com");
+        Repository::save(&repo, &first).await.unwrap();
+
+        let taken = Repository::save(
+            &repo,
+            &User::fixture(TenantId::default(), "TEST@example.com"),
+        )
+        .await;
+        assert!(matches!(taken, Err(DomainError::Conflict(_))));
+
+        // Once deleted, the address is free again
+        first.take_events();
+        first.delete(Utc::now()).unwrap();
+        Repository::save(&repo, &first).await.unwrap();
+        Repository::save(
+            &repo,
+            &User::fixture(TenantId::default(), "test@example.com"),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_database_is_migrated_once_and_reopened() {
+        let path = std::env::temp_dir().join(format!("users-{}.db", Uuid::new_v4()));
+        let config = DatabaseConfig {
+            url: format!("sqlite://{}", path.display()),
+            max_connections: 2,
+        };
+        let saved = User::fixture(TenantId::default(), "test@example.com");
+        {
+            let repo = SqliteUserRepository::connect(&config).await.unwrap();
+            Repository::save(&repo, &saved).await.unwrap();
+            repo.pool().close().await;
+        }
+
+        let repo = SqliteUserRepository::connect(&config).await.unwrap();
+        let found = repo
+            .find_by_id(&TenantId::default(), &saved.id)
+            .await
+            .unwrap();
+        assert_eq!(found.map(|u| u.email), Some(saved.email));
+        repo.pool().close().await;
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_other_urls_are_rejected() {
+        let result = SqliteUserRepository::connect(&DatabaseConfig {
+            url: "postgres://localhost/users".to_string(),
+            max_connections: 1,
+        })
+        .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
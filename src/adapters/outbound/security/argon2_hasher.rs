@@ -0,0 +1,138 @@
+//! Argon2id password hasher
+//!
+//! Hashes are encoded as PHC strings, so algorithm parameters travel with
+//! each hash and can be tuned without invalidating stored credentials.
+
+use argon2::password_hash::{
+    rand_core::OsRng, PasswordHash as PhcHash, PasswordHasher as _, PasswordVerifier as _,
+    SaltString,
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use async_trait::async_trait;
+
+use crate::domain::{entities::PasswordHash, errors::DomainError, ports::PasswordHasher};
+
+/// Argon2id password hasher
+///
+/// Hashing runs on tokio's blocking thread pool to keep the async
+/// executor responsive.
+#[derive(Clone)]
+pub struct Argon2PasswordHasher {
+    params: Params,
+}
+
+impl Argon2PasswordHasher {
+    /// Create a hasher with the recommended default parameters
+    pub fn new() -> Self {
+        Self {
+            params: Params::default(),
+        }
+    }
+
+    /// Create a hasher with custom cost parameters
+    ///
+    /// - `memory_kib`: memory size in KiB
+    /// - `iterations`: number of passes
+    /// - `parallelism`: degree of parallelism
+    pub fn with_params(
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    ) -> Result<Self, DomainError> {
+        let params = Params::new(memory_kib, iterations, parallelism, None).map_err(|e| {
+            DomainError::Infrastructure(anyhow::anyhow!("Invalid Argon2 parameters: {}", e))
+        })?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl Default for Argon2PasswordHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for Argon2PasswordHasher {
+    async fn hash(&self, password: &str) -> Result<PasswordHash, DomainError> {
+        let argon2 = self.argon2();
+        let password = password.to_owned();
+
+        tokio::task::spawn_blocking(move || {
+            let salt = SaltString::generate(&mut OsRng);
+            argon2
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| PasswordHash::new(hash.to_string()))
+                .map_err(|e| {
+                    DomainError::Infrastructure(anyhow::anyhow!("Password hashing failed: {}", e))
+                })
+        })
+        .await
+        .map_err(|e| DomainError::Infrastructure(e.into()))?
+    }
+
+    async fn verify(&self, password: &str, hash: &PasswordHash) -> Result<bool, DomainError> {
+        let argon2 = self.argon2();
+        let password = password.to_owned();
+        let encoded = hash.as_str().to_owned();
+
+        tokio::task::spawn_blocking(move || {
+            let parsed = PhcHash::new(&encoded).map_err(|e| {
+                DomainError::Infrastructure(anyhow::anyhow!("Malformed password hash: {}", e))
+            })?;
+
+            match argon2.verify_password(password.as_bytes(), &parsed) {
+                Ok(()) => Ok(true),
+                Err(argon2::password_hash::Error::Password) => Ok(false),
+                Err(e) => Err(DomainError::Infrastructure(anyhow::anyhow!(
+                    "Password verification failed: {}",
+                    e
+                ))),
+            }
+        })
+        .await
+        .map_err(|e| DomainError::Infrastructure(e.into()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so the tests stay fast
+    fn hasher() -> Argon2PasswordHasher {
+        Argon2PasswordHasher::with_params(8, 1, 1).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hash_and_verify() {
+        let hasher = hasher();
+        let hash = hasher.hash("correct horse").await.unwrap();
+
+        assert!(hash.as_str().starts_with("$argon2id$"));
+        assert!(hasher.verify("correct horse", &hash).await.unwrap());
+        assert!(!hasher.verify("wrong horse", &hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_hash_is_salted() {
+        let hasher = hasher();
+        let first = hasher.hash("correct horse").await.unwrap();
+        let second = hasher.hash("correct horse").await.unwrap();
+
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_hash_is_an_error() {
+        let result = hasher()
+            .verify("correct horse", &PasswordHash::new("not-a-hash"))
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Infrastructure(_))));
+    }
+}
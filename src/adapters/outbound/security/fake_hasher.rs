@@ -0,0 +1,56 @@
+//! Fake password hasher for tests
+//!
+//! Produces deterministic, unsalted "hashes" instantly. Never use it
+//! outside of tests.
+
+use async_trait::async_trait;
+
+use crate::domain::{entities::PasswordHash, errors::DomainError, ports::PasswordHasher};
+
+const PREFIX: &str = "fake$";
+
+/// Insecure password hasher that skips all key stretching
+pub struct FakePasswordHasher;
+
+impl FakePasswordHasher {
+    /// Create a new fake hasher
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for FakePasswordHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for FakePasswordHasher {
+    async fn hash(&self, password: &str) -> Result<PasswordHash, DomainError> {
+        Ok(PasswordHash::new(format!("{}{}", PREFIX, password)))
+    }
+
+    async fn verify(&self, password: &str, hash: &PasswordHash) -> Result<bool, DomainError> {
+        match hash.as_str().strip_prefix(PREFIX) {
+            Some(stored) => Ok(stored == password),
+            None => Err(DomainError::Infrastructure(anyhow::anyhow!(
+                "Not a fake password hash"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hash_and_verify() {
+        let hasher = FakePasswordHasher::new();
+        let hash = hasher.hash("correct horse").await.unwrap();
+
+        assert!(hasher.verify("correct horse", &hash).await.unwrap());
+        assert!(!hasher.verify("wrong horse", &hash).await.unwrap());
+    }
+}
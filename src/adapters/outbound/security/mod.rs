@@ -0,0 +1,16 @@
+//! Security adapters
+//!
+//! Implementations of security-related ports such as password hashing
+//! and token signing.
+//!
+//! - [`Argon2PasswordHasher`]: Argon2id, for production use
+//! - [`FakePasswordHasher`]: instant and insecure, for tests only
+//! - [`HmacTokenSigner`]: HMAC-SHA256 signatures for emailed links
+
+mod argon2_hasher;
+mod fake_hasher;
+mod hmac_signer;
+
+pub use argon2_hasher::Argon2PasswordHasher;
+pub use fake_hasher::FakePasswordHasher;
+pub use hmac_signer::HmacTokenSigner;
@@ -1,12 +1,31 @@
 //! CLI definition using clap
 
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
+use rust_hexagonal_template::domain::{
+    ports::{Cursor, UserSortKey, DEFAULT_PAGE_SIZE},
+    DisplayName, TenantId, UserStatusKind,
+};
 
 /// A CLI tool demonstrating hexagonal architecture
 #[derive(Parser)]
 #[command(name = "cli-tool")]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    /// Storage backend (`memory`, `file`, `events`)
+    #[arg(long, global = true, default_value = "file")]
+    pub store: String,
+
+    /// Data file used by the `file` store; the `events` store keeps its
+    /// streams in a directory next to it
+    #[arg(long, global = true, default_value = "users.json")]
+    pub data_file: PathBuf,
+
+    /// Tenant to operate in
+    #[arg(long, global = true, default_value = TenantId::DEFAULT)]
+    pub tenant: String,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -22,7 +41,23 @@ pub enum Commands {
 
         /// User's display name
         #[arg(short, long)]
-        name: String,
+        name: DisplayName,
+
+        /// Initial password (optional)
+        #[arg(short, long)]
+        password: Option<String>,
+    },
+
+    /// Check a user's email and password
+    #[command(name = "authenticate")]
+    Authenticate {
+        /// User's email address
+        #[arg(short, long)]
+        email: String,
+
+        /// User's password
+        #[arg(short, long)]
+        password: String,
     },
 
     /// Get a user by ID
@@ -33,15 +68,171 @@ pub enum Commands {
         id: String,
     },
 
-    /// List all users
+    /// List users, a page at a time
     #[command(name = "list-users")]
-    ListUsers,
+    ListUsers {
+        /// Only users with an email at this domain
+        #[arg(long)]
+        email_domain: Option<String>,
+
+        /// Only users whose name starts with this, ignoring case
+        #[arg(long)]
+        name_prefix: Option<String>,
+
+        /// Only users registered at or after this RFC 3339 time
+        #[arg(long)]
+        created_from: Option<String>,
+
+        /// Only users registered before this RFC 3339 time
+        #[arg(long)]
+        created_until: Option<String>,
+
+        /// Only users in this status (`pending`, `active`, `suspended`, `deactivated`)
+        #[arg(long)]
+        status: Option<UserStatusKind>,
+
+        /// Sort by `created_at`, `email` or `name`
+        #[arg(long, default_value = "created_at")]
+        sort: UserSortKey,
+
+        /// Sort in descending order
+        #[arg(long)]
+        desc: bool,
+
+        /// Users per page
+        #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+        limit: usize,
+
+        /// Continue after the page that printed this cursor
+        #[arg(long)]
+        cursor: Option<Cursor>,
+    },
+
+    /// Activate a pending user
+    #[command(name = "activate-user")]
+    ActivateUser {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+    },
+
+    /// Suspend an active user
+    #[command(name = "suspend-user")]
+    SuspendUser {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+
+        /// Why the user is suspended
+        #[arg(short, long)]
+        reason: String,
+    },
+
+    /// Lift a user's suspension
+    #[command(name = "reactivate-user")]
+    ReactivateUser {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+    },
+
+    /// Permanently deactivate a user
+    #[command(name = "deactivate-user")]
+    DeactivateUser {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+    },
 
-    /// Delete a user
+    /// Grant a role to a user
+    #[command(name = "assign-role")]
+    AssignRole {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+
+        /// Role name (`member`, `support`, `admin`)
+        #[arg(short, long)]
+        role: String,
+    },
+
+    /// Take a role away from a user
+    #[command(name = "revoke-role")]
+    RevokeRole {
+        /// User ID (UUID)
+        #[arg(short, long)]
+        id: String,
+
+        /// Role name (`member`, `support`, `admin`)
+        #[arg(short, long)]
+        role: String,
+    },
+
+    /// Confirm a user's email address
+    #[command(name = "verify-email")]
+    VerifyEmail {
+        /// Token from the verification link
+        #[arg(short, long)]
+        token: String,
+    },
+
+    /// Email a password reset link
+    #[command(name = "request-password-reset")]
+    RequestPasswordReset {
+        /// User's email address
+        #[arg(short, long)]
+        email: String,
+    },
+
+    /// Set a new password with a token from a reset link
+    #[command(name = "reset-password")]
+    ResetPassword {
+        /// Token from the reset link
+        #[arg(short, long)]
+        token: String,
+
+        /// New password
+        #[arg(short, long)]
+        password: String,
+    },
+
+    /// Delete a user; it can be restored until purged
     #[command(name = "delete-user")]
     DeleteUser {
         /// User ID to delete (UUID)
         #[arg(short, long)]
         id: String,
     },
+
+    /// Restore a deleted user
+    #[command(name = "restore-user")]
+    RestoreUser {
+        /// User ID to restore (UUID)
+        #[arg(short, long)]
+        id: String,
+    },
+
+    /// Show who changed users, and how
+    #[command(name = "audit")]
+    Audit {
+        /// Only show changes to this user (UUID)
+        #[arg(short, long)]
+        id: Option<String>,
+
+        /// Only show changes made at or after this RFC 3339 time
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Only show changes made before this RFC 3339 time
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Permanently remove users deleted a while ago
+    #[command(name = "purge")]
+    Purge {
+        /// Minimum days since deletion
+        #[arg(long, default_value_t = 30)]
+        older_than_days: i64,
+    },
 }
@@ -8,60 +8,264 @@
 //! cargo run --bin cli-tool -- --help
 //! cargo run --bin cli-tool -- create-user --email user@example.com --name "John Doe"
 //! cargo run --bin cli-tool -- list-users
+//! cargo run --bin cli-tool -- list-users --email-domain example.com --sort name --limit 20
+//! cargo run --bin cli-tool -- --store file --data-file /tmp/users.json list-users
+//! cargo run --bin cli-tool -- --store events --data-file /tmp/users.json list-users
+//! cargo run --bin cli-tool -- --tenant acme list-users
+//! cargo run --bin cli-tool -- purge --older-than-days 30
 //! ```
+//!
+//! Verification links are signed with `APP_TOKEN_SECRET`; set it to something
+//! private outside of development.
 
 mod cli;
-mod repository;
-mod types;
+mod storage;
+
+use std::sync::Arc;
 
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use clap::Parser;
 use colored::Colorize;
+use rust_hexagonal_template::adapters::outbound::{
+    events::InProcessEventBus,
+    external::ConsoleEmailService,
+    security::{Argon2PasswordHasher, HmacTokenSigner},
+};
+use rust_hexagonal_template::domain::{
+    events::{PasswordResetRequested, UserRegistered},
+    ports::{SortDirection, UserQuery, UserRepository},
+    services::{EmailVerification, PasswordReset, PasswordResetEmail, UserService, WelcomeEmail},
+    AuditQuery, DisplayName, Principal, Role, TenantId, User, UserId, UserStatus,
+};
 
 use crate::cli::{Cli, Commands};
-use crate::repository::FileUserRepository;
-use crate::types::{Email, User, UserId, UserRepository};
+use crate::storage::{RepositoryFactory, StoreOptions};
+
+/// Environment variable holding the secret used to sign emailed links
+const TOKEN_SECRET_VAR: &str = "APP_TOKEN_SECRET";
+
+/// Development-only fallback for [`TOKEN_SECRET_VAR`]
+const DEV_TOKEN_SECRET: &str = "insecure-development-secret";
+
+/// Whoever runs the CLI has direct access to the store, so acts as the system
+const OPERATOR: &Principal = &Principal::System;
+
+/// User service over a storage backend chosen at runtime
+type CliUserService = UserService<dyn UserRepository, ConsoleEmailService>;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let cli = Cli::parse();
 
-    // Initialize file-based repository
-    let repo = FileUserRepository::new("users.json")?;
-
-    match cli.command {
-        Commands::CreateUser { email, name } => {
-            create_user(&repo, &email, &name).await?;
-        }
-        Commands::GetUser { id } => {
-            get_user(&repo, &id).await?;
-        }
-        Commands::ListUsers => {
-            list_users(&repo).await?;
-        }
-        Commands::DeleteUser { id } => {
-            delete_user(&repo, &id).await?;
-        }
+    if let Err(e) = run(cli).await {
+        eprintln!("{} {}", "Error:".red(), e);
+        std::process::exit(1);
     }
-
-    Ok(())
 }
 
-async fn create_user(repo: &FileUserRepository, email: &str, name: &str) -> Result<()> {
-    let email = Email::new(email)?;
+async fn run(cli: Cli) -> Result<()> {
+    let options = StoreOptions {
+        data_file: cli.data_file,
+    };
+    let storage = RepositoryFactory::with_defaults().create(&cli.store, &options)?;
+    let tenant = &TenantId::new(&cli.tenant)?;
+    let token_secret =
+        std::env::var(TOKEN_SECRET_VAR).unwrap_or_else(|_| DEV_TOKEN_SECRET.to_string());
 
-    // Check for existing user
-    if repo.find_by_email(&email).await?.is_some() {
-        eprintln!(
-            "{} User with email {} already exists",
-            "Error:".red(),
-            email
-        );
-        std::process::exit(1);
+    let signer = Arc::new(HmacTokenSigner::new(token_secret));
+
+    let email_service = Arc::new(ConsoleEmailService::new());
+    let events = Arc::new(
+        InProcessEventBus::builder()
+            .subscribe::<UserRegistered>(Arc::new(WelcomeEmail::new(email_service.clone())))
+            .subscribe::<PasswordResetRequested>(Arc::new(PasswordResetEmail::new(
+                email_service.clone(),
+                signer.clone(),
+                "http://localhost:3000/reset-password",
+            )))
+            .start(),
+    );
+
+    let service = UserService::new(storage.users, email_service)
+        .with_event_publisher(events.clone())
+        .with_audit_log(storage.audit_log)
+        .with_password_hasher(Arc::new(Argon2PasswordHasher::new()))
+        .with_email_verification(EmailVerification {
+            tokens: storage.verification_tokens,
+            signer: signer.clone(),
+            link_base_url: "http://localhost:3000/verify-email".to_string(),
+            token_ttl: Duration::hours(24),
+        })
+        .with_password_reset(PasswordReset {
+            tokens: storage.password_reset_tokens,
+            signer,
+            token_ttl: Duration::minutes(30),
+        });
+
+    let outcome = async {
+        match cli.command {
+            Commands::CreateUser {
+                email,
+                name,
+                password,
+            } => {
+                create_user(&service, tenant, &email, name, password.as_deref()).await?;
+            }
+            Commands::Authenticate { email, password } => {
+                let user = service.authenticate(tenant, &email, &password).await?;
+                println!("{} Authenticated", "Success:".green());
+                print_user(&user);
+            }
+            Commands::GetUser { id } => {
+                get_user(&service, tenant, &id).await?;
+            }
+            Commands::ListUsers {
+                email_domain,
+                name_prefix,
+                created_from,
+                created_until,
+                status,
+                sort,
+                desc,
+                limit,
+                cursor,
+            } => {
+                let query = UserQuery {
+                    email_domain,
+                    name_prefix,
+                    created_from: created_from.as_deref().map(parse_time).transpose()?,
+                    created_until: created_until.as_deref().map(parse_time).transpose()?,
+                    status,
+                    sort,
+                    direction: if desc {
+                        SortDirection::Descending
+                    } else {
+                        SortDirection::Ascending
+                    },
+                    after: cursor,
+                    limit,
+                };
+                list_users(&service, tenant, &query).await?;
+            }
+            Commands::ActivateUser { id } => {
+                let user = service
+                    .activate(tenant, OPERATOR, &parse_user_id(&id)?, None)
+                    .await?;
+                print_status_change("activated", &user);
+            }
+            Commands::SuspendUser { id, reason } => {
+                let user = service
+                    .suspend(tenant, OPERATOR, &parse_user_id(&id)?, &reason, None)
+                    .await?;
+                print_status_change("suspended", &user);
+            }
+            Commands::ReactivateUser { id } => {
+                let user = service
+                    .reactivate(tenant, OPERATOR, &parse_user_id(&id)?, None)
+                    .await?;
+                print_status_change("reactivated", &user);
+            }
+            Commands::DeactivateUser { id } => {
+                let user = service
+                    .deactivate(tenant, OPERATOR, &parse_user_id(&id)?, None)
+                    .await?;
+                print_status_change("deactivated", &user);
+            }
+            Commands::AssignRole { id, role } => {
+                let role: Role = role.parse()?;
+                let user = service
+                    .assign_role(tenant, OPERATOR, &parse_user_id(&id)?, role, None)
+                    .await?;
+                println!("{} Assigned role {}", "Success:".green(), role);
+                print_user(&user);
+            }
+            Commands::RevokeRole { id, role } => {
+                let role: Role = role.parse()?;
+                let user = service
+                    .revoke_role(tenant, OPERATOR, &parse_user_id(&id)?, role, None)
+                    .await?;
+                println!("{} Revoked role {}", "Success:".green(), role);
+                print_user(&user);
+            }
+            Commands::VerifyEmail { token } => {
+                let user = service.verify_email(tenant, &token).await?;
+                println!("{} Email verified", "Success:".green());
+                print_user(&user);
+            }
+            Commands::RequestPasswordReset { email } => {
+                service.request_password_reset(tenant, &email).await?;
+                println!(
+                    "{} If an account exists for {}, a reset link is on its way",
+                    "Success:".green(),
+                    email
+                );
+            }
+            Commands::ResetPassword { token, password } => {
+                service.reset_password(tenant, &token, &password).await?;
+                println!("{} Password updated", "Success:".green());
+            }
+            Commands::DeleteUser { id } => {
+                delete_user(&service, tenant, &id).await?;
+            }
+            Commands::RestoreUser { id } => {
+                let user = service
+                    .restore(tenant, OPERATOR, &parse_user_id(&id)?, None)
+                    .await?;
+                println!("{} User restored", "Success:".green());
+                print_user(&user);
+            }
+            Commands::Audit { id, from, until } => {
+                let query = AuditQuery {
+                    user_id: id.as_deref().map(parse_user_id).transpose()?,
+                    from: from.as_deref().map(parse_time).transpose()?,
+                    until: until.as_deref().map(parse_time).transpose()?,
+                };
+                show_audit_log(&service, tenant, &query).await?;
+            }
+            Commands::Purge { older_than_days } => {
+                let report = service
+                    .purge_deleted(tenant, OPERATOR, Duration::days(older_than_days))
+                    .await?;
+                println!(
+                    "{} Purged {} deleted user(s)",
+                    "Success:".green(),
+                    report.purged
+                );
+                for (id, e) in &report.failures {
+                    eprintln!("{} Could not purge {}: {}", "Error:".red(), id, e);
+                }
+                if !report.failures.is_empty() {
+                    anyhow::bail!("{} user(s) could not be purged", report.failures.len());
+                }
+            }
+        }
+
+        Ok::<_, anyhow::Error>(())
     }
+    .await;
 
-    let user = User::new(email, name);
-    repo.save(&user).await?;
+    // Deliver queued events such as the welcome email before exiting
+    events.shutdown().await;
+
+    outcome
+}
+
+async fn create_user(
+    service: &CliUserService,
+    tenant: &TenantId,
+    email: &str,
+    name: DisplayName,
+    password: Option<&str>,
+) -> Result<()> {
+    let user = match password {
+        Some(password) => {
+            service
+                .register_with_password(tenant, email, name, password)
+                .await?
+        }
+        None => service.register(tenant, email, name).await?,
+    };
 
     println!("{} Created user", "Success:".green());
     print_user(&user);
@@ -69,27 +273,19 @@ async fn create_user(repo: &FileUserRepository, email: &str, name: &str) -> Resu
     Ok(())
 }
 
-async fn get_user(repo: &FileUserRepository, id: &str) -> Result<()> {
-    let uuid = uuid::Uuid::parse_str(id)?;
-    let user_id = UserId(uuid);
-
-    match repo.find_by_id(&user_id).await? {
-        Some(user) => {
-            print_user(&user);
-        }
-        None => {
-            eprintln!("{} User not found", "Error:".red());
-            std::process::exit(1);
-        }
-    }
+async fn get_user(service: &CliUserService, tenant: &TenantId, id: &str) -> Result<()> {
+    let user = service
+        .get_by_id(tenant, OPERATOR, &parse_user_id(id)?)
+        .await?;
+    print_user(&user);
 
     Ok(())
 }
 
-async fn list_users(repo: &FileUserRepository) -> Result<()> {
-    let users = repo.list().await?;
+async fn list_users(service: &CliUserService, tenant: &TenantId, query: &UserQuery) -> Result<()> {
+    let page = service.list(tenant, OPERATOR, query).await?;
 
-    if users.is_empty() {
+    if page.items.is_empty() {
         println!("No users found.");
         return Ok(());
     }
@@ -97,37 +293,115 @@ async fn list_users(repo: &FileUserRepository) -> Result<()> {
     println!("{}", "Users:".bold());
     println!("{}", "-".repeat(60));
 
-    for user in users {
+    for user in &page.items {
         println!(
-            "{}: {} <{}>",
+            "{}: {} <{}> [{}]",
             user.id.0.to_string().dimmed(),
-            user.name.bold(),
-            user.email
+            user.name.as_str().bold(),
+            user.email,
+            user.status
         );
     }
 
+    println!("{}", "-".repeat(60));
+    println!("{} of {} user(s)", page.items.len(), page.total);
+    if let Some(cursor) = page.next_cursor {
+        println!("{} --cursor {}", "Next page:".dimmed(), cursor);
+    }
+
+    Ok(())
+}
+
+async fn delete_user(service: &CliUserService, tenant: &TenantId, id: &str) -> Result<()> {
+    service
+        .delete(tenant, OPERATOR, &parse_user_id(id)?, None)
+        .await?;
+    println!("{} User deleted", "Success:".green());
+
     Ok(())
 }
 
-async fn delete_user(repo: &FileUserRepository, id: &str) -> Result<()> {
-    let uuid = uuid::Uuid::parse_str(id)?;
-    let user_id = UserId(uuid);
+async fn show_audit_log(
+    service: &CliUserService,
+    tenant: &TenantId,
+    query: &AuditQuery,
+) -> Result<()> {
+    let records = service.audit_log(tenant, OPERATOR, query).await?;
 
-    // Verify user exists
-    if repo.find_by_id(&user_id).await?.is_none() {
-        eprintln!("{} User not found", "Error:".red());
-        std::process::exit(1);
+    if records.is_empty() {
+        println!("No audit records found.");
+        return Ok(());
     }
 
-    repo.delete(&user_id).await?;
-    println!("{} User deleted", "Success:".green());
+    for record in records {
+        println!(
+            "{} {} {} by {}",
+            record.occurred_at.to_rfc3339().dimmed(),
+            record.action.bold(),
+            record.user_id,
+            record.actor
+        );
+        for change in record.changes {
+            let show = |value: Option<serde_json::Value>| {
+                value.map_or_else(|| "-".to_string(), |v| v.to_string())
+            };
+            println!(
+                "    {}: {} -> {}",
+                change.field,
+                show(change.before),
+                show(change.after)
+            );
+        }
+    }
 
     Ok(())
 }
 
+fn parse_user_id(id: &str) -> Result<UserId> {
+    Ok(UserId::from_uuid(uuid::Uuid::parse_str(id)?))
+}
+
+fn parse_time(time: &str) -> Result<DateTime<Utc>> {
+    let time = DateTime::parse_from_rfc3339(time)
+        .map_err(|e| anyhow::anyhow!("Invalid time {}: {}", time, e))?;
+    Ok(time.with_timezone(&Utc))
+}
+
 fn print_user(user: &User) {
     println!("  {}: {}", "ID".dimmed(), user.id);
-    println!("  {}: {}", "Email".dimmed(), user.email);
+    println!("  {}: {}", "Tenant".dimmed(), user.tenant_id);
+    println!(
+        "  {}: {} ({})",
+        "Email".dimmed(),
+        user.email,
+        if user.is_email_verified() {
+            "verified"
+        } else {
+            "unverified"
+        }
+    );
     println!("  {}: {}", "Name".dimmed(), user.name);
+    println!("  {}: {}", "Status".dimmed(), user.status);
+    println!("  {}: {}", "Version".dimmed(), user.version);
+    println!(
+        "  {}: {}",
+        "Roles".dimmed(),
+        user.roles
+            .iter()
+            .map(|r| r.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if let UserStatus::Suspended { reason } = &user.status {
+        println!("  {}: {}", "Reason".dimmed(), reason);
+    }
     println!("  {}: {}", "Created".dimmed(), user.created_at);
+    if let Some(deleted_at) = user.deleted_at {
+        println!("  {}: {}", "Deleted".dimmed(), deleted_at);
+    }
+}
+
+fn print_status_change(action: &str, user: &User) {
+    println!("{} User {}", "Success:".green(), action);
+    print_user(user);
 }
@@ -0,0 +1,134 @@
+//! Storage backend factory
+//!
+//! Resolves the `--store` option to repository implementations at runtime.
+//! Register additional backends with [`RepositoryFactory::register`].
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rust_hexagonal_template::adapters::outbound::persistence::{
+    EventSourcedUserRepository, FileAuditLog, FileEventStore, FilePasswordResetTokenRepository,
+    FileUserRepository, FileVerificationTokenRepository, InMemoryAuditLog,
+    InMemoryPasswordResetTokenRepository, InMemoryUserRepository,
+    InMemoryVerificationTokenRepository,
+};
+use rust_hexagonal_template::domain::ports::{
+    AuditLog, PasswordResetTokenRepository, UserRepository, VerificationTokenRepository,
+};
+
+/// Events after which the `events` store snapshots a user
+const SNAPSHOT_EVERY: u64 = 50;
+
+/// Options shared by all storage backends
+pub struct StoreOptions {
+    /// Path used by file-based backends
+    pub data_file: PathBuf,
+}
+
+impl StoreOptions {
+    /// File holding verification tokens, next to the data file
+    fn tokens_file(&self) -> PathBuf {
+        self.data_file.with_extension("tokens.json")
+    }
+
+    /// File holding password reset token digests, next to the data file
+    fn resets_file(&self) -> PathBuf {
+        self.data_file.with_extension("resets.json")
+    }
+
+    /// Append-only audit log, next to the data file
+    fn audit_file(&self) -> PathBuf {
+        self.data_file.with_extension("audit.jsonl")
+    }
+
+    /// Directory holding user event streams, next to the data file
+    fn events_dir(&self) -> PathBuf {
+        self.data_file.with_extension("events")
+    }
+}
+
+/// Repositories provided by a storage backend
+pub struct Storage {
+    pub users: Arc<dyn UserRepository>,
+    pub verification_tokens: Arc<dyn VerificationTokenRepository>,
+    pub password_reset_tokens: Arc<dyn PasswordResetTokenRepository>,
+    pub audit_log: Arc<dyn AuditLog>,
+}
+
+/// Constructor for a storage backend
+type Constructor = Box<dyn Fn(&StoreOptions) -> anyhow::Result<Storage> + Send + Sync>;
+
+/// Registry of named storage backends
+pub struct RepositoryFactory {
+    constructors: BTreeMap<&'static str, Constructor>,
+}
+
+impl RepositoryFactory {
+    /// Create a factory with the bundled backends (`memory`, `file`, `events`)
+    pub fn with_defaults() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+        .register("memory", |_| {
+            Ok(Storage {
+                users: Arc::new(InMemoryUserRepository::new()),
+                verification_tokens: Arc::new(InMemoryVerificationTokenRepository::new()),
+                password_reset_tokens: Arc::new(InMemoryPasswordResetTokenRepository::new()),
+                audit_log: Arc::new(InMemoryAuditLog::new()),
+            })
+        })
+        .register("file", |options| {
+            Ok(Storage {
+                users: Arc::new(FileUserRepository::new(&options.data_file)?),
+                verification_tokens: Arc::new(FileVerificationTokenRepository::new(
+                    options.tokens_file(),
+                )?),
+                password_reset_tokens: Arc::new(FilePasswordResetTokenRepository::new(
+                    options.resets_file(),
+                )?),
+                audit_log: Arc::new(FileAuditLog::new(options.audit_file())),
+            })
+        })
+        .register("events", |options| {
+            let store = Arc::new(FileEventStore::new(options.events_dir())?);
+            Ok(Storage {
+                users: Arc::new(
+                    EventSourcedUserRepository::new(store).with_snapshots(SNAPSHOT_EVERY),
+                ),
+                verification_tokens: Arc::new(FileVerificationTokenRepository::new(
+                    options.tokens_file(),
+                )?),
+                password_reset_tokens: Arc::new(FilePasswordResetTokenRepository::new(
+                    options.resets_file(),
+                )?),
+                audit_log: Arc::new(FileAuditLog::new(options.audit_file())),
+            })
+        })
+    }
+
+    /// Register a backend under `name`, replacing any existing one
+    pub fn register<F>(mut self, name: &'static str, constructor: F) -> Self
+    where
+        F: Fn(&StoreOptions) -> anyhow::Result<Storage> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name, Box::new(constructor));
+        self
+    }
+
+    /// Build the backend registered under `name`
+    pub fn create(&self, name: &str, options: &StoreOptions) -> anyhow::Result<Storage> {
+        match self.constructors.get(name) {
+            Some(constructor) => constructor(options),
+            None => anyhow::bail!(
+                "Unknown store '{}' (available: {})",
+                name,
+                self.constructors
+                    .keys()
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
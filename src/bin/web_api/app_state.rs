@@ -2,187 +2,233 @@
 
 use std::sync::Arc;
 
-use async_trait::async_trait;
+use chrono::Duration;
+use rust_hexagonal_template::adapters::inbound::http::TenantResolver;
+#[cfg(feature = "postgres")]
+use rust_hexagonal_template::adapters::outbound::persistence::PostgresUserRepository;
+#[cfg(feature = "sqlite")]
+use rust_hexagonal_template::adapters::outbound::persistence::SqliteUserRepository;
+use rust_hexagonal_template::adapters::outbound::{
+    events::InProcessEventBus,
+    external::ConsoleEmailService,
+    persistence::{InMemoryAuditLog, InMemoryUserRepository, InMemoryVerificationTokenRepository},
+    security::{Argon2PasswordHasher, HmacTokenSigner},
+};
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+use rust_hexagonal_template::config::DatabaseConfig;
+use rust_hexagonal_template::domain::{
+    events::{PasswordResetRequested, UserRegistered},
+    ports::{
+        AuditLog, EmailService, OutboxRepository, PasswordResetTokenRepository, UserRepository,
+        VerificationTokenRepository,
+    },
+    services::{
+        EmailVerification, OutboxRelay, PasswordReset, PasswordResetEmail, UserService,
+        WelcomeEmail,
+    },
+    DisplayName, DomainError, Principal, Role, TenantId,
+};
+
+/// Environment variable holding the secret used to sign emailed links
+const TOKEN_SECRET_VAR: &str = "APP_TOKEN_SECRET";
+
+/// Environment variables naming the administrator created at startup
+const ADMIN_EMAIL_VAR: &str = "APP_ADMIN_EMAIL";
+const ADMIN_PASSWORD_VAR: &str = "APP_ADMIN_PASSWORD";
+const ADMIN_TENANT_VAR: &str = "APP_ADMIN_TENANT";
+
+/// Environment variable naming the domain under which tenant subdomains live
+const TENANT_BASE_DOMAIN_VAR: &str = "APP_TENANT_BASE_DOMAIN";
+
+/// Page that receives verification links and posts the token to `/users/verify`
+const VERIFY_LINK_BASE_URL: &str = "http://localhost:3000/verify-email";
+
+/// Page that receives reset links and posts to `/auth/password-reset/confirm`
+const RESET_LINK_BASE_URL: &str = "http://localhost:3000/reset-password";
 
-// Domain types (in real project, import from main crate)
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::RwLock;
-use uuid::Uuid;
-
-// =============================================================================
-// Domain Layer (copied from main crate for example independence)
-// =============================================================================
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct UserId(pub Uuid);
-
-impl UserId {
-    pub fn new() -> Self {
-        Self(Uuid::new_v4())
-    }
-}
-
-impl Default for UserId {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl std::fmt::Display for UserId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
+/// Shared application state
+///
+/// Every user endpoint goes through the domain [`UserService`], so validation,
+/// conflict checks and the welcome email live in one place.
+pub struct AppState<R, E>
+where
+    R: UserRepository,
+    E: EmailService,
+{
+    pub user_service: Arc<UserService<R, E>>,
+    pub tenants: TenantResolver,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Email(String);
+impl<R, E> AppState<R, E>
+where
+    R: UserRepository,
+    E: EmailService + 'static,
+{
+    /// Create state from existing adapters
+    ///
+    /// Passwords are hashed with Argon2id; emailed tokens are signed and
+    /// digested with HMAC-SHA256. Domain events are stored in `outbox`
+    /// together with each change, and a background relay delivers them to
+    /// subscribers such as the welcome and password reset emails. `outbox`
+    /// must share storage with `repository` and `password_reset_tokens`.
+    /// Every change is recorded in `audit_log`. Must be called within a
+    /// Tokio runtime.
+    pub fn new(
+        repository: Arc<R>,
+        outbox: Arc<dyn OutboxRepository>,
+        audit_log: Arc<dyn AuditLog>,
+        email_service: Arc<E>,
+        verification_tokens: Arc<dyn VerificationTokenRepository>,
+        password_reset_tokens: Arc<dyn PasswordResetTokenRepository>,
+    ) -> Self {
+        let signer = Arc::new(HmacTokenSigner::new(token_secret()));
+        let subscribers = InProcessEventBus::builder()
+            .subscribe::<UserRegistered>(Arc::new(WelcomeEmail::new(email_service.clone())))
+            .subscribe::<PasswordResetRequested>(Arc::new(PasswordResetEmail::new(
+                email_service.clone(),
+                signer.clone(),
+                RESET_LINK_BASE_URL,
+            )))
+            .dispatcher();
+        let relay = OutboxRelay::new(outbox.clone(), Arc::new(subscribers));
+        tokio::spawn(async move { relay.run().await });
+
+        let user_service = UserService::new(repository, email_service)
+            .with_outbox(outbox)
+            .with_audit_log(audit_log)
+            .with_password_hasher(Arc::new(Argon2PasswordHasher::new()))
+            .with_email_verification(EmailVerification {
+                tokens: verification_tokens,
+                signer: signer.clone(),
+                link_base_url: VERIFY_LINK_BASE_URL.to_string(),
+                token_ttl: Duration::hours(24),
+            })
+            .with_password_reset(PasswordReset {
+                tokens: password_reset_tokens,
+                signer,
+                token_ttl: Duration::minutes(30),
+            });
 
-impl Email {
-    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
-        let value = value.into();
-        if value.is_empty() || !value.contains('@') {
-            return Err(DomainError::ValidationError("Invalid email".into()));
+        Self {
+            user_service: Arc::new(user_service),
+            tenants: tenant_resolver(),
         }
-        Ok(Self(value.to_lowercase()))
     }
 
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for Email {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    /// Create the administrator named by `APP_ADMIN_EMAIL`/`APP_ADMIN_PASSWORD`
+    ///
+    /// Without an admin nobody could list users or grant roles over HTTP.
+    /// The admin belongs to `APP_ADMIN_TENANT`, or the default tenant.
+    /// Does nothing when the variables are unset or the account exists.
+    pub async fn bootstrap_admin(&self) -> Result<(), DomainError> {
+        let (Ok(email), Ok(password)) = (
+            std::env::var(ADMIN_EMAIL_VAR),
+            std::env::var(ADMIN_PASSWORD_VAR),
+        ) else {
+            tracing::warn!(
+                "{} and {} are not set; no administrator available",
+                ADMIN_EMAIL_VAR,
+                ADMIN_PASSWORD_VAR
+            );
+            return Ok(());
+        };
+        let tenant = match std::env::var(ADMIN_TENANT_VAR) {
+            Ok(tenant) => TenantId::new(tenant)?,
+            Err(_) => TenantId::default(),
+        };
+
+        let name = DisplayName::new("Administrator")?;
+        let admin = match self
+            .user_service
+            .register_with_password(&tenant, &email, name, &password)
+            .await
+        {
+            Ok(user) => user,
+            Err(DomainError::Conflict(_)) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        self.user_service
+            .assign_role(&tenant, &Principal::System, &admin.id, Role::Admin, None)
+            .await?;
+
+        tracing::info!("Created administrator {} in tenant {}", email, tenant);
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct User {
-    pub id: UserId,
-    pub email: Email,
-    pub name: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
-
-impl User {
-    pub fn new(email: Email, name: impl Into<String>) -> Self {
-        let now = Utc::now();
-        Self {
-            id: UserId::new(),
-            email,
-            name: name.into(),
-            created_at: now,
-            updated_at: now,
-        }
+impl AppState<InMemoryUserRepository, ConsoleEmailService> {
+    /// Create state with in-memory repository (for development)
+    pub fn new_in_memory() -> Self {
+        let repository = Arc::new(InMemoryUserRepository::new());
+        Self::new(
+            repository.clone(),
+            repository.clone(),
+            Arc::new(InMemoryAuditLog::new()),
+            Arc::new(ConsoleEmailService::new()),
+            Arc::new(InMemoryVerificationTokenRepository::new()),
+            repository,
+        )
     }
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum DomainError {
-    #[error("Entity not found: {entity_type} with id {id}")]
-    NotFound { entity_type: &'static str, id: Uuid },
-    #[error("Validation error: {0}")]
-    ValidationError(String),
-    #[error("Conflict: {0}")]
-    Conflict(String),
-    #[error("Infrastructure error: {0}")]
-    Infrastructure(#[from] anyhow::Error),
-}
-
-impl DomainError {
-    pub fn not_found<T>(id: Uuid) -> Self {
-        Self::NotFound {
-            entity_type: std::any::type_name::<T>(),
-            id,
-        }
+#[cfg(feature = "postgres")]
+impl AppState<PostgresUserRepository, ConsoleEmailService> {
+    /// Create state with users, their outbox and password reset tokens in
+    /// the PostgreSQL database at `config.url`; verification tokens and the
+    /// audit log stay in memory
+    pub async fn new_postgres(config: &DatabaseConfig) -> Result<Self, DomainError> {
+        let repository = Arc::new(PostgresUserRepository::connect(config).await?);
+        Ok(Self::new(
+            repository.clone(),
+            repository.clone(),
+            Arc::new(InMemoryAuditLog::new()),
+            Arc::new(ConsoleEmailService::new()),
+            Arc::new(InMemoryVerificationTokenRepository::new()),
+            repository,
+        ))
     }
 }
 
-// =============================================================================
-// Ports
-// =============================================================================
-
-#[async_trait]
-pub trait UserRepository: Send + Sync {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError>;
-    async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError>;
-    async fn save(&self, user: &User) -> Result<(), DomainError>;
-    async fn delete(&self, id: &UserId) -> Result<(), DomainError>;
-    async fn list(&self) -> Result<Vec<User>, DomainError>;
-}
-
-// =============================================================================
-// In-Memory Repository
-// =============================================================================
-
-pub struct InMemoryUserRepository {
-    users: RwLock<HashMap<UserId, User>>,
-}
-
-impl InMemoryUserRepository {
-    pub fn new() -> Self {
-        Self {
-            users: RwLock::new(HashMap::new()),
-        }
+#[cfg(feature = "sqlite")]
+impl AppState<SqliteUserRepository, ConsoleEmailService> {
+    /// Create state with users, their outbox and password reset tokens in
+    /// the SQLite database at `config.url`; verification tokens and the
+    /// audit log stay in memory
+    pub async fn new_sqlite(config: &DatabaseConfig) -> Result<Self, DomainError> {
+        let repository = Arc::new(SqliteUserRepository::connect(config).await?);
+        Ok(Self::new(
+            repository.clone(),
+            repository.clone(),
+            Arc::new(InMemoryAuditLog::new()),
+            Arc::new(ConsoleEmailService::new()),
+            Arc::new(InMemoryVerificationTokenRepository::new()),
+            repository,
+        ))
     }
 }
 
-#[async_trait]
-impl UserRepository for InMemoryUserRepository {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-        let users = self.users.read().unwrap();
-        Ok(users.get(id).cloned())
+/// Resolve tenants from `X-Tenant-Id`, then from subdomains of
+/// `APP_TENANT_BASE_DOMAIN` if set, else use the default tenant
+fn tenant_resolver() -> TenantResolver {
+    let resolver = TenantResolver::new().with_fallback(TenantId::default());
+    match std::env::var(TENANT_BASE_DOMAIN_VAR) {
+        Ok(domain) if !domain.is_empty() => resolver.with_base_domain(domain),
+        _ => resolver,
     }
-
-    async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
-        let users = self.users.read().unwrap();
-        Ok(users.values().find(|u| &u.email == email).cloned())
-    }
-
-    async fn save(&self, user: &User) -> Result<(), DomainError> {
-        let mut users = self.users.write().unwrap();
-        users.insert(user.id, user.clone());
-        Ok(())
-    }
-
-    async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        let mut users = self.users.write().unwrap();
-        users.remove(id);
-        Ok(())
-    }
-
-    async fn list(&self) -> Result<Vec<User>, DomainError> {
-        let users = self.users.read().unwrap();
-        Ok(users.values().cloned().collect())
-    }
-}
-
-// =============================================================================
-// Application State
-// =============================================================================
-
-/// Shared application state
-pub struct AppState {
-    pub user_repository: Arc<dyn UserRepository>,
 }
 
-impl AppState {
-    /// Create state with in-memory repository (for development)
-    pub fn new_in_memory() -> Self {
-        Self {
-            user_repository: Arc::new(InMemoryUserRepository::new()),
+/// Read the token signing secret, falling back to a random per-process one
+fn token_secret() -> Vec<u8> {
+    match std::env::var(TOKEN_SECRET_VAR) {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => {
+            tracing::warn!(
+                "{} is not set; using a random secret, links will not survive restarts",
+                TOKEN_SECRET_VAR
+            );
+            let mut secret = uuid::Uuid::new_v4().as_bytes().to_vec();
+            secret.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+            secret
         }
     }
-
-    // In production, add:
-    // pub async fn new_postgres(database_url: &str) -> Result<Self> {
-    //     let pool = PgPool::connect(database_url).await?;
-    //     Ok(Self {
-    //         user_repository: Arc::new(PostgresUserRepository::new(pool)),
-    //     })
-    // }
 }
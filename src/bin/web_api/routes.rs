@@ -2,24 +2,25 @@
 
 use std::sync::Arc;
 
-use axum::{
-    routing::{delete, get, post},
-    Router,
-};
+use axum::{routing::get, Router};
+use rust_hexagonal_template::adapters::inbound::http::user_router;
+use rust_hexagonal_template::domain::ports::{EmailService, UserRepository};
 
 use crate::app_state::AppState;
 use crate::handlers;
 
 /// Create the application router
-pub fn create_router(state: Arc<AppState>) -> Router {
+pub fn create_router<R, E>(state: Arc<AppState<R, E>>) -> Router
+where
+    R: UserRepository + 'static,
+    E: EmailService + 'static,
+{
     Router::new()
         // Health check
         .route("/health", get(handlers::health))
         // User routes
-        .route("/users", post(handlers::create_user))
-        .route("/users", get(handlers::list_users))
-        .route("/users/{id}", get(handlers::get_user))
-        .route("/users/{id}", delete(handlers::delete_user))
-        // Add state
-        .with_state(state)
+        .merge(user_router(
+            state.user_service.clone(),
+            state.tenants.clone(),
+        ))
 }
@@ -0,0 +1,268 @@
+//! Access control types
+//!
+//! Roles grant permissions; a [`Principal`] is whoever performs an operation.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::tenant::TenantId;
+use super::user::{User, UserId};
+use crate::domain::errors::DomainError;
+
+/// Operation that may require authorization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Read any user's profile
+    ReadUsers,
+    /// List all users
+    ListUsers,
+    /// Change any user's profile
+    UpdateUsers,
+    /// Delete any user
+    DeleteUsers,
+    /// Restore deleted users
+    RestoreUsers,
+    /// Permanently remove deleted users
+    PurgeUsers,
+    /// Activate, suspend, reactivate or deactivate users
+    ManageUserStatus,
+    /// Assign and revoke roles
+    ManageRoles,
+    /// See the delivery state of outgoing events
+    InspectOutbox,
+    /// Read the audit log
+    ReadAuditLog,
+}
+
+impl Permission {
+    /// Whether users hold this permission over their own account
+    fn granted_to_self(self) -> bool {
+        matches!(
+            self,
+            Self::ReadUsers | Self::UpdateUsers | Self::DeleteUsers
+        )
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ReadUsers => "read_users",
+            Self::ListUsers => "list_users",
+            Self::UpdateUsers => "update_users",
+            Self::DeleteUsers => "delete_users",
+            Self::RestoreUsers => "restore_users",
+            Self::PurgeUsers => "purge_users",
+            Self::ManageUserStatus => "manage_user_status",
+            Self::ManageRoles => "manage_roles",
+            Self::InspectOutbox => "inspect_outbox",
+            Self::ReadAuditLog => "read_audit_log",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Named bundle of permissions assigned to users
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Regular account; may only act on itself
+    Member,
+    /// Help desk; may inspect users and change their status
+    Support,
+    /// Full control over all users
+    Admin,
+}
+
+impl Role {
+    /// Permissions granted by this role
+    pub fn permissions(self) -> &'static [Permission] {
+        use Permission::*;
+
+        match self {
+            Self::Member => &[],
+            Self::Support => &[
+                ReadUsers,
+                ListUsers,
+                ManageUserStatus,
+                InspectOutbox,
+                ReadAuditLog,
+            ],
+            Self::Admin => &[
+                ReadUsers,
+                ListUsers,
+                UpdateUsers,
+                DeleteUsers,
+                RestoreUsers,
+                PurgeUsers,
+                ManageUserStatus,
+                ManageRoles,
+                InspectOutbox,
+                ReadAuditLog,
+            ],
+        }
+    }
+
+    /// Short machine-readable name of the role
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::Support => "support",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "member" => Ok(Self::Member),
+            "support" => Ok(Self::Support),
+            "admin" => Ok(Self::Admin),
+            other => Err(DomainError::validation(format!("Unknown role: {}", other))),
+        }
+    }
+}
+
+/// Default role set for new users
+pub(crate) fn default_roles() -> BTreeSet<Role> {
+    BTreeSet::from([Role::Member])
+}
+
+/// Whoever performs an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Principal {
+    /// Trusted operator or internal process; holds every permission
+    System,
+    /// An authenticated user; confined to its own tenant
+    User {
+        id: UserId,
+        tenant_id: TenantId,
+        roles: BTreeSet<Role>,
+    },
+}
+
+impl Principal {
+    /// Principal acting on behalf of `user`
+    pub fn from_user(user: &User) -> Self {
+        Self::User {
+            id: user.id,
+            tenant_id: user.tenant_id.clone(),
+            roles: user.roles.clone(),
+        }
+    }
+
+    /// Whether the principal may perform `permission` in `tenant`,
+    /// optionally on `target`
+    ///
+    /// Users never act outside their own tenant, and always hold self-service
+    /// permissions over their own account.
+    pub fn can(&self, tenant: &TenantId, permission: Permission, target: Option<&UserId>) -> bool {
+        match self {
+            Self::System => true,
+            Self::User { tenant_id, .. } if tenant_id != tenant => false,
+            Self::User { id, roles, .. } => {
+                (permission.granted_to_self() && target == Some(id))
+                    || roles
+                        .iter()
+                        .any(|role| role.permissions().contains(&permission))
+            }
+        }
+    }
+
+    /// Fail with `Forbidden` unless the principal [`can`](Self::can) act
+    pub fn authorize(
+        &self,
+        tenant: &TenantId,
+        permission: Permission,
+        target: Option<&UserId>,
+    ) -> Result<(), DomainError> {
+        if self.can(tenant, permission, target) {
+            Ok(())
+        } else {
+            Err(DomainError::forbidden(format!(
+                "Missing permission: {}",
+                permission
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant() -> TenantId {
+        TenantId::default()
+    }
+
+    fn principal(roles: &[Role]) -> (Principal, UserId) {
+        let id = UserId::new();
+        let principal = Principal::User {
+            id,
+            tenant_id: TenantId::default(),
+            roles: roles.iter().copied().collect(),
+        };
+        (principal, id)
+    }
+
+    #[test]
+    fn test_member_may_only_act_on_self() {
+        let (member, own_id) = principal(&[Role::Member]);
+        let other = UserId::new();
+
+        assert!(member.can(&tenant(), Permission::ReadUsers, Some(&own_id)));
+        assert!(member.can(&tenant(), Permission::DeleteUsers, Some(&own_id)));
+        assert!(!member.can(&tenant(), Permission::ReadUsers, Some(&other)));
+        assert!(!member.can(&tenant(), Permission::ListUsers, None));
+        assert!(!member.can(&tenant(), Permission::ManageRoles, Some(&own_id)));
+    }
+
+    #[test]
+    fn test_support_and_admin_permissions() {
+        let (support, _) = principal(&[Role::Support]);
+        let (admin, _) = principal(&[Role::Admin]);
+        let other = UserId::new();
+
+        assert!(support.can(&tenant(), Permission::ListUsers, None));
+        assert!(support.can(&tenant(), Permission::ManageUserStatus, Some(&other)));
+        assert!(!support.can(&tenant(), Permission::DeleteUsers, Some(&other)));
+        assert!(admin.can(&tenant(), Permission::DeleteUsers, Some(&other)));
+        assert!(Principal::System.can(&tenant(), Permission::ManageRoles, Some(&other)));
+    }
+
+    #[test]
+    fn test_users_are_confined_to_their_tenant() {
+        let (admin, own_id) = principal(&[Role::Admin]);
+        let other_tenant = TenantId::new("other").unwrap();
+
+        assert!(!admin.can(&other_tenant, Permission::ListUsers, None));
+        assert!(!admin.can(&other_tenant, Permission::ReadUsers, Some(&own_id)));
+        assert!(Principal::System.can(&other_tenant, Permission::ListUsers, None));
+    }
+
+    #[test]
+    fn test_authorize_returns_forbidden() {
+        let (member, _) = principal(&[Role::Member]);
+
+        assert!(matches!(
+            member.authorize(&tenant(), Permission::ListUsers, None),
+            Err(DomainError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn test_role_parsing() {
+        assert_eq!("admin".parse::<Role>().unwrap(), Role::Admin);
+        assert!("root".parse::<Role>().is_err());
+    }
+}
@@ -0,0 +1,253 @@
+//! Audit log records
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use super::access::Principal;
+use super::tenant::TenantId;
+use super::user::{User, UserId};
+
+/// Fields that change with every write and would only clutter a diff
+const BOOKKEEPING_FIELDS: &[&str] = &["updated_at", "version"];
+
+/// Fields whose values must never appear in the audit log
+const SECRET_FIELDS: &[&str] = &["password_hash"];
+
+/// Stand-in for the value of a secret field
+const REDACTED: &str = "[redacted]";
+
+/// Who performed an audited change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum AuditActor {
+    /// Trusted operator or internal process
+    System,
+    /// An authenticated user
+    User(UserId),
+    /// Someone who did not sign in, e.g. when registering or following an
+    /// emailed link
+    Anonymous,
+}
+
+impl From<&Principal> for AuditActor {
+    fn from(principal: &Principal) -> Self {
+        match principal {
+            Principal::System => Self::System,
+            Principal::User { id, .. } => Self::User(*id),
+        }
+    }
+}
+
+impl std::fmt::Display for AuditActor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::System => f.write_str("system"),
+            Self::User(id) => write!(f, "user {}", id),
+            Self::Anonymous => f.write_str("anonymous"),
+        }
+    }
+}
+
+/// Value of one field before and after a change
+///
+/// `None` means the field was absent: unset, or the whole user did not
+/// exist yet or any more.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl FieldChange {
+    /// Fields that differ between two states of a user
+    ///
+    /// `before` is `None` for a new user, `after` for a removed one.
+    /// Bookkeeping fields are left out and secrets are redacted, so a
+    /// password change shows up without revealing either hash.
+    pub fn between(before: Option<&User>, after: Option<&User>) -> Vec<Self> {
+        let before = fields(before);
+        let after = fields(after);
+
+        let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter(|name| !BOOKKEEPING_FIELDS.contains(&name.as_str()))
+            .filter(|name| before.get(*name) != after.get(*name))
+            .map(|name| {
+                let value = |fields: &Map<String, Value>| {
+                    fields.get(name).map(|value| {
+                        if SECRET_FIELDS.contains(&name.as_str()) {
+                            Value::from(REDACTED)
+                        } else {
+                            value.clone()
+                        }
+                    })
+                };
+                Self {
+                    field: name.clone(),
+                    before: value(&before),
+                    after: value(&after),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A user's serialized fields, without nulls
+fn fields(user: Option<&User>) -> Map<String, Value> {
+    match user.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
+/// A change made to a user, as kept in the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Unique record identifier
+    pub id: Uuid,
+    /// Tenant of the changed user
+    pub tenant_id: TenantId,
+    /// The changed user
+    pub user_id: UserId,
+    /// Who made the change
+    pub actor: AuditActor,
+    /// Operation that made the change, e.g. `update_name`
+    pub action: String,
+    /// When the change was made
+    pub occurred_at: DateTime<Utc>,
+    /// Fields that changed
+    pub changes: Vec<FieldChange>,
+}
+
+impl AuditRecord {
+    /// Record `id` of `action` by `actor` on `user`, happening at `now`
+    pub fn new(
+        id: Uuid,
+        user: &User,
+        actor: AuditActor,
+        action: impl Into<String>,
+        changes: Vec<FieldChange>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            tenant_id: user.tenant_id.clone(),
+            user_id: user.id,
+            actor,
+            action: action.into(),
+            occurred_at: now,
+            changes,
+        }
+    }
+}
+
+/// Which audit records to read
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    /// Only records about this user
+    pub user_id: Option<UserId>,
+    /// Only records made at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only records made before this time
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditQuery {
+    /// Records about `user_id`
+    pub fn for_user(user_id: UserId) -> Self {
+        Self {
+            user_id: Some(user_id),
+            ..Self::default()
+        }
+    }
+
+    /// Whether `record` is selected by the query
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.user_id.map_or(true, |id| record.user_id == id)
+            && self.from.map_or(true, |from| record.occurred_at >= from)
+            && self.until.map_or(true, |until| record.occurred_at < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DisplayName, PasswordHash};
+
+    fn user() -> User {
+        User::fixture(TenantId::default(), "test@example.com")
+    }
+
+    #[test]
+    fn test_changes_between_states() {
+        let before = user();
+        let mut after = before.clone();
+        after.update_name(DisplayName::new("New").unwrap(), Utc::now());
+        after.set_password_hash(PasswordHash::new("secret hash"), Utc::now());
+
+        let changes = FieldChange::between(Some(&before), Some(&after));
+        assert_eq!(
+            changes,
+            [
+                FieldChange {
+                    field: "name".to_string(),
+                    before: Some(Value::from("Test User")),
+                    after: Some(Value::from("New")),
+                },
+                FieldChange {
+                    field: "password_hash".to_string(),
+                    before: None,
+                    after: Some(Value::from(REDACTED)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_creation_and_removal_list_every_field() {
+        let user = user();
+
+        let created = FieldChange::between(None, Some(&user));
+        assert!(created.iter().all(|c| c.before.is_none()));
+        assert!(created.iter().any(|c| c.field == "email"));
+        assert!(!created.iter().any(|c| c.field == "version"));
+
+        let removed = FieldChange::between(Some(&user), None);
+        assert_eq!(removed.len(), created.len());
+        assert!(removed.iter().all(|c| c.after.is_none()));
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let user = user();
+        let record = AuditRecord::new(
+            Uuid::now_v7(),
+            &user,
+            AuditActor::System,
+            "register",
+            Vec::new(),
+            Utc::now(),
+        );
+
+        assert!(AuditQuery::default().matches(&record));
+        assert!(AuditQuery::for_user(user.id).matches(&record));
+        assert!(!AuditQuery::for_user(UserId::new()).matches(&record));
+
+        let range = AuditQuery {
+            from: Some(record.occurred_at),
+            until: Some(record.occurred_at),
+            ..AuditQuery::default()
+        };
+        assert!(!range.matches(&record));
+    }
+}
@@ -0,0 +1,198 @@
+//! Display name value object
+
+use icu_normalizer::ComposingNormalizerBorrowed;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::domain::errors::DomainError;
+
+/// Maximum display name length, in user-perceived characters (graphemes)
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+/// Zero-width joiners, which some scripts and emoji sequences need
+const JOINERS: [char; 2] = ['\u{200C}', '\u{200D}'];
+
+/// A user's display name
+///
+/// NFC-normalized and trimmed, at most [`MAX_DISPLAY_NAME_LENGTH`]
+/// graphemes, and free of control, invisible and bidi formatting characters
+/// that could disguise one name as another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DisplayName(String);
+
+impl DisplayName {
+    /// Create a new validated display name
+    pub fn new(value: impl AsRef<str>) -> Result<Self, DomainError> {
+        let normalized = ComposingNormalizerBorrowed::new_nfc().normalize(value.as_ref());
+        let name = normalized.trim();
+
+        if name.is_empty() {
+            return Err(DomainError::validation("Display name cannot be empty"));
+        }
+
+        if name.graphemes(true).count() > MAX_DISPLAY_NAME_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Display name must be at most {} characters",
+                MAX_DISPLAY_NAME_LENGTH
+            )));
+        }
+
+        let chars: Vec<char> = name.chars().collect();
+        let hidden = chars.iter().enumerate().any(|(i, &c)| {
+            if JOINERS.contains(&c) {
+                // Only meaningful between two non-ASCII characters
+                let joins = |other: Option<&char>| other.is_some_and(|c| !c.is_ascii());
+                !(i > 0 && joins(chars.get(i - 1)) && joins(chars.get(i + 1)))
+            } else {
+                is_hidden(c)
+            }
+        });
+        if hidden {
+            return Err(DomainError::validation(
+                "Display name cannot contain control, invisible or bidi formatting characters",
+            ));
+        }
+
+        Ok(Self(name.to_string()))
+    }
+
+    /// Get the display name as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Whether `c` is a control, invisible or bidi formatting character
+fn is_hidden(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            // Bidi marks, embeddings, overrides and isolates
+            '\u{061C}'
+                | '\u{200E}'
+                | '\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2066}'..='\u{2069}'
+                // Invisible or blank characters
+                | '\u{00AD}'
+                | '\u{034F}'
+                | '\u{115F}'
+                | '\u{1160}'
+                | '\u{17B4}'
+                | '\u{17B5}'
+                | '\u{180B}'..='\u{180F}'
+                | '\u{200B}'
+                | '\u{2028}'
+                | '\u{2029}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{206A}'..='\u{206F}'
+                | '\u{3164}'
+                | '\u{FEFF}'
+                | '\u{FFA0}'
+                | '\u{FFF9}'..='\u{FFFB}'
+                | '\u{1D173}'..='\u{1D17A}'
+                | '\u{E0000}'..='\u{E007F}'
+        )
+}
+
+impl std::fmt::Display for DisplayName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for DisplayName {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for DisplayName {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<DisplayName> for String {
+    fn from(name: DisplayName) -> Self {
+        name.0
+    }
+}
+
+impl PartialEq<str> for DisplayName {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for DisplayName {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name_is_trimmed_and_normalized() {
+        let name = DisplayName::new("  Ada Lovelace \n").unwrap();
+        assert_eq!(name, "Ada Lovelace");
+
+        // "e" followed by a combining acute accent composes to "é"
+        let name = DisplayName::new("Rene\u{301}e").unwrap();
+        assert_eq!(name.as_str(), "Ren\u{E9}e");
+        assert_eq!(name, DisplayName::new("Ren\u{E9}e").unwrap());
+    }
+
+    #[test]
+    fn test_display_name_rejects_empty() {
+        assert!(DisplayName::new("").is_err());
+        assert!(DisplayName::new(" \t ").is_err());
+    }
+
+    #[test]
+    fn test_display_name_length_counts_graphemes() {
+        // Family emoji: several code points, one grapheme
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert!(DisplayName::new(family.repeat(MAX_DISPLAY_NAME_LENGTH)).is_ok());
+        assert!(DisplayName::new(family.repeat(MAX_DISPLAY_NAME_LENGTH + 1)).is_err());
+        assert!(DisplayName::new("a".repeat(MAX_DISPLAY_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_display_name_rejects_hidden_characters() {
+        for invalid in [
+            "Ada\u{0}Lovelace",
+            "Ada\nLovelace",
+            "Ada\u{200B}",
+            "ad\u{200D}min",
+            "\u{FEFF}Ada",
+            "Ada \u{202E}ecalevoL",
+            "Ada \u{2067}Lovelace\u{2069}",
+            "Ada\u{00AD}Lovelace",
+            "\u{3164}",
+        ] {
+            assert!(DisplayName::new(invalid).is_err(), "accepted {:?}", invalid);
+        }
+
+        // Joiners between non-ASCII characters are legitimate, e.g. Persian
+        assert!(
+            DisplayName::new("\u{645}\u{6CC}\u{200C}\u{62E}\u{648}\u{627}\u{647}\u{645}").is_ok()
+        );
+    }
+
+    #[test]
+    fn test_display_name_deserialization_is_validated() {
+        let name: DisplayName = serde_json::from_str("\" Ada \"").unwrap();
+        assert_eq!(name, "Ada");
+        assert_eq!(serde_json::to_string(&name).unwrap(), "\"Ada\"");
+        assert!(serde_json::from_str::<DisplayName>("\"\"").is_err());
+    }
+}
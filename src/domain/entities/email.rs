@@ -0,0 +1,436 @@
+//! Email address value object
+//!
+//! Addresses are validated following RFC 5322 (dot-atom or quoted local
+//! parts), RFC 6532 (UTF-8 local parts) and IDNA (internationalized
+//! domains), within the length limits of RFC 5321.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::DomainError;
+
+/// Maximum length of a whole address, in octets of its ASCII form
+pub const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Maximum length of the local part, in octets
+pub const MAX_EMAIL_LOCAL_PART_LENGTH: usize = 64;
+
+/// Characters allowed in a dot-atom besides letters and digits
+const ATEXT_SPECIALS: &str = "!#$%&'*+-/=?^_`{|}~";
+
+/// Rules deriving the canonical form of an address
+///
+/// The canonical form decides whether two addresses belong to the same
+/// mailbox. It always lowercases the local part and uses the ASCII form of
+/// the domain; the rules below fold further variants together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailCanonicalization {
+    /// Drop a `+tag` suffix from the local part
+    pub strip_plus_tags: bool,
+    /// Domains (in ASCII form) whose mailboxes ignore dots in the local part
+    pub dotless_domains: Vec<String>,
+}
+
+impl EmailCanonicalization {
+    /// Rules matching Gmail, which ignores both `+tags` and dots
+    pub fn gmail() -> Self {
+        Self {
+            strip_plus_tags: true,
+            dotless_domains: vec!["gmail.com".to_string(), "googlemail.com".to_string()],
+        }
+    }
+
+    /// Every combination of the rules, for addresses at `domain`
+    fn every(domain: &str) -> impl Iterator<Item = Self> + '_ {
+        [false, true].into_iter().flat_map(move |strip_plus_tags| {
+            [Vec::new(), vec![domain.to_string()]]
+                .into_iter()
+                .map(move |dotless_domains| Self {
+                    strip_plus_tags,
+                    dotless_domains,
+                })
+        })
+    }
+
+    /// Canonical local part of an unquoted `local` at `domain`
+    fn local_part(&self, local: &str, domain: &str) -> String {
+        let mut local = local;
+        if self.strip_plus_tags {
+            if let Some((mailbox, _tag)) = local.split_once('+') {
+                if !mailbox.is_empty() {
+                    local = mailbox;
+                }
+            }
+        }
+
+        let local = local.to_lowercase();
+        if self.dotless_domains.iter().any(|d| d == domain) {
+            local.replace('.', "")
+        } else {
+            local
+        }
+    }
+}
+
+/// Email value object with validation
+///
+/// Keeps the address as typed for display and a canonical form used for
+/// equality, so `Jane.Doe@Example.com` and `jane.doe@example.com` are the
+/// same address while the former is still shown as entered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "EmailRepr")]
+pub struct Email {
+    address: String,
+    canonical: String,
+}
+
+impl Email {
+    /// Create a new validated email, canonicalized by the default rules
+    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+        Self::with_rules(value, &EmailCanonicalization::default())
+    }
+
+    /// Create a new validated email, canonicalized by `rules`
+    pub fn with_rules(
+        value: impl Into<String>,
+        rules: &EmailCanonicalization,
+    ) -> Result<Self, DomainError> {
+        let address = value.into();
+
+        if address.is_empty() {
+            return Err(DomainError::validation("Email cannot be empty"));
+        }
+
+        if address.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(DomainError::validation(
+                "Email cannot contain whitespace or control characters",
+            ));
+        }
+
+        // Quoted local parts may themselves contain `@`
+        let Some((local, domain)) = address.rsplit_once('@') else {
+            return Err(DomainError::validation("Email must contain @"));
+        };
+
+        if local.is_empty() || domain.is_empty() {
+            return Err(DomainError::validation("Invalid email format"));
+        }
+
+        if local.len() > MAX_EMAIL_LOCAL_PART_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Email local part must be at most {} characters",
+                MAX_EMAIL_LOCAL_PART_LENGTH
+            )));
+        }
+
+        let quoted = is_quoted_string(local);
+        if !quoted && !is_dot_atom(local) {
+            return Err(DomainError::validation("Invalid email local part"));
+        }
+
+        let domain = ascii_domain(domain)?;
+        if local.len() + 1 + domain.len() > MAX_EMAIL_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Email must be at most {} characters",
+                MAX_EMAIL_LENGTH
+            )));
+        }
+
+        let canonical_local = if quoted {
+            local.to_lowercase()
+        } else {
+            rules.local_part(local, &domain)
+        };
+        let canonical = format!("{}@{}", canonical_local, domain);
+
+        Ok(Self { address, canonical })
+    }
+
+    /// Get the email as typed
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Local part as typed
+    pub fn local_part(&self) -> &str {
+        self.split().0
+    }
+
+    /// Domain as typed
+    pub fn domain(&self) -> &str {
+        self.split().1
+    }
+
+    /// Address with the domain in ASCII (punycode) form, for delivery
+    pub fn ascii(&self) -> String {
+        let (_, domain) = self.canonical_split();
+        format!("{}@{}", self.local_part(), domain)
+    }
+
+    /// Canonical form, identifying the mailbox
+    pub fn canonical(&self) -> &str {
+        &self.canonical
+    }
+
+    fn split(&self) -> (&str, &str) {
+        self.address.rsplit_once('@').unwrap_or((&self.address, ""))
+    }
+
+    fn canonical_split(&self) -> (&str, &str) {
+        self.canonical
+            .rsplit_once('@')
+            .unwrap_or((&self.canonical, ""))
+    }
+}
+
+impl PartialEq for Email {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical == other.canonical
+    }
+}
+
+impl Eq for Email {}
+
+impl std::hash::Hash for Email {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.canonical.hash(state);
+    }
+}
+
+impl std::fmt::Display for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.address)
+    }
+}
+
+impl std::str::FromStr for Email {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// Stored form of an [`Email`]
+///
+/// Records written before canonical forms existed hold a plain string,
+/// which is validated and canonicalized by the default rules. Newer records
+/// keep their canonical form, as it may come from other rules, but it must
+/// be the form the address takes under some combination of the rules.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EmailRepr {
+    Plain(String),
+    Stored { address: String, canonical: String },
+}
+
+impl TryFrom<EmailRepr> for Email {
+    type Error = DomainError;
+
+    fn try_from(repr: EmailRepr) -> Result<Self, Self::Error> {
+        match repr {
+            EmailRepr::Plain(address) => Self::new(address),
+            EmailRepr::Stored { address, canonical } => {
+                let email = Self::new(address)?;
+                let derivable =
+                    EmailCanonicalization::every(email.canonical_split().1).any(|rules| {
+                        Self::with_rules(email.address.as_str(), &rules)
+                            .is_ok_and(|derived| derived.canonical == canonical)
+                    });
+                if !derivable {
+                    return Err(DomainError::validation(format!(
+                        "Canonical email {} does not match {}",
+                        canonical, email.address
+                    )));
+                }
+                Ok(Self {
+                    address: email.address,
+                    canonical,
+                })
+            }
+        }
+    }
+}
+
+/// Whether `local` is a dot-atom: atoms of `atext` (or UTF-8) joined by dots
+fn is_dot_atom(local: &str) -> bool {
+    local.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ATEXT_SPECIALS.contains(c) || !c.is_ascii())
+    })
+}
+
+/// Whether `local` is a quoted string such as `"john..doe"`
+fn is_quoted_string(local: &str) -> bool {
+    let Some(inner) = local
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return false;
+    };
+
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_graphic() || !escaped.is_ascii() => {}
+                _ => return false,
+            },
+            '"' => return false,
+            c if c.is_ascii_graphic() || !c.is_ascii() => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// ASCII (punycode) form of a host name domain
+fn ascii_domain(domain: &str) -> Result<String, DomainError> {
+    if domain.starts_with('[') {
+        return Err(DomainError::validation(
+            "Email domain must be a host name, not an address literal",
+        ));
+    }
+
+    if domain.ends_with('.') {
+        return Err(DomainError::validation("Invalid email domain"));
+    }
+
+    let ascii = idna::domain_to_ascii_strict(domain)
+        .map_err(|_| DomainError::validation("Invalid email domain"))?;
+
+    if !ascii.contains('.') {
+        return Err(DomainError::validation("Email domain must contain a dot"));
+    }
+
+    Ok(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_validation_valid() {
+        let email = Email::new("test@example.com");
+        assert!(email.is_ok());
+        assert_eq!(email.unwrap().as_str(), "test@example.com");
+    }
+
+    #[test]
+    fn test_email_keeps_typed_form() {
+        let email = Email::new("Jane.Doe@Example.COM").unwrap();
+        assert_eq!(email.as_str(), "Jane.Doe@Example.COM");
+        assert_eq!(email.to_string(), "Jane.Doe@Example.COM");
+        assert_eq!(email.canonical(), "jane.doe@example.com");
+        assert_eq!(email, Email::new("jane.doe@example.com").unwrap());
+    }
+
+    #[test]
+    fn test_email_validation_rejects_malformed() {
+        for invalid in [
+            "",
+            "invalid-email",
+            "test@localhost",
+            "@example.com",
+            "test@",
+            "te st@example.com",
+            " test@example.com",
+            "test@example.com\n",
+            "te\u{7}st@example.com",
+            ".test@example.com",
+            "te..st@example.com",
+            "test.@example.com",
+            "te(st@example.com",
+            "test@exa_mple.com",
+            "test@-example.com",
+            "test@example.com.",
+            "test@[127.0.0.1]",
+            "\"unterminated@example.com",
+        ] {
+            assert!(Email::new(invalid).is_err(), "accepted {:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_email_validation_accepts_rfc_forms() {
+        for valid in [
+            "o'brien+news@example.com",
+            "\"john..doe\"@example.com",
+            "\"a@b\"@example.com",
+            "ünsal@example.com",
+            "user@sub.example.co.uk",
+        ] {
+            assert!(Email::new(valid).is_ok(), "rejected {:?}", valid);
+        }
+    }
+
+    #[test]
+    fn test_email_length_limits() {
+        let local = "a".repeat(MAX_EMAIL_LOCAL_PART_LENGTH);
+        assert!(Email::new(format!("{}@example.com", local)).is_ok());
+        assert!(Email::new(format!("{}a@example.com", local)).is_err());
+
+        let label = "b".repeat(60);
+        let domain = format!("{0}.{0}.{0}.{0}.com", label);
+        assert!(Email::new(format!("{}@{}", local, domain)).is_err());
+    }
+
+    #[test]
+    fn test_idn_domain_is_converted_to_punycode() {
+        let email = Email::new("user@Bücher.example").unwrap();
+        assert_eq!(email.as_str(), "user@Bücher.example");
+        assert_eq!(email.domain(), "Bücher.example");
+        assert_eq!(email.ascii(), "user@xn--bcher-kva.example");
+        assert_eq!(email.canonical(), "user@xn--bcher-kva.example");
+        assert_eq!(email, Email::new("user@xn--bcher-kva.example").unwrap());
+    }
+
+    #[test]
+    fn test_canonicalization_rules() {
+        let gmail = EmailCanonicalization::gmail();
+        let email = Email::with_rules("Jane.Doe+news@gmail.com", &gmail).unwrap();
+        assert_eq!(email.as_str(), "Jane.Doe+news@gmail.com");
+        assert_eq!(email.canonical(), "janedoe@gmail.com");
+
+        // Dots only matter to the listed domains
+        let other = Email::with_rules("Jane.Doe+news@example.com", &gmail).unwrap();
+        assert_eq!(other.canonical(), "jane.doe@example.com");
+
+        // The default rules keep tags and dots
+        let plain = Email::new("Jane.Doe+news@gmail.com").unwrap();
+        assert_eq!(plain.canonical(), "jane.doe+news@gmail.com");
+
+        // Quoted local parts are taken literally
+        let quoted = Email::with_rules("\"a+b\"@gmail.com", &gmail).unwrap();
+        assert_eq!(quoted.canonical(), "\"a+b\"@gmail.com");
+    }
+
+    #[test]
+    fn test_email_serde_round_trip() {
+        let email = Email::new("Jane@Example.com").unwrap();
+        let json = serde_json::to_string(&email).unwrap();
+        let parsed: Email = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_str(), "Jane@Example.com");
+        assert_eq!(parsed.canonical(), "jane@example.com");
+
+        // Plain strings from older records are still accepted
+        let legacy: Email = serde_json::from_str("\"jane@example.com\"").unwrap();
+        assert_eq!(legacy, email);
+        assert!(serde_json::from_str::<Email>("\"not an email\"").is_err());
+
+        // Stored forms are validated too
+        let mismatched = r#"{"address": "jane@example.com", "canonical": "jane@example.org"}"#;
+        assert!(serde_json::from_str::<Email>(mismatched).is_err());
+        let invalid = r#"{"address": "jane@@example.com", "canonical": "jane@example.com"}"#;
+        assert!(serde_json::from_str::<Email>(invalid).is_err());
+        let other = r#"{"address": "alice@gmail.com", "canonical": "bob@gmail.com"}"#;
+        assert!(serde_json::from_str::<Email>(other).is_err());
+
+        // Canonical forms from other rules are kept
+        let gmail = r#"{"address": "Jane.Doe+news@gmail.com", "canonical": "janedoe@gmail.com"}"#;
+        let parsed: Email = serde_json::from_str(gmail).unwrap();
+        assert_eq!(parsed.canonical(), "janedoe@gmail.com");
+    }
+}
@@ -0,0 +1,18 @@
+//! Test fixtures shared by the tests of every layer
+
+use chrono::Utc;
+
+use super::{DisplayName, Email, TenantId, User, UserId};
+
+impl User {
+    /// A user of `tenant` with `email`, named "Test User" and registered now
+    pub(crate) fn fixture(tenant: TenantId, email: &str) -> Self {
+        User::new(
+            UserId::new(),
+            tenant,
+            Email::new(email).unwrap(),
+            DisplayName::new("Test User").unwrap(),
+            Utc::now(),
+        )
+    }
+}
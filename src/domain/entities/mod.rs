@@ -6,15 +6,19 @@
 //! ## Example Entity
 //!
 //! ```rust,ignore
-//! use uuid::Uuid;
 //! use chrono::{DateTime, Utc};
 //!
 //! pub struct User {
-//!     pub id: Uuid,
+//!     pub id: UserId,
+//!     pub tenant_id: TenantId,
 //!     pub email: Email,
-//!     pub name: String,
+//!     pub name: DisplayName,
+//!     pub status: UserStatus,
 //!     pub created_at: DateTime<Utc>,
 //!     pub updated_at: DateTime<Utc>,
+//!     pub deleted_at: Option<DateTime<Utc>>,
+//!     pub version: u64,
+//!     // Roles, credentials and pending events omitted
 //! }
 //! ```
 //!
@@ -30,6 +34,26 @@
 //! }
 //! ```
 
+mod access;
+mod audit;
+mod display_name;
+mod email;
+#[cfg(test)]
+mod fixtures;
+mod outbox;
+mod password;
+mod password_reset;
+mod tenant;
 mod user;
+mod verification;
 
-pub use user::{Email, User, UserId};
+pub use access::{Permission, Principal, Role};
+pub use audit::{AuditActor, AuditQuery, AuditRecord, FieldChange};
+pub use display_name::{DisplayName, MAX_DISPLAY_NAME_LENGTH};
+pub use email::{Email, EmailCanonicalization, MAX_EMAIL_LENGTH, MAX_EMAIL_LOCAL_PART_LENGTH};
+pub use outbox::{OutboxMessage, OutboxStatus};
+pub use password::{validate_password, PasswordHash, MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
+pub use password_reset::PasswordResetToken;
+pub use tenant::{TenantId, MAX_TENANT_ID_LENGTH};
+pub use user::{User, UserId, UserStatus, UserStatusKind};
+pub use verification::VerificationToken;
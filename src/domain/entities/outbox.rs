@@ -0,0 +1,172 @@
+//! Outbox message entity
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::tenant::TenantId;
+use crate::domain::errors::DomainError;
+use crate::domain::events::DomainEvent;
+
+/// Delivery state of an outbox message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    /// Waiting for its first or next delivery attempt
+    Pending,
+    /// Handed to the publisher successfully
+    Delivered,
+    /// Gave up after too many failed attempts
+    Failed,
+}
+
+impl OutboxStatus {
+    /// Short machine-readable name of the status
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for OutboxStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "delivered" => Ok(Self::Delivered),
+            "failed" => Ok(Self::Failed),
+            other => Err(DomainError::validation(format!(
+                "Unknown outbox status: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A domain event awaiting delivery
+///
+/// Written in the same atomic operation as the state change that raised
+/// the event, then delivered by a relay, so events survive crashes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    /// Unique message identifier
+    pub id: Uuid,
+    /// Tenant the event belongs to
+    pub tenant_id: TenantId,
+    /// The event to deliver
+    pub event: DomainEvent,
+    /// Delivery state
+    pub status: OutboxStatus,
+    /// Failed delivery attempts so far
+    pub attempts: u32,
+    /// Error of the most recent failed attempt
+    pub last_error: Option<String>,
+    /// When the message was written
+    pub created_at: DateTime<Utc>,
+    /// Earliest time of the next delivery attempt
+    pub next_attempt_at: DateTime<Utc>,
+    /// When the message was delivered, if it was
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    /// Wrap an event as message `id`, due as soon as the event occurred
+    pub fn new(id: Uuid, event: DomainEvent) -> Self {
+        let occurred_at = event.occurred_at();
+        Self {
+            id,
+            tenant_id: event.tenant_id().clone(),
+            event,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            created_at: occurred_at,
+            next_attempt_at: occurred_at,
+            delivered_at: None,
+        }
+    }
+
+    /// Whether the relay should attempt delivery at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == OutboxStatus::Pending && self.next_attempt_at <= now
+    }
+
+    /// Record a successful delivery
+    pub fn mark_delivered(&mut self, now: DateTime<Utc>) {
+        self.status = OutboxStatus::Delivered;
+        self.delivered_at = Some(now);
+        self.last_error = None;
+    }
+
+    /// Record a failed delivery
+    ///
+    /// Schedules a retry after `backoff`, or marks the message failed once
+    /// `max_attempts` is reached.
+    pub fn record_failure(
+        &mut self,
+        error: impl Into<String>,
+        now: DateTime<Utc>,
+        backoff: Duration,
+        max_attempts: u32,
+    ) {
+        self.attempts += 1;
+        self.last_error = Some(error.into());
+        if self.attempts >= max_attempts {
+            self.status = OutboxStatus::Failed;
+        } else {
+            self.next_attempt_at = now + backoff;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::User;
+
+    fn message() -> OutboxMessage {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        OutboxMessage::new(Uuid::new_v4(), user.take_events().remove(0))
+    }
+
+    #[test]
+    fn test_retry_then_give_up() {
+        let mut message = message();
+        let now = Utc::now();
+        assert!(message.is_due(now));
+
+        message.record_failure("down", now, Duration::seconds(10), 2);
+        assert_eq!(message.status, OutboxStatus::Pending);
+        assert!(!message.is_due(now));
+        assert!(message.is_due(now + Duration::seconds(10)));
+
+        message.record_failure("still down", now, Duration::seconds(20), 2);
+        assert_eq!(message.status, OutboxStatus::Failed);
+        assert_eq!(message.last_error.as_deref(), Some("still down"));
+        assert!(!message.is_due(now + Duration::days(1)));
+    }
+
+    #[test]
+    fn test_mark_delivered() {
+        let mut message = message();
+        let now = Utc::now();
+
+        message.record_failure("down", now, Duration::zero(), 5);
+        message.mark_delivered(now);
+
+        assert_eq!(message.status, OutboxStatus::Delivered);
+        assert_eq!(message.delivered_at, Some(now));
+        assert!(message.last_error.is_none());
+        assert!(!message.is_due(now));
+    }
+}
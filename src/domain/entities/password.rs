@@ -0,0 +1,105 @@
+//! Password credential value objects
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::DomainError;
+
+/// Minimum accepted password length (in characters)
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Maximum accepted password length (in characters)
+///
+/// Bounds the work done by slow hash functions on attacker-supplied input.
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Check a plaintext password against the password policy
+pub fn validate_password(password: &str) -> Result<(), DomainError> {
+    let length = password.chars().count();
+
+    if length < MIN_PASSWORD_LENGTH {
+        return Err(DomainError::validation(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+
+    if length > MAX_PASSWORD_LENGTH {
+        return Err(DomainError::validation(format!(
+            "Password must be at most {} characters",
+            MAX_PASSWORD_LENGTH
+        )));
+    }
+
+    Ok(())
+}
+
+/// Hashed password, as produced by a `PasswordHasher`
+///
+/// The `Debug` output is redacted so the hash never ends up in logs.
+/// Stored hashes must not be empty.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PasswordHash(String);
+
+impl PasswordHash {
+    /// Wrap an encoded hash (e.g. a PHC string)
+    pub fn new(encoded: impl Into<String>) -> Self {
+        Self(encoded.into())
+    }
+
+    /// Get the encoded hash
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for PasswordHash {
+    type Error = DomainError;
+
+    fn try_from(encoded: String) -> Result<Self, Self::Error> {
+        if encoded.trim().is_empty() {
+            return Err(DomainError::validation("Password hash cannot be empty"));
+        }
+        Ok(Self::new(encoded))
+    }
+}
+
+impl From<PasswordHash> for String {
+    fn from(hash: PasswordHash) -> Self {
+        hash.0
+    }
+}
+
+impl std::fmt::Debug for PasswordHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PasswordHash(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_policy() {
+        assert!(validate_password("correct horse").is_ok());
+        assert!(validate_password("short").is_err());
+        assert!(validate_password(&"x".repeat(MAX_PASSWORD_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_password_hash_debug_is_redacted() {
+        let hash = PasswordHash::new("$argon2id$v=19$secret");
+        let debug = format!("{:?}", hash);
+
+        assert!(!debug.contains("secret"));
+        assert_eq!(debug, "PasswordHash(<redacted>)");
+    }
+
+    #[test]
+    fn test_password_hash_deserialization_rejects_empty() {
+        let hash: PasswordHash = serde_json::from_str("\"$argon2id$v=19$x\"").unwrap();
+        assert_eq!(hash.as_str(), "$argon2id$v=19$x");
+        assert!(serde_json::from_str::<PasswordHash>("\"\"").is_err());
+    }
+}
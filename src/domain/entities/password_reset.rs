@@ -0,0 +1,67 @@
+//! Password reset token entity
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::user::UserId;
+
+/// Single-use token authorizing a password change
+///
+/// The token handed to the user is never stored; only a keyed digest of
+/// it is, so a leaked token table cannot be used to take over accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordResetToken {
+    /// Keyed digest of the token sent to the user
+    pub token_hash: String,
+    /// User whose password may be reset
+    pub user_id: UserId,
+    /// When the token was issued
+    pub created_at: DateTime<Utc>,
+    /// When the token stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// When the token was redeemed, if it was
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl PasswordResetToken {
+    /// Record a new token digest issued at `now`, valid for `ttl`
+    pub fn new(
+        token_hash: impl Into<String>,
+        user_id: UserId,
+        now: DateTime<Utc>,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            token_hash: token_hash.into(),
+            user_id,
+            created_at: now,
+            expires_at: now + ttl,
+            used_at: None,
+        }
+    }
+
+    /// Whether the token is past its expiry at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether the token has already been redeemed
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_expiry() {
+        let token =
+            PasswordResetToken::new("digest", UserId::new(), Utc::now(), Duration::minutes(30));
+
+        assert!(!token.is_expired(token.created_at));
+        assert!(token.is_expired(token.expires_at));
+        assert!(!token.is_used());
+    }
+}
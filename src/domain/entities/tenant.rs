@@ -0,0 +1,117 @@
+//! Tenant value object
+//!
+//! Every user belongs to exactly one tenant (an organisation sharing the
+//! deployment); users of different tenants never see each other.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::DomainError;
+
+/// Maximum tenant ID length; keeps IDs usable as a DNS label
+pub const MAX_TENANT_ID_LENGTH: usize = 63;
+
+/// Tenant identifier
+///
+/// A lowercase slug such as `acme` or `acme-eu`: ASCII letters, digits and
+/// inner hyphens, so it can double as a subdomain.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Tenant used by single-tenant deployments and records predating tenancy
+    pub const DEFAULT: &'static str = "default";
+
+    /// Create a new validated tenant ID
+    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
+        let value = value.into().to_ascii_lowercase();
+
+        if value.is_empty() {
+            return Err(DomainError::validation("Tenant ID cannot be empty"));
+        }
+
+        if value.len() > MAX_TENANT_ID_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Tenant ID must be at most {} characters",
+                MAX_TENANT_ID_LENGTH
+            )));
+        }
+
+        let valid_chars = value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_chars || value.starts_with('-') || value.ends_with('-') {
+            return Err(DomainError::validation(
+                "Tenant ID may only contain letters, digits and inner hyphens",
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    /// Get the tenant ID as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl std::fmt::Display for TenantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for TenantId {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(tenant: TenantId) -> Self {
+        tenant.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_tenant_ids() {
+        assert_eq!(TenantId::new("acme").unwrap().as_str(), "acme");
+        assert_eq!(TenantId::new("Acme-EU").unwrap().as_str(), "acme-eu");
+        assert_eq!(TenantId::default().as_str(), TenantId::DEFAULT);
+    }
+
+    #[test]
+    fn test_invalid_tenant_ids() {
+        assert!(TenantId::new("").is_err());
+        assert!(TenantId::new("-acme").is_err());
+        assert!(TenantId::new("acme.com").is_err());
+        assert!(TenantId::new("a".repeat(MAX_TENANT_ID_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_tenant_id_deserialization_is_validated() {
+        let tenant: TenantId = serde_json::from_str("\"acme\"").unwrap();
+        assert_eq!(tenant.as_str(), "acme");
+        assert!(serde_json::from_str::<TenantId>("\"acme.com\"").is_err());
+    }
+}
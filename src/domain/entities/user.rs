@@ -3,11 +3,23 @@
 //! This is an example entity to demonstrate the pattern.
 //! Replace or extend with your own domain entities.
 
+use std::collections::BTreeSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::access::{default_roles, Role};
+use super::display_name::DisplayName;
+use super::email::Email;
+use super::password::PasswordHash;
+use super::tenant::TenantId;
 use crate::domain::errors::DomainError;
+use crate::domain::events::{
+    DomainEvent, UserDeleted, UserEmailChanged, UserEmailVerified, UserPasswordChanged, UserPurged,
+    UserRegistered, UserRenamed, UserRestored, UserRoleAssigned, UserRoleRevoked,
+    UserStatusChanged,
+};
 
 /// Strongly-typed user identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -37,86 +49,538 @@ impl std::fmt::Display for UserId {
     }
 }
 
-/// Email value object with validation
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Email(String);
-
-impl Email {
-    /// Create a new validated email
-    pub fn new(value: impl Into<String>) -> Result<Self, DomainError> {
-        let value = value.into();
+/// Lifecycle status of a user account
+///
+/// ```text
+/// Pending ──activate──► Active ◄──reactivate── Suspended
+///    │                    │ └──────suspend──────►  │
+///    └──────────┬─────────┴───────────┬────────────┘
+///           deactivate            deactivate
+///               ▼                     ▼
+///                     Deactivated
+/// ```
+///
+/// `Deactivated` is terminal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case", try_from = "UserStatusRepr")]
+pub enum UserStatus {
+    /// Registered but not yet activated
+    #[default]
+    Pending,
+    /// Fully usable account
+    Active,
+    /// Temporarily blocked by an operator
+    Suspended { reason: String },
+    /// Permanently closed
+    Deactivated,
+}
 
-        // Basic email validation
-        if value.is_empty() {
-            return Err(DomainError::validation("Email cannot be empty"));
+impl UserStatus {
+    /// Suspended status with a validated reason
+    pub fn suspended(reason: impl Into<String>) -> Result<Self, DomainError> {
+        let reason = reason.into();
+        if reason.trim().is_empty() {
+            return Err(DomainError::validation("Suspension reason cannot be empty"));
         }
+        Ok(Self::Suspended { reason })
+    }
 
-        if !value.contains('@') {
-            return Err(DomainError::validation("Email must contain @"));
+    /// Short machine-readable name of the status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Suspended { .. } => "suspended",
+            Self::Deactivated => "deactivated",
         }
+    }
 
-        let parts: Vec<&str> = value.split('@').collect();
-        if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
-            return Err(DomainError::validation("Invalid email format"));
+    /// The status without its details, e.g. for filtering
+    pub fn kind(&self) -> UserStatusKind {
+        match self {
+            Self::Pending => UserStatusKind::Pending,
+            Self::Active => UserStatusKind::Active,
+            Self::Suspended { .. } => UserStatusKind::Suspended,
+            Self::Deactivated => UserStatusKind::Deactivated,
         }
+    }
+}
 
-        if !parts[1].contains('.') {
-            return Err(DomainError::validation("Email domain must contain a dot"));
+impl std::fmt::Display for UserStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A [`UserStatus`] without its details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatusKind {
+    Pending,
+    Active,
+    Suspended,
+    Deactivated,
+}
+
+impl UserStatusKind {
+    /// Short machine-readable name, as in [`UserStatus::as_str`]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Suspended => "suspended",
+            Self::Deactivated => "deactivated",
         }
+    }
+}
 
-        Ok(Self(value.to_lowercase()))
+impl std::fmt::Display for UserStatusKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
+}
 
-    /// Get the email as a string slice
-    pub fn as_str(&self) -> &str {
-        &self.0
+impl std::str::FromStr for UserStatusKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "active" => Ok(Self::Active),
+            "suspended" => Ok(Self::Suspended),
+            "deactivated" => Ok(Self::Deactivated),
+            other => Err(DomainError::validation(format!(
+                "Unknown user status: {}",
+                other
+            ))),
+        }
     }
 }
 
-impl std::fmt::Display for Email {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+/// Stored form of a [`UserStatus`], before its reason is checked
+#[derive(Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum UserStatusRepr {
+    Pending,
+    Active,
+    Suspended { reason: String },
+    Deactivated,
+}
+
+impl TryFrom<UserStatusRepr> for UserStatus {
+    type Error = DomainError;
+
+    fn try_from(repr: UserStatusRepr) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            UserStatusRepr::Pending => Self::Pending,
+            UserStatusRepr::Active => Self::Active,
+            UserStatusRepr::Suspended { reason } => Self::suspended(reason)?,
+            UserStatusRepr::Deactivated => Self::Deactivated,
+        })
     }
 }
 
 /// User entity
+///
+/// The user never reads the system clock: creating or changing it takes
+/// the current time from the caller, typically a `Clock`, so its events
+/// are reproducible.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     /// Unique identifier
     pub id: UserId,
-    /// User's email address
+    /// Tenant the user belongs to
+    #[serde(default)]
+    pub tenant_id: TenantId,
+    /// User's email address; unique within the tenant
     pub email: Email,
     /// User's display name
-    pub name: String,
+    pub name: DisplayName,
+    /// Lifecycle status
+    #[serde(default)]
+    pub status: UserStatus,
+    /// Assigned roles
+    #[serde(default = "default_roles")]
+    pub roles: BTreeSet<Role>,
+    /// When the current email address was verified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_verified_at: Option<DateTime<Utc>>,
+    /// Password credential, if the user has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<PasswordHash>,
     /// When the user was created
     pub created_at: DateTime<Utc>,
     /// When the user was last updated
     pub updated_at: DateTime<Utc>,
+    /// When the user was deleted; deleted users can be restored until purged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Number of changes ever made to the user, counting registration
+    ///
+    /// Repositories use it to reject saves based on stale data.
+    #[serde(default)]
+    pub version: u64,
+    /// Events recorded since the user was loaded, awaiting publication
+    #[serde(skip)]
+    events: Vec<DomainEvent>,
 }
 
 impl User {
-    /// Create a new user
-    pub fn new(email: Email, name: impl Into<String>) -> Self {
-        let now = Utc::now();
-        Self {
-            id: UserId::new(),
+    /// Create a new user `id` in `tenant_id`, registered at `now`
+    ///
+    /// Records [`UserRegistered`].
+    pub fn new(
+        id: UserId,
+        tenant_id: TenantId,
+        email: Email,
+        name: DisplayName,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let registered = UserRegistered {
+            user_id: id,
+            tenant_id,
             email,
-            name: name.into(),
-            created_at: now,
-            updated_at: now,
+            name,
+            occurred_at: now,
+        };
+        let mut user = Self::registered(&registered);
+        user.events.push(registered.into());
+        user
+    }
+
+    /// Rebuild a user by replaying its events, oldest first
+    ///
+    /// The first event must be the user's [`UserRegistered`].
+    pub fn from_history(events: &[DomainEvent]) -> Result<Self, DomainError> {
+        let Some((DomainEvent::UserRegistered(registered), rest)) = events.split_first() else {
+            return Err(DomainError::validation(
+                "User history must start with UserRegistered",
+            ));
+        };
+
+        let mut user = Self::registered(registered);
+        user.apply_history(rest)?;
+        Ok(user)
+    }
+
+    /// Replay further events onto the user, e.g. ones newer than a snapshot
+    ///
+    /// Replayed events are not recorded as pending.
+    pub fn apply_history(&mut self, events: &[DomainEvent]) -> Result<(), DomainError> {
+        for event in events {
+            if event.user_id() != self.id {
+                return Err(DomainError::validation(format!(
+                    "{} of user {} cannot be applied to user {}",
+                    event.name(),
+                    event.user_id(),
+                    self.id
+                )));
+            }
+            if let DomainEvent::UserRegistered(_) = event {
+                return Err(DomainError::validation(format!(
+                    "User {} is already registered",
+                    self.id
+                )));
+            }
+            self.apply(event);
         }
+        Ok(())
+    }
+
+    fn registered(event: &UserRegistered) -> Self {
+        Self {
+            id: event.user_id,
+            tenant_id: event.tenant_id.clone(),
+            email: event.email.clone(),
+            name: event.name.clone(),
+            status: UserStatus::Pending,
+            roles: default_roles(),
+            email_verified_at: None,
+            password_hash: None,
+            created_at: event.occurred_at,
+            updated_at: event.occurred_at,
+            deleted_at: None,
+            version: 1,
+            events: Vec::new(),
+        }
+    }
+
+    /// Version the user had when it was loaded, before its pending changes
+    ///
+    /// A repository accepts a save only while it still stores this version.
+    pub fn expected_version(&self) -> u64 {
+        self.version.saturating_sub(self.events.len() as u64)
     }
 
     /// Update the user's name
-    pub fn update_name(&mut self, name: impl Into<String>) {
-        self.name = name.into();
-        self.updated_at = Utc::now();
+    ///
+    /// Records [`UserRenamed`] if the name actually changes.
+    pub fn update_name(&mut self, name: DisplayName, now: DateTime<Utc>) {
+        if name == self.name {
+            return;
+        }
+
+        self.raise(UserRenamed {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            old_name: self.name.clone(),
+            new_name: name,
+            occurred_at: now,
+        });
     }
 
     /// Update the user's email
-    pub fn update_email(&mut self, email: Email) {
-        self.email = email;
-        self.updated_at = Utc::now();
+    ///
+    /// A new address starts out unverified. Records [`UserEmailChanged`] if
+    /// the address actually changes.
+    pub fn update_email(&mut self, email: Email, now: DateTime<Utc>) {
+        if email == self.email {
+            return;
+        }
+
+        self.raise(UserEmailChanged {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            old_email: self.email.clone(),
+            new_email: email,
+            occurred_at: now,
+        });
+    }
+
+    /// Whether the user was deleted and not restored since
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Delete the user, keeping it restorable
+    ///
+    /// Records [`UserDeleted`].
+    pub fn delete(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if self.is_deleted() {
+            return Err(DomainError::business_rule("User is already deleted"));
+        }
+        self.raise(UserDeleted {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            email: self.email.clone(),
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    /// Undo a deletion
+    ///
+    /// Records [`UserRestored`].
+    pub fn restore(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if !self.is_deleted() {
+            return Err(DomainError::business_rule("User is not deleted"));
+        }
+        self.raise(UserRestored {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    /// Mark a deleted user for permanent removal
+    ///
+    /// Records [`UserPurged`]; removing the user is up to the repository.
+    pub fn purge(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if !self.is_deleted() {
+            return Err(DomainError::business_rule(
+                "Only deleted users can be purged",
+            ));
+        }
+        self.raise(UserPurged {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    /// Events recorded since the user was created or loaded
+    pub fn pending_events(&self) -> &[DomainEvent] {
+        &self.events
+    }
+
+    /// Copy of the user without its pending events, as repositories keep it
+    pub fn without_events(&self) -> Self {
+        Self {
+            events: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Remove and return the recorded events, oldest first
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Apply a new event and record it as pending
+    fn raise(&mut self, event: impl Into<DomainEvent>) {
+        let event = event.into();
+        self.apply(&event);
+        self.events.push(event);
+    }
+
+    /// Change state according to `event`; the only place state changes
+    fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            // Not a change to the user
+            DomainEvent::PasswordResetRequested(_) => return,
+            DomainEvent::UserRegistered(_) | DomainEvent::UserPurged(_) => {}
+            DomainEvent::UserRenamed(e) => self.name = e.new_name.clone(),
+            DomainEvent::UserEmailChanged(e) => {
+                self.email = e.new_email.clone();
+                self.email_verified_at = None;
+            }
+            DomainEvent::UserEmailVerified(e) => {
+                self.email_verified_at = Some(e.occurred_at);
+                if self.status == UserStatus::Pending {
+                    self.status = UserStatus::Active;
+                }
+            }
+            DomainEvent::UserPasswordChanged(e) => {
+                self.password_hash = Some(e.password_hash.clone());
+            }
+            DomainEvent::UserStatusChanged(e) => self.status = e.new_status.clone(),
+            DomainEvent::UserRoleAssigned(e) => {
+                self.roles.insert(e.role);
+            }
+            DomainEvent::UserRoleRevoked(e) => {
+                self.roles.remove(&e.role);
+            }
+            DomainEvent::UserDeleted(e) => self.deleted_at = Some(e.occurred_at),
+            DomainEvent::UserRestored(_) => self.deleted_at = None,
+        }
+        self.version += 1;
+        self.updated_at = event.occurred_at();
+    }
+
+    /// Whether the current email address has been verified
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
+    /// Mark the current email address as verified
+    ///
+    /// Verifying the address also activates a pending account.
+    pub fn verify_email(&mut self, now: DateTime<Utc>) {
+        self.raise(UserEmailVerified {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            email: self.email.clone(),
+            occurred_at: now,
+        });
+    }
+
+    /// Set or replace the user's password credential
+    pub fn set_password_hash(&mut self, hash: PasswordHash, now: DateTime<Utc>) {
+        self.raise(UserPasswordChanged {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            password_hash: hash,
+            occurred_at: now,
+        });
+    }
+
+    /// Whether the user holds `role`
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+
+    /// Grant a role
+    pub fn assign_role(&mut self, role: Role, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if self.has_role(role) {
+            return Err(DomainError::business_rule(format!(
+                "User already has the {} role",
+                role
+            )));
+        }
+        self.raise(UserRoleAssigned {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            role,
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    /// Take away a role
+    pub fn revoke_role(&mut self, role: Role, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if !self.has_role(role) {
+            return Err(DomainError::business_rule(format!(
+                "User does not have the {} role",
+                role
+            )));
+        }
+        self.raise(UserRoleRevoked {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            role,
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    /// Whether the account may sign in
+    pub fn can_authenticate(&self) -> bool {
+        matches!(self.status, UserStatus::Pending | UserStatus::Active)
+    }
+
+    /// Activate a pending account
+    pub fn activate(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        match self.status {
+            UserStatus::Pending => self.transition(UserStatus::Active, now),
+            _ => Err(self.invalid_transition("activate")),
+        }
+    }
+
+    /// Suspend an active account
+    pub fn suspend(
+        &mut self,
+        reason: impl Into<String>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let suspended = UserStatus::suspended(reason)?;
+
+        match self.status {
+            UserStatus::Active => self.transition(suspended, now),
+            _ => Err(self.invalid_transition("suspend")),
+        }
+    }
+
+    /// Lift a suspension
+    pub fn reactivate(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        match self.status {
+            UserStatus::Suspended { .. } => self.transition(UserStatus::Active, now),
+            _ => Err(self.invalid_transition("reactivate")),
+        }
+    }
+
+    /// Permanently close the account
+    pub fn deactivate(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        match self.status {
+            UserStatus::Deactivated => Err(self.invalid_transition("deactivate")),
+            _ => self.transition(UserStatus::Deactivated, now),
+        }
+    }
+
+    fn transition(&mut self, status: UserStatus, now: DateTime<Utc>) -> Result<(), DomainError> {
+        self.raise(UserStatusChanged {
+            user_id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            old_status: self.status.clone(),
+            new_status: status,
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    fn invalid_transition(&self, action: &str) -> DomainError {
+        DomainError::business_rule(format!("Cannot {} a {} user", action, self.status))
     }
 }
 
@@ -125,57 +589,263 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_email_validation_valid() {
-        let email = Email::new("test@example.com");
-        assert!(email.is_ok());
-        assert_eq!(email.unwrap().as_str(), "test@example.com");
+    fn test_user_creation() {
+        let email = Email::new("test@example.com").unwrap();
+        let user = User::new(
+            UserId::new(),
+            TenantId::default(),
+            email,
+            DisplayName::new("Test User").unwrap(),
+            Utc::now(),
+        );
+
+        assert_eq!(user.name, "Test User");
+        assert_eq!(user.email.as_str(), "test@example.com");
+        assert_eq!(user.status, UserStatus::Pending);
     }
 
     #[test]
-    fn test_email_validation_uppercase_normalized() {
-        let email = Email::new("Test@Example.COM").unwrap();
-        assert_eq!(email.as_str(), "test@example.com");
+    fn test_user_update_name() {
+        let created = Utc::now();
+        let renamed = created + chrono::Duration::minutes(5);
+        let email = Email::new("test@example.com").unwrap();
+        let mut user = User::new(
+            UserId::new(),
+            TenantId::default(),
+            email,
+            DisplayName::new("Old Name").unwrap(),
+            created,
+        );
+
+        user.update_name(DisplayName::new("New Name").unwrap(), renamed);
+
+        assert_eq!(user.name, "New Name");
+        assert_eq!(user.created_at, created);
+        assert_eq!(user.updated_at, renamed);
     }
 
     #[test]
-    fn test_email_validation_empty() {
-        let email = Email::new("");
-        assert!(email.is_err());
+    fn test_user_lifecycle() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+
+        user.activate(Utc::now()).unwrap();
+        assert_eq!(user.status, UserStatus::Active);
+
+        user.suspend("Spam", Utc::now()).unwrap();
+        assert_eq!(
+            user.status,
+            UserStatus::Suspended {
+                reason: "Spam".to_string()
+            }
+        );
+
+        user.reactivate(Utc::now()).unwrap();
+        assert_eq!(user.status, UserStatus::Active);
+
+        user.deactivate(Utc::now()).unwrap();
+        assert_eq!(user.status, UserStatus::Deactivated);
     }
 
     #[test]
-    fn test_email_validation_no_at() {
-        let email = Email::new("invalid-email");
-        assert!(email.is_err());
+    fn test_user_invalid_transitions() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+
+        assert!(matches!(
+            user.suspend("Spam", Utc::now()),
+            Err(DomainError::BusinessRuleViolation(_))
+        ));
+        assert!(matches!(
+            user.reactivate(Utc::now()),
+            Err(DomainError::BusinessRuleViolation(_))
+        ));
+
+        user.deactivate(Utc::now()).unwrap();
+        assert!(matches!(
+            user.activate(Utc::now()),
+            Err(DomainError::BusinessRuleViolation(_))
+        ));
+        assert!(matches!(
+            user.deactivate(Utc::now()),
+            Err(DomainError::BusinessRuleViolation(_))
+        ));
     }
 
     #[test]
-    fn test_email_validation_no_domain_dot() {
-        let email = Email::new("test@localhost");
-        assert!(email.is_err());
+    fn test_user_suspend_requires_reason() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.activate(Utc::now()).unwrap();
+
+        assert!(matches!(
+            user.suspend("  ", Utc::now()),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert_eq!(user.status, UserStatus::Active);
     }
 
     #[test]
-    fn test_user_creation() {
-        let email = Email::new("test@example.com").unwrap();
-        let user = User::new(email, "Test User");
+    fn test_status_deserialization_is_validated() {
+        let status: UserStatus =
+            serde_json::from_str(r#"{"state": "suspended", "reason": "Abuse"}"#).unwrap();
+        assert_eq!(status, UserStatus::suspended("Abuse").unwrap());
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!({"state": "suspended", "reason": "Abuse"})
+        );
+        assert!(
+            serde_json::from_str::<UserStatus>(r#"{"state": "suspended", "reason": ""}"#).is_err()
+        );
+    }
 
-        assert_eq!(user.name, "Test User");
-        assert_eq!(user.email.as_str(), "test@example.com");
+    #[test]
+    fn test_user_status_defaults_when_missing() {
+        let user = User::fixture(TenantId::default(), "test@example.com");
+        let mut json = serde_json::to_value(&user).unwrap();
+        json.as_object_mut().unwrap().remove("status");
+
+        let user: User = serde_json::from_value(json).unwrap();
+        assert_eq!(user.status, UserStatus::Pending);
     }
 
     #[test]
-    fn test_user_update_name() {
-        let email = Email::new("test@example.com").unwrap();
-        let mut user = User::new(email, "Old Name");
-        let original_updated = user.updated_at;
+    fn test_user_debug_hides_password_hash() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.set_password_hash(PasswordHash::new("$argon2id$secret"), Utc::now());
 
-        // Small delay to ensure timestamp changes
-        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(!format!("{:?}", user).contains("secret"));
+    }
 
-        user.update_name("New Name");
+    #[test]
+    fn test_verify_email_activates_pending_user() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        assert!(!user.is_email_verified());
 
-        assert_eq!(user.name, "New Name");
-        assert!(user.updated_at > original_updated);
+        user.verify_email(Utc::now());
+
+        assert!(user.is_email_verified());
+        assert_eq!(user.status, UserStatus::Active);
+    }
+
+    #[test]
+    fn test_update_email_resets_verification() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.verify_email(Utc::now());
+
+        user.update_email(Email::new("new@example.com").unwrap(), Utc::now());
+
+        assert!(!user.is_email_verified());
+    }
+
+    #[test]
+    fn test_role_assignment() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        assert!(user.has_role(Role::Member));
+
+        user.assign_role(Role::Admin, Utc::now()).unwrap();
+        assert!(user.has_role(Role::Admin));
+        assert!(user.assign_role(Role::Admin, Utc::now()).is_err());
+
+        user.revoke_role(Role::Admin, Utc::now()).unwrap();
+        assert!(!user.has_role(Role::Admin));
+        assert!(user.revoke_role(Role::Admin, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_changes_record_events() {
+        let mut user = User::fixture(TenantId::default(), "old@example.com");
+        user.update_name(DisplayName::new("New").unwrap(), Utc::now());
+        user.update_email(Email::new("new@example.com").unwrap(), Utc::now());
+        user.delete(Utc::now()).unwrap();
+
+        let names: Vec<_> = user.take_events().iter().map(|e| e.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "UserRegistered",
+                "UserRenamed",
+                "UserEmailChanged",
+                "UserDeleted"
+            ]
+        );
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_events_are_not_serialized() {
+        let user = User::fixture(TenantId::default(), "test@example.com");
+        let json = serde_json::to_string(&user).unwrap();
+        let loaded: User = serde_json::from_str(&json).unwrap();
+
+        assert!(!json.contains("UserRegistered"));
+        assert!(loaded.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_replaying_history_rebuilds_user() {
+        let mut user = User::fixture(TenantId::default(), "old@example.com");
+        user.set_password_hash(PasswordHash::new("hash"), Utc::now());
+        user.update_email(Email::new("new@example.com").unwrap(), Utc::now());
+        user.verify_email(Utc::now());
+        user.suspend("Spam", Utc::now()).unwrap();
+        user.assign_role(Role::Support, Utc::now()).unwrap();
+        user.update_name(DisplayName::new("New").unwrap(), Utc::now());
+        let history = user.take_events();
+
+        let rebuilt = User::from_history(&history).unwrap();
+        assert_eq!(
+            serde_json::to_value(&rebuilt).unwrap(),
+            serde_json::to_value(&user).unwrap()
+        );
+        assert!(rebuilt.pending_events().is_empty());
+
+        assert!(User::from_history(&history[1..]).is_err());
+        let mut other = User::fixture(TenantId::default(), user.email.as_str());
+        assert!(other.apply_history(&history[1..]).is_err());
+    }
+
+    #[test]
+    fn test_version_counts_changes() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        assert_eq!((user.version, user.expected_version()), (1, 0));
+
+        user.take_events();
+        user.update_name(DisplayName::new("Renamed").unwrap(), Utc::now());
+        user.update_name(DisplayName::new("Renamed").unwrap(), Utc::now());
+        assert_eq!((user.version, user.expected_version()), (2, 1));
+
+        let history =
+            [User::fixture(TenantId::default(), user.email.as_str()).take_events()].concat();
+        assert_eq!(User::from_history(&history).unwrap().version, 1);
+    }
+
+    #[test]
+    fn test_soft_delete_and_restore() {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        assert!(user.restore(Utc::now()).is_err());
+        assert!(user.purge(Utc::now()).is_err());
+
+        user.delete(Utc::now()).unwrap();
+        assert!(user.is_deleted());
+        assert!(user.delete(Utc::now()).is_err());
+
+        user.restore(Utc::now()).unwrap();
+        assert!(!user.is_deleted());
+
+        user.delete(Utc::now()).unwrap();
+        let version = user.version;
+        let purged_at = Utc::now() + chrono::Duration::days(30);
+        user.purge(purged_at).unwrap();
+        assert_eq!(user.version, version + 1);
+        assert_eq!(user.updated_at, purged_at);
+        let names: Vec<_> = user.take_events().iter().map(|e| e.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "UserRegistered",
+                "UserDeleted",
+                "UserRestored",
+                "UserDeleted",
+                "UserPurged"
+            ]
+        );
     }
 }
@@ -0,0 +1,74 @@
+//! Email verification token entity
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::email::Email;
+use super::user::UserId;
+
+/// Single-use token proving control of an email address
+///
+/// Only the random `id` is stored; the link sent to the user carries the
+/// id together with a signature produced by a `TokenSigner`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationToken {
+    /// Random token identifier
+    pub id: Uuid,
+    /// User the token was issued for
+    pub user_id: UserId,
+    /// Address being verified
+    pub email: Email,
+    /// When the token was issued
+    pub created_at: DateTime<Utc>,
+    /// When the token stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// When the token was redeemed, if it was
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl VerificationToken {
+    /// Issue token `id` at `now`, valid for `ttl`
+    ///
+    /// `id` must be unpredictable, e.g. a random UUID.
+    pub fn new(id: Uuid, user_id: UserId, email: Email, now: DateTime<Utc>, ttl: Duration) -> Self {
+        Self {
+            id,
+            user_id,
+            email,
+            created_at: now,
+            expires_at: now + ttl,
+            used_at: None,
+        }
+    }
+
+    /// Whether the token is past its expiry at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether the token has already been redeemed
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_expiry() {
+        let token = VerificationToken::new(
+            Uuid::new_v4(),
+            UserId::new(),
+            Email::new("test@example.com").unwrap(),
+            Utc::now(),
+            Duration::hours(1),
+        );
+
+        assert!(!token.is_expired(token.created_at));
+        assert!(token.is_expired(token.created_at + Duration::hours(1)));
+        assert!(!token.is_used());
+    }
+}
@@ -25,6 +25,18 @@ pub enum DomainError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// Authentication failed (e.g., wrong email or password)
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The acting principal lacks a required permission
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// The caller acted on an outdated version of an entity
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
     /// Infrastructure error (wrapped from adapters)
     #[error("Infrastructure error: {0}")]
     Infrastructure(#[from] anyhow::Error),
@@ -53,4 +65,19 @@ impl DomainError {
     pub fn conflict(message: impl Into<String>) -> Self {
         Self::Conflict(message.into())
     }
+
+    /// Create an unauthorized error
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized(message.into())
+    }
+
+    /// Create a forbidden error
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden(message.into())
+    }
+
+    /// Create a precondition failed error
+    pub fn precondition_failed(message: impl Into<String>) -> Self {
+        Self::PreconditionFailed(message.into())
+    }
 }
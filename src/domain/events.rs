@@ -0,0 +1,316 @@
+//! Domain events
+//!
+//! Facts about state changes, recorded by aggregates and published by the
+//! domain services after the change has been persisted.
+//!
+//! Every change to a `User` raises an event, so a user can be rebuilt by
+//! replaying its events (see `User::from_history`). A few events, such as
+//! [`PasswordResetRequested`], only trigger side effects and are not part
+//! of a user's history.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    DisplayName, Email, PasswordHash, Role, TenantId, UserId, UserStatus,
+};
+
+/// A user registered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserRegistered {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub email: Email,
+    pub name: DisplayName,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user changed their display name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserRenamed {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub old_name: DisplayName,
+    pub new_name: DisplayName,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user changed their email address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserEmailChanged {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub old_email: Email,
+    pub new_email: Email,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user confirmed their email address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserEmailVerified {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub email: Email,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user's password was set or replaced
+///
+/// Carries the new hash so the credential can be rebuilt from events;
+/// the hash's `Debug` output stays redacted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserPasswordChanged {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub password_hash: PasswordHash,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user's lifecycle status changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserStatusChanged {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub old_status: UserStatus,
+    pub new_status: UserStatus,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user was granted a role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserRoleAssigned {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub role: Role,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user lost a role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserRoleRevoked {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub role: Role,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user was deleted
+///
+/// Deletion is soft: the user can be restored until it is purged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserDeleted {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub email: Email,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A deleted user was restored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserRestored {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A deleted user was removed for good
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserPurged {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user asked for a password reset link
+///
+/// Carries the ID the emailed token is derived from rather than the token,
+/// so a stored event does not hold a working link.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasswordResetRequested {
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub email: Email,
+    pub token_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Any event raised by the domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum DomainEvent {
+    UserRegistered(UserRegistered),
+    UserRenamed(UserRenamed),
+    UserEmailChanged(UserEmailChanged),
+    UserEmailVerified(UserEmailVerified),
+    UserPasswordChanged(UserPasswordChanged),
+    UserStatusChanged(UserStatusChanged),
+    UserRoleAssigned(UserRoleAssigned),
+    UserRoleRevoked(UserRoleRevoked),
+    UserDeleted(UserDeleted),
+    UserRestored(UserRestored),
+    UserPurged(UserPurged),
+    PasswordResetRequested(PasswordResetRequested),
+}
+
+impl DomainEvent {
+    /// Name of the event type, as used in the serialized form
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UserRegistered(_) => "UserRegistered",
+            Self::UserRenamed(_) => "UserRenamed",
+            Self::UserEmailChanged(_) => "UserEmailChanged",
+            Self::UserEmailVerified(_) => "UserEmailVerified",
+            Self::UserPasswordChanged(_) => "UserPasswordChanged",
+            Self::UserStatusChanged(_) => "UserStatusChanged",
+            Self::UserRoleAssigned(_) => "UserRoleAssigned",
+            Self::UserRoleRevoked(_) => "UserRoleRevoked",
+            Self::UserDeleted(_) => "UserDeleted",
+            Self::UserRestored(_) => "UserRestored",
+            Self::UserPurged(_) => "UserPurged",
+            Self::PasswordResetRequested(_) => "PasswordResetRequested",
+        }
+    }
+
+    /// User the event is about
+    pub fn user_id(&self) -> UserId {
+        match self {
+            Self::UserRegistered(e) => e.user_id,
+            Self::UserRenamed(e) => e.user_id,
+            Self::UserEmailChanged(e) => e.user_id,
+            Self::UserEmailVerified(e) => e.user_id,
+            Self::UserPasswordChanged(e) => e.user_id,
+            Self::UserStatusChanged(e) => e.user_id,
+            Self::UserRoleAssigned(e) => e.user_id,
+            Self::UserRoleRevoked(e) => e.user_id,
+            Self::UserDeleted(e) => e.user_id,
+            Self::UserRestored(e) => e.user_id,
+            Self::UserPurged(e) => e.user_id,
+            Self::PasswordResetRequested(e) => e.user_id,
+        }
+    }
+
+    /// Tenant of the user the event is about
+    pub fn tenant_id(&self) -> &TenantId {
+        match self {
+            Self::UserRegistered(e) => &e.tenant_id,
+            Self::UserRenamed(e) => &e.tenant_id,
+            Self::UserEmailChanged(e) => &e.tenant_id,
+            Self::UserEmailVerified(e) => &e.tenant_id,
+            Self::UserPasswordChanged(e) => &e.tenant_id,
+            Self::UserStatusChanged(e) => &e.tenant_id,
+            Self::UserRoleAssigned(e) => &e.tenant_id,
+            Self::UserRoleRevoked(e) => &e.tenant_id,
+            Self::UserDeleted(e) => &e.tenant_id,
+            Self::UserRestored(e) => &e.tenant_id,
+            Self::UserPurged(e) => &e.tenant_id,
+            Self::PasswordResetRequested(e) => &e.tenant_id,
+        }
+    }
+
+    /// When the change happened
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            Self::UserRegistered(e) => e.occurred_at,
+            Self::UserRenamed(e) => e.occurred_at,
+            Self::UserEmailChanged(e) => e.occurred_at,
+            Self::UserEmailVerified(e) => e.occurred_at,
+            Self::UserPasswordChanged(e) => e.occurred_at,
+            Self::UserStatusChanged(e) => e.occurred_at,
+            Self::UserRoleAssigned(e) => e.occurred_at,
+            Self::UserRoleRevoked(e) => e.occurred_at,
+            Self::UserDeleted(e) => e.occurred_at,
+            Self::UserRestored(e) => e.occurred_at,
+            Self::UserPurged(e) => e.occurred_at,
+            Self::PasswordResetRequested(e) => e.occurred_at,
+        }
+    }
+}
+
+/// A concrete event type that subscribers can listen for
+///
+/// Implemented by every payload of [`DomainEvent`], so subscribers can be
+/// written against `UserRegistered` instead of matching on the enum.
+pub trait Event: Send + Sync + 'static {
+    /// Extract this event type from a domain event, if it is one
+    fn from_domain(event: &DomainEvent) -> Option<&Self>;
+}
+
+macro_rules! impl_event {
+    ($($variant:ident),* $(,)?) => {
+        $(
+            impl Event for $variant {
+                fn from_domain(event: &DomainEvent) -> Option<&Self> {
+                    match event {
+                        DomainEvent::$variant(e) => Some(e),
+                        _ => None,
+                    }
+                }
+            }
+
+            impl From<$variant> for DomainEvent {
+                fn from(event: $variant) -> Self {
+                    Self::$variant(event)
+                }
+            }
+        )*
+    };
+}
+
+impl_event!(
+    UserRegistered,
+    UserRenamed,
+    UserEmailChanged,
+    UserEmailVerified,
+    UserPasswordChanged,
+    UserStatusChanged,
+    UserRoleAssigned,
+    UserRoleRevoked,
+    UserDeleted,
+    UserRestored,
+    UserPurged,
+    PasswordResetRequested,
+);
+
+/// Every event is an [`Event`] too, for subscribers interested in all of them
+impl Event for DomainEvent {
+    fn from_domain(event: &DomainEvent) -> Option<&Self> {
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renamed() -> DomainEvent {
+        UserRenamed {
+            user_id: UserId::new(),
+            tenant_id: TenantId::default(),
+            old_name: DisplayName::new("Old").unwrap(),
+            new_name: DisplayName::new("New").unwrap(),
+            occurred_at: Utc::now(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_typed_extraction() {
+        let event = renamed();
+
+        assert_eq!(UserRenamed::from_domain(&event).unwrap().new_name, "New");
+        assert!(UserRegistered::from_domain(&event).is_none());
+        assert!(DomainEvent::from_domain(&event).is_some());
+    }
+
+    #[test]
+    fn test_serialization_is_tagged() {
+        let event = renamed();
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["type"], "UserRenamed");
+        assert_eq!(json["data"]["new_name"], "New");
+        assert_eq!(serde_json::from_value::<DomainEvent>(json).unwrap(), event);
+    }
+}
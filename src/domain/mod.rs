@@ -2,6 +2,7 @@
 //!
 //! This module contains the heart of the application:
 //! - **entities**: Business objects and value objects
+//! - **events**: Domain events raised by entities
 //! - **ports**: Trait definitions (interfaces) for external dependencies
 //! - **services**: Business logic and use cases
 //! - **errors**: Domain-specific error types
@@ -14,6 +15,7 @@
 
 pub mod entities;
 pub mod errors;
+pub mod events;
 pub mod ports;
 pub mod services;
 
@@ -0,0 +1,38 @@
+//! Audit log port definitions
+//!
+//! Keep a trail of who changed which user, and how.
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::{AuditQuery, AuditRecord, TenantId},
+    errors::DomainError,
+};
+
+/// Audit log port
+///
+/// An append-only record of changes to users. Records are never updated or
+/// removed, not even when the user they describe is purged.
+#[async_trait]
+pub trait AuditLog: Send + Sync {
+    /// Append a record
+    async fn append(&self, record: &AuditRecord) -> Result<(), DomainError>;
+
+    /// Records of `tenant` selected by `query`, oldest first
+    async fn query(
+        &self,
+        tenant: &TenantId,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditRecord>, DomainError>;
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub AuditLog {}
+
+    #[async_trait]
+    impl AuditLog for AuditLog {
+        async fn append(&self, record: &AuditRecord) -> Result<(), DomainError>;
+        async fn query(&self, tenant: &TenantId, query: &AuditQuery) -> Result<Vec<AuditRecord>, DomainError>;
+    }
+}
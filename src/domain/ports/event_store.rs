@@ -0,0 +1,77 @@
+//! Event store port definitions
+//!
+//! Persist users as append-only streams of their domain events instead of
+//! as current state.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::{User, UserId},
+    errors::DomainError,
+    events::DomainEvent,
+};
+
+/// State of a user after the first `version` events of its stream
+///
+/// Lets a user be loaded without replaying its whole history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Number of events folded into `user`
+    pub version: u64,
+    /// The user as rebuilt from those events
+    pub user: User,
+}
+
+/// Event store port
+///
+/// Holds one append-only stream of events per user. A stream's version is
+/// the number of events in it.
+///
+/// # Example Implementation
+///
+/// ```rust,ignore
+/// pub struct PostgresEventStore {
+///     pool: PgPool,
+/// }
+///
+/// #[async_trait]
+/// impl EventStore for PostgresEventStore {
+///     async fn append(
+///         &self,
+///         id: &UserId,
+///         expected_version: u64,
+///         events: &[DomainEvent],
+///     ) -> Result<u64, DomainError> {
+///         // INSERT ... (stream_id, version, payload); a unique index on
+///         // (stream_id, version) rejects concurrent appends
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Append `events` to the stream of `id` and return its new version
+    ///
+    /// Fails with `Conflict` unless the stream is at `expected_version`,
+    /// so concurrent writers cannot interleave their events.
+    async fn append(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        events: &[DomainEvent],
+    ) -> Result<u64, DomainError>;
+
+    /// Events of the stream of `id` after the first `after`, oldest first
+    ///
+    /// An unknown stream is empty.
+    async fn read(&self, id: &UserId, after: u64) -> Result<Vec<DomainEvent>, DomainError>;
+
+    /// IDs of every non-empty stream
+    async fn stream_ids(&self) -> Result<Vec<UserId>, DomainError>;
+
+    /// Store a snapshot, replacing any earlier one of the same stream
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), DomainError>;
+
+    /// Latest snapshot of the stream of `id`
+    async fn load_snapshot(&self, id: &UserId) -> Result<Option<Snapshot>, DomainError>;
+}
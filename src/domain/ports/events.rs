@@ -0,0 +1,57 @@
+//! Event port definitions
+//!
+//! Domain services hand recorded events to an [`EventPublisher`]; adapters
+//! decide how they reach the [`EventHandler`]s interested in them.
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    errors::DomainError,
+    events::{DomainEvent, Event},
+};
+
+/// Event publisher port
+///
+/// Called after the state change behind the events has been persisted.
+/// Implementations may deliver synchronously, in the background or to an
+/// external broker.
+///
+/// # Example Implementation
+///
+/// ```rust,ignore
+/// pub struct KafkaEventPublisher {
+///     producer: FutureProducer,
+/// }
+///
+/// #[async_trait]
+/// impl EventPublisher for KafkaEventPublisher {
+///     async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), DomainError> {
+///         // Serialize each event and send it to a topic
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// Publish events, oldest first
+    async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), DomainError>;
+}
+
+/// Subscriber to one type of event
+///
+/// `E` is a concrete event such as `UserRegistered`, or [`DomainEvent`] to
+/// receive everything.
+#[async_trait]
+pub trait EventHandler<E: Event>: Send + Sync {
+    /// React to an event
+    async fn handle(&self, event: &E) -> Result<(), DomainError>;
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub EventPublisher {}
+
+    #[async_trait]
+    impl EventPublisher for EventPublisher {
+        async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), DomainError>;
+    }
+}
@@ -0,0 +1,60 @@
+//! ID generator port definitions
+//!
+//! Hand out identifiers for new users and records, so tests and replays
+//! can predict them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use uuid::Uuid;
+
+/// ID generator port
+///
+/// Identifiers must be unique; they need not be unpredictable, so secrets
+/// such as emailed tokens never come from here.
+pub trait IdGenerator: Send + Sync {
+    /// A new, unused identifier
+    fn next_id(&self) -> Uuid;
+}
+
+/// Time-ordered random UUIDs (version 7)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn next_id(&self) -> Uuid {
+        Uuid::now_v7()
+    }
+}
+
+/// Generator counting up from `00000000-0000-0000-0000-000000000001`, for
+/// tests
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    last: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first ID is 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> Uuid {
+        let id = self.last.fetch_add(1, Ordering::Relaxed) + 1;
+        Uuid::from_u128(u128::from(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequential_ids() {
+        let ids = SequentialIdGenerator::new();
+        assert_eq!(ids.next_id(), Uuid::from_u128(1));
+        assert_eq!(ids.next_id(), Uuid::from_u128(2));
+    }
+}
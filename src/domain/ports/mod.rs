@@ -5,15 +5,39 @@
 //!
 //! ## Types of Ports
 //!
-//! - **Repository ports**: Data persistence abstractions
-//! - **Service ports**: External service abstractions (email, payments, etc.)
+//! - **Repository ports**: Data persistence abstractions, listing users
+//!   page by page with a [`UserQuery`]
+//! - **Service ports**: External service abstractions (email, password hashing, etc.)
+//! - **Event ports**: Publishing domain events and subscribing to them
+//! - **Event store port**: Append-only event streams for event-sourced users
+//! - **Audit log port**: Append-only record of who changed which user
+//! - **Clock and ID generator ports**: The current time and fresh
+//!   identifiers; the system implementations and test fakes live next to
+//!   the traits, as the domain services default to them
 //!
 //! ## Key Principle
 //!
 //! The domain defines WHAT it needs (traits), adapters define HOW to provide it.
 
+pub mod audit;
+pub mod clock;
+pub mod event_store;
+pub mod events;
+pub mod ids;
+pub mod query;
 pub mod repositories;
 pub mod services;
 
-pub use repositories::UserRepository;
-pub use services::EmailService;
+pub use audit::AuditLog;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use event_store::{EventStore, Snapshot};
+pub use events::{EventHandler, EventPublisher};
+pub use ids::{IdGenerator, SequentialIdGenerator, UuidV7Generator};
+pub use query::{
+    Cursor, Page, Position, SortDirection, UserQuery, UserSortKey, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+pub use repositories::{
+    AggregateRoot, OutboxRepository, PasswordResetTokenRepository, Repository, UserRepository,
+    UserStream, VerificationTokenRepository,
+};
+pub use services::{EmailService, PasswordHasher, TokenSigner};
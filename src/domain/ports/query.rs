@@ -0,0 +1,539 @@
+//! User query specification
+//!
+//! Describes which users a listing selects, in which order, and where a
+//! page starts. Repositories that cannot push a query down to their store
+//! evaluate it with [`UserQuery::page`].
+
+use std::cmp::Ordering;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use icu_normalizer::ComposingNormalizerBorrowed;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{User, UserId, UserStatusKind},
+    errors::DomainError,
+};
+
+/// Page size used unless a query asks for another
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Largest page a query may ask for
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Field users are ordered by
+///
+/// Ties are broken by user ID, so the order is total and stable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortKey {
+    /// Registration time
+    #[default]
+    CreatedAt,
+    /// Canonical email address
+    Email,
+    /// Display name, ignoring case
+    Name,
+}
+
+impl UserSortKey {
+    /// Short machine-readable name of the key
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::Email => "email",
+            Self::Name => "name",
+        }
+    }
+
+    /// Value of the key for `user`
+    ///
+    /// Values compare as plain strings; times are rendered at a fixed
+    /// precision so that their text order is their chronological order.
+    pub fn value(self, user: &User) -> String {
+        match self {
+            Self::CreatedAt => user.created_at.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            Self::Email => user.email.canonical().to_string(),
+            Self::Name => user.name.as_str().to_lowercase(),
+        }
+    }
+}
+
+impl std::fmt::Display for UserSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for UserSortKey {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created_at" => Ok(Self::CreatedAt),
+            "email" => Ok(Self::Email),
+            "name" => Ok(Self::Name),
+            other => Err(DomainError::validation(format!(
+                "Unknown sort key: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// Short machine-readable name of the direction
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ascending => "asc",
+            Self::Descending => "desc",
+        }
+    }
+
+    /// `ordering` of two ascending values, turned to this direction
+    pub fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            Self::Ascending => ordering,
+            Self::Descending => ordering.reverse(),
+        }
+    }
+}
+
+impl std::fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SortDirection {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(Self::Ascending),
+            "desc" => Ok(Self::Descending),
+            other => Err(DomainError::validation(format!(
+                "Unknown sort direction: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Where a page ends: the sort value and ID of its last user
+///
+/// Carries the sort it was taken under, so a cursor cannot be replayed
+/// against a differently ordered query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub sort: UserSortKey,
+    pub direction: SortDirection,
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl Position {
+    /// Whether this position comes before `(value, id)` in its sort
+    pub fn is_before(&self, value: &str, id: &Uuid) -> bool {
+        let ordering = (value, id).cmp(&(self.value.as_str(), &self.id));
+        self.direction.apply(ordering) == Ordering::Greater
+    }
+}
+
+/// Opaque token naming where the next page starts
+///
+/// Clients pass it back unchanged; its contents are not part of the API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// Cursor pointing just after `position`
+    pub fn new(position: &Position) -> Self {
+        let json = serde_json::to_vec(position).expect("positions serialize");
+        Self(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// The position the cursor points after
+    pub fn position(&self) -> Result<Position, DomainError> {
+        URL_SAFE_NO_PAD
+            .decode(&self.0)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| DomainError::validation("Invalid cursor"))
+    }
+
+    /// The token as handed to clients
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for Cursor {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cursor = Self(s.to_string());
+        cursor.position()?;
+        Ok(cursor)
+    }
+}
+
+/// One page of a listing
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// Items of this page, in the query's order
+    pub items: Vec<T>,
+    /// Where the next page starts, unless this is the last one
+    pub next_cursor: Option<Cursor>,
+    /// Number of items matching the query across all pages
+    pub total: usize,
+}
+
+impl<T> Page<T> {
+    /// Convert the items, keeping the cursor and total
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total: self.total,
+        }
+    }
+}
+
+/// Which live users of a tenant to list, in which order, from where
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserQuery {
+    /// Only users whose email is at this domain, ignoring case
+    pub email_domain: Option<String>,
+    /// Only users whose name starts with this, ignoring case
+    pub name_prefix: Option<String>,
+    /// Only users registered at or after this time
+    pub created_from: Option<DateTime<Utc>>,
+    /// Only users registered before this time
+    pub created_until: Option<DateTime<Utc>>,
+    /// Only users in this status
+    pub status: Option<UserStatusKind>,
+    /// Field to order by
+    pub sort: UserSortKey,
+    /// Order ascending or descending
+    pub direction: SortDirection,
+    /// Start after this cursor, from a previous page
+    pub after: Option<Cursor>,
+    /// Maximum number of users per page
+    pub limit: usize,
+}
+
+impl Default for UserQuery {
+    fn default() -> Self {
+        Self {
+            email_domain: None,
+            name_prefix: None,
+            created_from: None,
+            created_until: None,
+            status: None,
+            sort: UserSortKey::default(),
+            direction: SortDirection::default(),
+            after: None,
+            limit: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl UserQuery {
+    /// The email domain filter in canonical (lowercase ASCII) form
+    pub fn canonical_email_domain(&self) -> Option<String> {
+        self.email_domain
+            .as_deref()
+            .map(|domain| idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase()))
+    }
+
+    /// The name prefix filter, normalized like names and lowercased
+    pub fn canonical_name_prefix(&self) -> Option<String> {
+        self.name_prefix.as_deref().map(|prefix| {
+            ComposingNormalizerBorrowed::new_nfc()
+                .normalize(prefix)
+                .to_lowercase()
+        })
+    }
+
+    /// The position to start after, checked against this query's sort
+    pub fn start(&self) -> Result<Option<Position>, DomainError> {
+        if self.limit == 0 || self.limit > MAX_PAGE_SIZE {
+            return Err(DomainError::validation(format!(
+                "Page size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        let Some(cursor) = &self.after else {
+            return Ok(None);
+        };
+        let position = cursor.position()?;
+        if position.sort != self.sort || position.direction != self.direction {
+            return Err(DomainError::validation(
+                "Cursor belongs to a differently sorted query",
+            ));
+        }
+        Ok(Some(position))
+    }
+
+    /// Position of `user` in this query's sort
+    pub fn position_of(&self, user: &User) -> Position {
+        Position {
+            sort: self.sort,
+            direction: self.direction,
+            value: self.sort.value(user),
+            id: user.id.0,
+        }
+    }
+
+    /// The users among `users` passing this query's filters, with their
+    /// sort values
+    fn matching<'a>(&self, users: impl IntoIterator<Item = &'a User>) -> Vec<(String, &'a User)> {
+        let email_domain = self.canonical_email_domain();
+        let name_prefix = self.canonical_name_prefix();
+
+        users
+            .into_iter()
+            .filter(|user| {
+                email_domain.as_deref().map_or(true, |domain| {
+                    user.email
+                        .canonical()
+                        .rsplit_once('@')
+                        .is_some_and(|(_, d)| d == domain)
+                }) && name_prefix.as_deref().map_or(true, |prefix| {
+                    user.name.as_str().to_lowercase().starts_with(prefix)
+                }) && self
+                    .created_from
+                    .map_or(true, |from| user.created_at >= from)
+                    && self
+                        .created_until
+                        .map_or(true, |until| user.created_at < until)
+                    && self
+                        .status
+                        .map_or(true, |status| user.status.kind() == status)
+            })
+            .map(|user| (self.sort.value(user), user))
+            .collect()
+    }
+
+    /// IDs of the users from `users` on this query's pages, in its order;
+    /// `users` must all be live users of the queried tenant
+    ///
+    /// Lets stores holding their users in memory sort them once to stream
+    /// them, instead of selecting each page anew.
+    pub fn ordered_ids<'a>(
+        &self,
+        users: impl IntoIterator<Item = &'a User>,
+    ) -> Result<Vec<UserId>, DomainError> {
+        let start = self.start()?;
+        let mut matching = self.matching(users);
+        if let Some(start) = start {
+            matching.retain(|(value, user)| start.is_before(value, &user.id.0));
+        }
+        matching.sort_by(|(a, x), (b, y)| self.direction.apply((a, x.id.0).cmp(&(b, y.id.0))));
+        Ok(matching.into_iter().map(|(_, user)| user.id).collect())
+    }
+
+    /// Select a page from `users`, which must all be live users of the
+    /// queried tenant
+    pub fn page<'a>(
+        &self,
+        users: impl IntoIterator<Item = &'a User>,
+    ) -> Result<Page<User>, DomainError> {
+        let start = self.start()?;
+        let mut matching = self.matching(users);
+        let total = matching.len();
+
+        if let Some(start) = start {
+            matching.retain(|(value, user)| start.is_before(value, &user.id.0));
+        }
+
+        // Only the page itself is sorted, so each page costs time linear in
+        // the number of matching users
+        let order = |(a, x): &(String, &User), (b, y): &(String, &User)| {
+            self.direction.apply((a, x.id.0).cmp(&(b, y.id.0)))
+        };
+        let more = matching.len() > self.limit;
+        if more {
+            matching.select_nth_unstable_by(self.limit, order);
+            matching.truncate(self.limit);
+        }
+        matching.sort_by(order);
+
+        let items: Vec<User> = matching.into_iter().map(|(_, user)| user.clone()).collect();
+        let next_cursor = match items.last() {
+            Some(last) if more => Some(Cursor::new(&self.position_of(last))),
+            _ => None,
+        };
+
+        Ok(Page {
+            items,
+            next_cursor,
+            total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DisplayName, TenantId, UserId};
+
+    fn user(email: &str, name: &str, minutes: i64) -> User {
+        let created = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + chrono::Duration::minutes(minutes);
+        let mut user = User::fixture(TenantId::default(), email);
+        user.name = DisplayName::new(name).unwrap();
+        user.created_at = created;
+        user
+    }
+
+    fn names(page: &Page<User>) -> Vec<&str> {
+        page.items.iter().map(|u| u.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut bob = user("bob@Example.com", "Bob", 1);
+        bob.activate(bob.created_at).unwrap();
+        let users = [
+            user("ada@example.com", "Ada", 0),
+            bob,
+            user("alan@other.org", "alan", 2),
+        ];
+
+        let query = UserQuery {
+            email_domain: Some("EXAMPLE.com".to_string()),
+            ..UserQuery::default()
+        };
+        assert_eq!(names(&query.page(&users).unwrap()), ["Ada", "Bob"]);
+
+        let query = UserQuery {
+            name_prefix: Some("A".to_string()),
+            ..UserQuery::default()
+        };
+        assert_eq!(names(&query.page(&users).unwrap()), ["Ada", "alan"]);
+
+        let query = UserQuery {
+            created_from: Some(users[1].created_at),
+            created_until: Some(users[2].created_at),
+            ..UserQuery::default()
+        };
+        assert_eq!(names(&query.page(&users).unwrap()), ["Bob"]);
+
+        let query = UserQuery {
+            status: Some(UserStatusKind::Pending),
+            ..UserQuery::default()
+        };
+        assert_eq!(query.page(&users).unwrap().total, 2);
+    }
+
+    #[test]
+    fn test_cursor_walks_every_page_in_order() {
+        let users: Vec<User> = (0..7)
+            .map(|i| user(&format!("u{}@example.com", i), &format!("User {}", i), i))
+            .collect();
+        let mut query = UserQuery {
+            direction: SortDirection::Descending,
+            limit: 3,
+            ..UserQuery::default()
+        };
+
+        let mut seen = Vec::new();
+        loop {
+            let page = query.page(&users).unwrap();
+            assert_eq!(page.total, 7);
+            seen.extend(page.items.iter().map(|u| u.id));
+            match page.next_cursor {
+                Some(cursor) => query.after = Some(cursor),
+                None => break,
+            }
+        }
+
+        let expected: Vec<UserId> = users.iter().rev().map(|u| u.id).collect();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_ordered_ids_follow_the_pages() {
+        let users: Vec<User> = (0..7)
+            .map(|i| user(&format!("u{}@example.com", i), &format!("User {}", i), i))
+            .collect();
+        let mut query = UserQuery {
+            direction: SortDirection::Descending,
+            limit: 3,
+            ..UserQuery::default()
+        };
+        let expected: Vec<UserId> = users.iter().rev().map(|u| u.id).collect();
+        assert_eq!(query.ordered_ids(&users).unwrap(), expected);
+
+        query.after = query.page(&users).unwrap().next_cursor;
+        assert_eq!(query.ordered_ids(&users).unwrap(), expected[3..]);
+    }
+
+    #[test]
+    fn test_sort_by_name_ignores_case() {
+        let users = [
+            user("a@example.com", "bob", 0),
+            user("b@example.com", "Alice", 1),
+            user("c@example.com", "Carol", 2),
+        ];
+        let query = UserQuery {
+            sort: UserSortKey::Name,
+            ..UserQuery::default()
+        };
+        assert_eq!(
+            names(&query.page(&users).unwrap()),
+            ["Alice", "bob", "Carol"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_cursors_are_rejected() {
+        assert!("not a cursor".parse::<Cursor>().is_err());
+
+        let users = [user("a@example.com", "A", 0), user("b@example.com", "B", 1)];
+        let page = UserQuery {
+            limit: 1,
+            ..UserQuery::default()
+        }
+        .page(&users)
+        .unwrap();
+
+        let resorted = UserQuery {
+            sort: UserSortKey::Email,
+            after: page.next_cursor,
+            ..UserQuery::default()
+        };
+        assert!(matches!(
+            resorted.page(&users),
+            Err(DomainError::ValidationError(_))
+        ));
+
+        let empty = UserQuery {
+            limit: 0,
+            ..UserQuery::default()
+        };
+        assert!(empty.page(&users).is_err());
+    }
+}
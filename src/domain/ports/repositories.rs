@@ -2,18 +2,141 @@
 //!
 //! Repositories abstract data persistence. The domain defines what operations
 //! it needs; adapters implement how to perform them (PostgreSQL, SQLite, etc.).
+//!
+//! [`Repository`] covers what every [`AggregateRoot`] needs, so a new entity
+//! only has to implement `AggregateRoot` to get generic in-memory and file
+//! storage; entity-specific ports such as [`UserRepository`] add their own
+//! lookups on top.
+
+use std::fmt::Display;
+use std::hash::Hash;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, TryStreamExt};
+use uuid::Uuid;
 
 use crate::domain::{
-    entities::{Email, User, UserId},
+    entities::{
+        Email, OutboxMessage, OutboxStatus, PasswordResetToken, TenantId, User, UserId,
+        VerificationToken,
+    },
     errors::DomainError,
+    ports::{Page, UserQuery},
 };
 
+/// An entity that is loaded and saved as a whole
+///
+/// Aggregates belong to a tenant and carry a version that grows with every
+/// change, which repositories use to reject concurrent writes.
+pub trait AggregateRoot: Clone + Send + Sync + 'static {
+    /// Identifier, unique within a tenant
+    type Id: Copy + Eq + Hash + Display + Send + Sync;
+
+    /// Name of the aggregate in error messages
+    const NAME: &'static str;
+
+    /// The aggregate's identifier
+    fn id(&self) -> Self::Id;
+
+    /// Tenant the aggregate belongs to
+    fn tenant_id(&self) -> &TenantId;
+
+    /// Current version, counting every change
+    fn version(&self) -> u64;
+
+    /// Version of the stored copy this aggregate was loaded from, or 0 if
+    /// it was never saved
+    ///
+    /// The default suits aggregates that bump their version once per saved
+    /// change; aggregates recording several pending changes subtract those.
+    fn expected_version(&self) -> u64 {
+        self.version().saturating_sub(1)
+    }
+
+    /// Whether the aggregate is deleted but kept until purged
+    fn is_deleted(&self) -> bool {
+        false
+    }
+
+    /// The aggregate as repositories keep it, e.g. without pending events
+    fn stored(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl AggregateRoot for User {
+    type Id = UserId;
+
+    const NAME: &'static str = "User";
+
+    fn id(&self) -> UserId {
+        self.id
+    }
+
+    fn tenant_id(&self) -> &TenantId {
+        &self.tenant_id
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn expected_version(&self) -> u64 {
+        User::expected_version(self)
+    }
+
+    fn is_deleted(&self) -> bool {
+        User::is_deleted(self)
+    }
+
+    fn stored(&self) -> Self {
+        self.without_events()
+    }
+}
+
+/// Repository port for any aggregate
+///
+/// Every operation is scoped to a single tenant: aggregates of other
+/// tenants must be invisible.
+///
+/// Deleted aggregates (see [`AggregateRoot::is_deleted`]) are kept until
+/// purged, but only [`find_deleted`](Self::find_deleted) and
+/// [`list_deleted`](Self::list_deleted) return them.
+#[async_trait]
+pub trait Repository<A: AggregateRoot>: Send + Sync {
+    /// Find a live aggregate of `tenant` by its ID
+    async fn find_by_id(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError>;
+
+    /// Save an aggregate (insert or update) within its own tenant
+    ///
+    /// Fails with a conflict unless the stored version is the aggregate's
+    /// [`expected_version`](AggregateRoot::expected_version).
+    async fn save(&self, aggregate: &A) -> Result<(), DomainError>;
+
+    /// Permanently remove an aggregate of `tenant` by its ID
+    async fn delete(&self, tenant: &TenantId, id: &A::Id) -> Result<(), DomainError>;
+
+    /// List all live aggregates of `tenant`
+    async fn list(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError>;
+
+    /// Find a deleted aggregate of `tenant` by its ID
+    async fn find_deleted(&self, tenant: &TenantId, id: &A::Id) -> Result<Option<A>, DomainError>;
+
+    /// List the deleted aggregates of `tenant`
+    async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<A>, DomainError>;
+}
+
+/// Users yielded one by one by [`UserRepository::stream`]
+pub type UserStream<'a> = BoxStream<'a, Result<User, DomainError>>;
+
 /// User repository port
 ///
-/// Defines operations for persisting and retrieving users.
-/// Implement this trait for your specific storage backend.
+/// Adds lookups by email to the generic [`Repository`] operations.
+/// Email addresses are unique per tenant only.
+///
+/// `user.pending_events()` holds the changes since a user was loaded, for
+/// stores that persist events rather than state.
 ///
 /// # Example Implementation
 ///
@@ -24,27 +147,222 @@ use crate::domain::{
 ///
 /// #[async_trait]
 /// impl UserRepository for PostgresUserRepository {
-///     async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-///         // PostgreSQL implementation
+///     async fn find_by_email(
+///         &self,
+///         tenant: &TenantId,
+///         email: &Email,
+///     ) -> Result<Option<User>, DomainError> {
+///         // SELECT ... WHERE tenant_id = $1 AND email_canonical = $2
 ///     }
 /// }
 /// ```
 #[async_trait]
-pub trait UserRepository: Send + Sync {
-    /// Find a user by their ID
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError>;
+pub trait UserRepository: Repository<User> {
+    /// Find a live user of `tenant` by their email
+    async fn find_by_email(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<Option<User>, DomainError>;
+
+    /// A page of the live users of `tenant` selected by `query`
+    async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError>;
+
+    /// Save several users, each as [`save`](Repository::save) would
+    ///
+    /// Returns one result per user, in order, so a stale user fails alone.
+    /// The default saves them one at a time.
+    async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>> {
+        let mut results = Vec::with_capacity(users.len());
+        for user in users {
+            results.push(self.save(user).await);
+        }
+        results
+    }
 
-    /// Find a user by their email
-    async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError>;
+    /// The live users of `tenant` among `ids`, in the order of `ids`
+    ///
+    /// Unknown IDs are skipped. The default looks them up one at a time.
+    async fn find_many_by_ids(
+        &self,
+        tenant: &TenantId,
+        ids: &[UserId],
+    ) -> Result<Vec<User>, DomainError> {
+        let mut users = Vec::with_capacity(ids.len());
+        for id in ids {
+            users.extend(self.find_by_id(tenant, id).await?);
+        }
+        Ok(users)
+    }
+
+    /// The live users of `tenant` owning any of `emails`, in no particular
+    /// order
+    ///
+    /// The default looks them up one at a time.
+    async fn find_many_by_emails(
+        &self,
+        tenant: &TenantId,
+        emails: &[Email],
+    ) -> Result<Vec<User>, DomainError> {
+        let mut users = Vec::with_capacity(emails.len());
+        for email in emails {
+            users.extend(self.find_by_email(tenant, email).await?);
+        }
+        Ok(users)
+    }
 
-    /// Save a user (insert or update)
-    async fn save(&self, user: &User) -> Result<(), DomainError>;
+    /// Permanently remove the users of `tenant` with `ids`
+    ///
+    /// The default removes them one at a time.
+    async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError> {
+        for id in ids {
+            self.delete(tenant, id).await?;
+        }
+        Ok(())
+    }
 
-    /// Delete a user by their ID
-    async fn delete(&self, id: &UserId) -> Result<(), DomainError>;
+    /// Every live user of `tenant` selected by `query`, across all pages
+    ///
+    /// Users are fetched a page of `query.limit` at a time, starting after
+    /// `query.after`, and only once the previous page was consumed, so a
+    /// slow consumer holds back the reads. The default walks
+    /// [`query`](Self::query) page by page; stores with server-side cursors
+    /// may do better.
+    fn stream<'a>(&'a self, tenant: &'a TenantId, query: UserQuery) -> UserStream<'a> {
+        let pages = stream::try_unfold(Some(query), move |query| async move {
+            let Some(mut query) = query else {
+                return Ok::<_, DomainError>(None);
+            };
+            let page = self.query(tenant, &query).await?;
+            let next = page.next_cursor.map(|cursor| {
+                query.after = Some(cursor);
+                query
+            });
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), next)))
+        });
+        Box::pin(pages.try_flatten())
+    }
 
-    /// List all users (with optional pagination in real implementations)
-    async fn list(&self) -> Result<Vec<User>, DomainError>;
+    /// Save a user and append `messages` to the outbox in one atomic write
+    ///
+    /// Only repositories that also implement [`OutboxRepository`] over the
+    /// same store can honour this; the default reports it as unsupported.
+    async fn save_with_outbox(
+        &self,
+        user: &User,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let _ = (user, messages);
+        Err(outbox_unsupported())
+    }
+
+    /// Permanently remove a user and append `messages` to the outbox in one
+    /// atomic write
+    ///
+    /// See [`save_with_outbox`](Self::save_with_outbox).
+    async fn delete_with_outbox(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let _ = (tenant, id, messages);
+        Err(outbox_unsupported())
+    }
+}
+
+fn outbox_unsupported() -> DomainError {
+    DomainError::Infrastructure(anyhow::anyhow!(
+        "This repository does not support a transactional outbox"
+    ))
+}
+
+/// Outbox repository port
+///
+/// Reads and updates the messages written by
+/// [`UserRepository::save_with_outbox`]. Messages are only ever appended
+/// together with a user write, so there is no standalone insert.
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Pending messages due for delivery at `now`, oldest first
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, DomainError>;
+
+    /// Store the new delivery state of a message
+    async fn update(&self, message: &OutboxMessage) -> Result<(), DomainError>;
+
+    /// Messages of `tenant`, optionally only those in `status`, oldest first
+    async fn list(
+        &self,
+        tenant: &TenantId,
+        status: Option<OutboxStatus>,
+    ) -> Result<Vec<OutboxMessage>, DomainError>;
+}
+
+/// Verification token repository port
+///
+/// Stores email verification tokens until they are redeemed or expire.
+#[async_trait]
+pub trait VerificationTokenRepository: Send + Sync {
+    /// Store a newly issued token
+    async fn save(&self, token: &VerificationToken) -> Result<(), DomainError>;
+
+    /// Store several newly issued tokens
+    ///
+    /// The default saves them one at a time.
+    async fn save_many(&self, tokens: &[VerificationToken]) -> Result<(), DomainError> {
+        for token in tokens {
+            self.save(token).await?;
+        }
+        Ok(())
+    }
+
+    /// Find a token by its ID
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<VerificationToken>, DomainError>;
+
+    /// Atomically mark an unused token as used
+    ///
+    /// Returns `false` if the token does not exist or was already used, so
+    /// concurrent redemptions of the same token succeed at most once.
+    async fn consume(&self, id: &Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError>;
+}
+
+/// Password reset token repository port
+///
+/// Tokens are looked up by their keyed digest; plaintext tokens never
+/// reach storage.
+#[async_trait]
+pub trait PasswordResetTokenRepository: Send + Sync {
+    /// Store a newly issued token
+    async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError>;
+
+    /// Find a token by its digest
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, DomainError>;
+
+    /// Atomically mark an unused token as used
+    ///
+    /// Returns `false` if the token does not exist or was already used.
+    async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError>;
+
+    /// Store a newly issued token and append `messages` to the outbox in one
+    /// atomic write
+    ///
+    /// Only repositories sharing a store with an [`OutboxRepository`] can
+    /// honour this; the default reports it as unsupported.
+    async fn save_with_outbox(
+        &self,
+        token: &PasswordResetToken,
+        messages: &[OutboxMessage],
+    ) -> Result<(), DomainError> {
+        let _ = (token, messages);
+        Err(outbox_unsupported())
+    }
 }
 
 // Generate mock for testing (when mockall feature is enabled in tests)
@@ -52,12 +370,63 @@ pub trait UserRepository: Send + Sync {
 mockall::mock! {
     pub UserRepository {}
 
+    #[async_trait]
+    impl Repository<User> for UserRepository {
+        async fn find_by_id(&self, tenant: &TenantId, id: &UserId) -> Result<Option<User>, DomainError>;
+        async fn save(&self, aggregate: &User) -> Result<(), DomainError>;
+        async fn delete(&self, tenant: &TenantId, id: &UserId) -> Result<(), DomainError>;
+        async fn list(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError>;
+        async fn find_deleted(&self, tenant: &TenantId, id: &UserId) -> Result<Option<User>, DomainError>;
+        async fn list_deleted(&self, tenant: &TenantId) -> Result<Vec<User>, DomainError>;
+    }
+
     #[async_trait]
     impl UserRepository for UserRepository {
-        async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError>;
-        async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError>;
-        async fn save(&self, user: &User) -> Result<(), DomainError>;
-        async fn delete(&self, id: &UserId) -> Result<(), DomainError>;
-        async fn list(&self) -> Result<Vec<User>, DomainError>;
+        async fn find_by_email(&self, tenant: &TenantId, email: &Email) -> Result<Option<User>, DomainError>;
+        async fn query(&self, tenant: &TenantId, query: &UserQuery) -> Result<Page<User>, DomainError>;
+        async fn save_many(&self, users: &[User]) -> Vec<Result<(), DomainError>>;
+        async fn find_many_by_ids(&self, tenant: &TenantId, ids: &[UserId]) -> Result<Vec<User>, DomainError>;
+        async fn find_many_by_emails(&self, tenant: &TenantId, emails: &[Email]) -> Result<Vec<User>, DomainError>;
+        async fn delete_many(&self, tenant: &TenantId, ids: &[UserId]) -> Result<(), DomainError>;
+        async fn save_with_outbox(&self, user: &User, messages: &[OutboxMessage]) -> Result<(), DomainError>;
+        async fn delete_with_outbox(&self, tenant: &TenantId, id: &UserId, messages: &[OutboxMessage]) -> Result<(), DomainError>;
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub VerificationTokenRepository {}
+
+    #[async_trait]
+    impl VerificationTokenRepository for VerificationTokenRepository {
+        async fn save(&self, token: &VerificationToken) -> Result<(), DomainError>;
+        async fn save_many(&self, tokens: &[VerificationToken]) -> Result<(), DomainError>;
+        async fn find_by_id(&self, id: &Uuid) -> Result<Option<VerificationToken>, DomainError>;
+        async fn consume(&self, id: &Uuid, used_at: DateTime<Utc>) -> Result<bool, DomainError>;
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub PasswordResetTokenRepository {}
+
+    #[async_trait]
+    impl PasswordResetTokenRepository for PasswordResetTokenRepository {
+        async fn save(&self, token: &PasswordResetToken) -> Result<(), DomainError>;
+        async fn find_by_hash(&self, token_hash: &str) -> Result<Option<PasswordResetToken>, DomainError>;
+        async fn consume(&self, token_hash: &str, used_at: DateTime<Utc>) -> Result<bool, DomainError>;
+        async fn save_with_outbox(&self, token: &PasswordResetToken, messages: &[OutboxMessage]) -> Result<(), DomainError>;
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub OutboxRepository {}
+
+    #[async_trait]
+    impl OutboxRepository for OutboxRepository {
+        async fn find_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxMessage>, DomainError>;
+        async fn update(&self, message: &OutboxMessage) -> Result<(), DomainError>;
+        async fn list(&self, tenant: &TenantId, status: Option<OutboxStatus>) -> Result<Vec<OutboxMessage>, DomainError>;
     }
 }
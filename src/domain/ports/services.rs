@@ -5,7 +5,10 @@
 
 use async_trait::async_trait;
 
-use crate::domain::{entities::Email, errors::DomainError};
+use crate::domain::{
+    entities::{Email, PasswordHash},
+    errors::DomainError,
+};
 
 /// Email service port
 ///
@@ -41,6 +44,47 @@ pub trait EmailService: Send + Sync {
     ) -> Result<(), DomainError>;
 }
 
+/// Password hashing port
+///
+/// Abstracts the password hashing algorithm so the domain never handles
+/// salts or algorithm parameters directly.
+///
+/// # Example Implementation
+///
+/// ```rust,ignore
+/// pub struct BcryptPasswordHasher;
+///
+/// #[async_trait]
+/// impl PasswordHasher for BcryptPasswordHasher {
+///     async fn hash(&self, password: &str) -> Result<PasswordHash, DomainError> {
+///         // bcrypt::hash on a blocking thread
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait PasswordHasher: Send + Sync {
+    /// Hash a plaintext password with a fresh salt
+    async fn hash(&self, password: &str) -> Result<PasswordHash, DomainError>;
+
+    /// Check a plaintext password against a stored hash
+    ///
+    /// Returns `Ok(false)` on mismatch; errors are reserved for malformed
+    /// hashes or infrastructure failures.
+    async fn verify(&self, password: &str, hash: &PasswordHash) -> Result<bool, DomainError>;
+}
+
+/// Token signing port
+///
+/// Produces and checks signatures for tokens embedded in links, so forged
+/// tokens are rejected before any storage lookup.
+pub trait TokenSigner: Send + Sync {
+    /// Sign a payload, returning an URL-safe signature
+    fn sign(&self, payload: &str) -> String;
+
+    /// Check a signature in constant time
+    fn verify(&self, payload: &str, signature: &str) -> bool;
+}
+
 // Generate mock for testing
 #[cfg(test)]
 mockall::mock! {
@@ -52,3 +96,24 @@ mockall::mock! {
         async fn send_html(&self, to: &Email, subject: &str, html_body: &str) -> Result<(), DomainError>;
     }
 }
+
+#[cfg(test)]
+mockall::mock! {
+    pub PasswordHasher {}
+
+    #[async_trait]
+    impl PasswordHasher for PasswordHasher {
+        async fn hash(&self, password: &str) -> Result<PasswordHash, DomainError>;
+        async fn verify(&self, password: &str, hash: &PasswordHash) -> Result<bool, DomainError>;
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub TokenSigner {}
+
+    impl TokenSigner for TokenSigner {
+        fn sign(&self, payload: &str) -> String;
+        fn verify(&self, payload: &str, signature: &str) -> bool;
+    }
+}
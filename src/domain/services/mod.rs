@@ -8,6 +8,14 @@
 //! Services are generic over their dependencies (ports), making them
 //! easily testable with mock implementations.
 
+mod outbox_relay;
+mod password_reset_email;
 mod user_service;
+mod welcome_email;
 
-pub use user_service::UserService;
+pub use outbox_relay::{OutboxRelay, RelaySettings};
+pub use password_reset_email::PasswordResetEmail;
+pub use user_service::{
+    EmailVerification, NewUser, PasswordReset, PurgeReport, RegistrationReport, UserService,
+};
+pub use welcome_email::WelcomeEmail;
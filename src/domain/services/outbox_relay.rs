@@ -0,0 +1,230 @@
+//! Outbox relay
+//!
+//! Delivers stored outbox messages to an event publisher.
+
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::domain::{
+    entities::OutboxMessage,
+    errors::DomainError,
+    ports::{Clock, EventPublisher, OutboxRepository, SystemClock},
+};
+
+/// Tuning for an [`OutboxRelay`]
+#[derive(Debug, Clone)]
+pub struct RelaySettings {
+    /// Most messages handled per poll
+    pub batch_size: usize,
+    /// Pause between polls
+    pub poll_interval: std::time::Duration,
+    /// Attempts before a message is marked failed
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further attempt
+    pub base_backoff: Duration,
+    /// Upper bound on the retry delay
+    pub max_backoff: Duration,
+}
+
+impl Default for RelaySettings {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            poll_interval: std::time::Duration::from_secs(1),
+            max_attempts: 10,
+            base_backoff: Duration::seconds(1),
+            max_backoff: Duration::minutes(10),
+        }
+    }
+}
+
+/// Moves due outbox messages to an event publisher
+///
+/// Delivery is at-least-once: a crash between publishing and recording the
+/// delivery repeats the message, so subscribers should be idempotent. Run
+/// a single relay per outbox.
+///
+/// # Example Usage
+///
+/// ```rust,ignore
+/// let relay = OutboxRelay::new(repo.clone(), Arc::new(dispatcher));
+/// tokio::spawn(async move { relay.run().await });
+/// ```
+pub struct OutboxRelay {
+    outbox: Arc<dyn OutboxRepository>,
+    publisher: Arc<dyn EventPublisher>,
+    settings: RelaySettings,
+    clock: Arc<dyn Clock>,
+}
+
+impl OutboxRelay {
+    /// Create a relay with default settings
+    ///
+    /// `publisher` should report subscriber failures, or failed deliveries
+    /// will never be retried.
+    pub fn new(outbox: Arc<dyn OutboxRepository>, publisher: Arc<dyn EventPublisher>) -> Self {
+        Self {
+            outbox,
+            publisher,
+            settings: RelaySettings::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replace the default settings
+    pub fn with_settings(mut self, settings: RelaySettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Decide which messages are due, and when to retry, by `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Poll the outbox forever
+    ///
+    /// Errors reading or updating the outbox are logged and retried on the
+    /// next poll.
+    pub async fn run(&self) {
+        loop {
+            if let Err(e) = self.relay_once().await {
+                tracing::error!("Outbox relay failed: {}", e);
+            }
+            tokio::time::sleep(self.settings.poll_interval).await;
+        }
+    }
+
+    /// Attempt delivery of every due message once
+    ///
+    /// Returns how many messages were delivered.
+    pub async fn relay_once(&self) -> Result<usize, DomainError> {
+        let messages = self
+            .outbox
+            .find_due(self.clock.now(), self.settings.batch_size)
+            .await?;
+
+        let mut delivered = 0;
+        for mut message in messages {
+            if self.deliver(&mut message).await {
+                delivered += 1;
+            }
+            self.outbox.update(&message).await?;
+        }
+        Ok(delivered)
+    }
+
+    async fn deliver(&self, message: &mut OutboxMessage) -> bool {
+        match self.publisher.publish(vec![message.event.clone()]).await {
+            Ok(()) => {
+                message.mark_delivered(self.clock.now());
+                true
+            }
+            Err(e) => {
+                let backoff = self.backoff(message.attempts);
+                message.record_failure(
+                    e.to_string(),
+                    self.clock.now(),
+                    backoff,
+                    self.settings.max_attempts,
+                );
+                tracing::warn!(
+                    "Delivering outbox message {} ({}) failed on attempt {}: {}",
+                    message.id,
+                    message.event.name(),
+                    message.attempts,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// Retry delay after `attempts` earlier failures
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 1_i32.checked_shl(attempts.min(30)).unwrap_or(i32::MAX);
+        self.settings
+            .base_backoff
+            .checked_mul(factor)
+            .map_or(self.settings.max_backoff, |delay| {
+                delay.min(self.settings.max_backoff)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{OutboxStatus, TenantId, User};
+    use crate::domain::ports::events::MockEventPublisher;
+    use crate::domain::ports::repositories::MockOutboxRepository;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn message() -> OutboxMessage {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        OutboxMessage::new(Uuid::new_v4(), user.take_events().remove(0))
+    }
+
+    #[tokio::test]
+    async fn test_relay_marks_delivered() {
+        let mut outbox = MockOutboxRepository::new();
+        let mut publisher = MockEventPublisher::new();
+
+        let pending = message();
+        outbox
+            .expect_find_due()
+            .returning(move |_, _| Ok(vec![pending.clone()]));
+        outbox
+            .expect_update()
+            .withf(|m| m.status == OutboxStatus::Delivered && m.delivered_at.is_some())
+            .times(1)
+            .returning(|_| Ok(()));
+        publisher.expect_publish().times(1).returning(|_| Ok(()));
+
+        let relay = OutboxRelay::new(Arc::new(outbox), Arc::new(publisher));
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_relay_schedules_retry_on_failure() {
+        let mut outbox = MockOutboxRepository::new();
+        let mut publisher = MockEventPublisher::new();
+
+        let pending = message();
+        outbox
+            .expect_find_due()
+            .returning(move |_, _| Ok(vec![pending.clone()]));
+        outbox
+            .expect_update()
+            .withf(|m| {
+                m.status == OutboxStatus::Pending
+                    && m.attempts == 1
+                    && m.last_error.is_some()
+                    && m.next_attempt_at > Utc::now()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        publisher
+            .expect_publish()
+            .returning(|_| Err(DomainError::Infrastructure(anyhow::anyhow!("down"))));
+
+        let relay = OutboxRelay::new(Arc::new(outbox), Arc::new(publisher));
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_limit() {
+        let relay = OutboxRelay::new(
+            Arc::new(MockOutboxRepository::new()),
+            Arc::new(MockEventPublisher::new()),
+        );
+
+        assert_eq!(relay.backoff(0), Duration::seconds(1));
+        assert_eq!(relay.backoff(3), Duration::seconds(8));
+        assert_eq!(relay.backoff(20), Duration::minutes(10));
+        assert_eq!(relay.backoff(u32::MAX), Duration::minutes(10));
+    }
+}
@@ -0,0 +1,120 @@
+//! Password reset email subscriber
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainError,
+    events::PasswordResetRequested,
+    ports::{EmailService, EventHandler, TokenSigner},
+};
+
+/// The token emailed for the reset with `token_id`
+///
+/// Derived by signing the ID, so only holders of the signing key can
+/// compute it from a stored [`PasswordResetRequested`] event.
+pub(crate) fn reset_token(signer: &dyn TokenSigner, token_id: &Uuid) -> String {
+    signer.sign(&format!("password-reset:{}", token_id))
+}
+
+/// Emails password reset links to users who asked for one
+///
+/// Subscribe it to [`PasswordResetRequested`] on an event bus, with the
+/// signer the [`UserService`](super::UserService) resets passwords with.
+pub struct PasswordResetEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    email_service: Arc<E>,
+    signer: Arc<dyn TokenSigner>,
+    link_base_url: String,
+}
+
+impl<E> PasswordResetEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    /// Create a subscriber sending through `email_service` links to
+    /// `link_base_url`, with the token appended as `?token=...`
+    pub fn new(
+        email_service: Arc<E>,
+        signer: Arc<dyn TokenSigner>,
+        link_base_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            email_service,
+            signer,
+            link_base_url: link_base_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<E> EventHandler<PasswordResetRequested> for PasswordResetEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    async fn handle(&self, event: &PasswordResetRequested) -> Result<(), DomainError> {
+        let body = format!(
+            "A password reset was requested for your account.\n\n\
+             Choose a new password by visiting:\n{}?token={}\n\n\
+             This link expires in {} minutes. If you did not ask for this, \
+             ignore this email.",
+            self.link_base_url,
+            reset_token(self.signer.as_ref(), &event.token_id),
+            (event.expires_at - event.occurred_at).num_minutes()
+        );
+        self.email_service
+            .send(&event.email, "Reset your password", &body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Email, TenantId, UserId};
+    use crate::domain::ports::services::{MockEmailService, MockTokenSigner};
+
+    #[tokio::test]
+    async fn test_sends_link_with_derived_token() {
+        let token_id = Uuid::new_v4();
+        let link = format!(
+            "https://example.com/reset?token=sig-password-reset:{}",
+            token_id
+        );
+
+        let mut mock_email = MockEmailService::new();
+        mock_email
+            .expect_send()
+            .withf(move |to, subject, body| {
+                to.as_str() == "test@example.com"
+                    && subject == "Reset your password"
+                    && body.contains(&link)
+                    && body.contains("30 minutes")
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let mut signer = MockTokenSigner::new();
+        signer.expect_sign().returning(|p| format!("sig-{}", p));
+
+        let subscriber = PasswordResetEmail::new(
+            Arc::new(mock_email),
+            Arc::new(signer),
+            "https://example.com/reset",
+        );
+        let now = chrono::Utc::now();
+        let event = PasswordResetRequested {
+            user_id: UserId::new(),
+            tenant_id: TenantId::default(),
+            email: Email::new("test@example.com").unwrap(),
+            token_id,
+            expires_at: now + chrono::Duration::minutes(30),
+            occurred_at: now,
+        };
+
+        subscriber.handle(&event).await.unwrap();
+    }
+}
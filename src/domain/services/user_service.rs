@@ -2,156 +2,1299 @@
 //!
 //! Contains business logic for user operations.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
 use crate::domain::{
-    entities::{Email, User, UserId},
+    entities::{
+        validate_password, AuditActor, AuditQuery, AuditRecord, DisplayName, Email,
+        EmailCanonicalization, FieldChange, OutboxMessage, OutboxStatus, PasswordResetToken,
+        Permission, Principal, Role, TenantId, User, UserId, VerificationToken,
+    },
     errors::DomainError,
-    ports::{EmailService, UserRepository},
+    events::{DomainEvent, PasswordResetRequested},
+    ports::{
+        AuditLog, Clock, EmailService, EventPublisher, IdGenerator, OutboxRepository, Page,
+        PasswordHasher, PasswordResetTokenRepository, SystemClock, TokenSigner, UserQuery,
+        UserRepository, UserStream, UuidV7Generator, VerificationTokenRepository,
+    },
 };
 
+use super::password_reset_email::reset_token;
+
+/// Email verification settings for [`UserService`]
+pub struct EmailVerification {
+    /// Storage for issued tokens
+    pub tokens: Arc<dyn VerificationTokenRepository>,
+    /// Signs the token embedded in the emailed link
+    pub signer: Arc<dyn TokenSigner>,
+    /// Base URL of the emailed link; the token is appended as `?token=...`
+    pub link_base_url: String,
+    /// How long a token stays valid
+    pub token_ttl: Duration,
+}
+
+/// Password reset settings for [`UserService`]
+///
+/// The link itself is emailed by a
+/// [`PasswordResetEmail`](super::PasswordResetEmail) subscriber, which must
+/// share `signer`.
+pub struct PasswordReset {
+    /// Storage for issued token digests
+    ///
+    /// With an outbox it must also implement
+    /// [`save_with_outbox`](PasswordResetTokenRepository::save_with_outbox).
+    pub tokens: Arc<dyn PasswordResetTokenRepository>,
+    /// Derives the emailed tokens and the keyed digest they are stored under
+    pub signer: Arc<dyn TokenSigner>,
+    /// How long a token stays valid
+    pub token_ttl: Duration,
+}
+
+/// One user to register with [`UserService::register_many`]
+#[derive(Debug, Clone)]
+pub struct NewUser {
+    /// User's email address, validated during registration
+    pub email: String,
+    /// User's display name
+    pub name: DisplayName,
+}
+
+/// Outcome of [`UserService::register_many`]
+///
+/// Holds one result per requested user, in the order they were given.
+#[derive(Debug)]
+pub struct RegistrationReport {
+    /// The registered user, or why it was not registered
+    pub results: Vec<Result<User, DomainError>>,
+}
+
+impl RegistrationReport {
+    /// The users that were registered
+    pub fn registered(&self) -> impl Iterator<Item = &User> {
+        self.results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+    }
+
+    /// Position in the batch and error of each user that was not
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &DomainError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().err().map(|e| (index, e)))
+    }
+}
+
+/// Outcome of [`UserService::purge_deleted`]
+///
+/// A user that fails to purge does not stop the others from being purged.
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    /// How many users were purged
+    pub purged: usize,
+    /// Each user that was not purged, and why
+    pub failures: Vec<(UserId, DomainError)>,
+}
+
 /// User service containing business logic
 ///
 /// This service is generic over its dependencies, allowing easy testing
-/// with mock implementations.
+/// with mock implementations. The dependencies may also be trait objects
+/// (`UserService<dyn UserRepository, dyn EmailService>`) when the adapter
+/// is only known at runtime.
+///
+/// Every operation runs within one tenant; users, email uniqueness and
+/// authorization never cross tenant boundaries.
 ///
 /// # Example Usage
 ///
 /// ```rust,ignore
 /// let repo = Arc::new(PostgresUserRepository::new(pool));
 /// let email = Arc::new(SendGridEmailService::new(api_key));
-/// let service = UserService::new(repo, email);
+/// let service = UserService::new(repo, email)
+///     .with_password_hasher(Arc::new(Argon2PasswordHasher::new()));
 ///
-/// let user = service.register("test@example.com", "Test User").await?;
+/// let tenant = TenantId::new("acme")?;
+/// let name = DisplayName::new("Test User")?;
+/// let user = service.register(&tenant, "test@example.com", name).await?;
 /// ```
 pub struct UserService<R, E>
 where
-    R: UserRepository,
-    E: EmailService,
+    R: UserRepository + ?Sized,
+    E: EmailService + ?Sized,
 {
     repository: Arc<R>,
     email_service: Arc<E>,
+    password_hasher: Option<Arc<dyn PasswordHasher>>,
+    email_verification: Option<EmailVerification>,
+    password_reset: Option<PasswordReset>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+    outbox: Option<Arc<dyn OutboxRepository>>,
+    audit_log: Option<Arc<dyn AuditLog>>,
+    email_canonicalization: EmailCanonicalization,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl<R, E> UserService<R, E>
 where
-    R: UserRepository,
-    E: EmailService + 'static,
+    R: UserRepository + ?Sized,
+    E: EmailService + ?Sized + 'static,
 {
     /// Create a new user service
     pub fn new(repository: Arc<R>, email_service: Arc<E>) -> Self {
         Self {
             repository,
             email_service,
+            password_hasher: None,
+            email_verification: None,
+            password_reset: None,
+            event_publisher: None,
+            outbox: None,
+            audit_log: None,
+            email_canonicalization: EmailCanonicalization::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV7Generator),
         }
     }
 
-    /// Register a new user
+    /// Enable password credentials using the given hasher
+    ///
+    /// Required by [`register_with_password`](Self::register_with_password)
+    /// and [`authenticate`](Self::authenticate).
+    pub fn with_password_hasher(mut self, hasher: Arc<dyn PasswordHasher>) -> Self {
+        self.password_hasher = Some(hasher);
+        self
+    }
+
+    /// Enable email verification
+    ///
+    /// Registration then emails a signed, expiring link instead of a plain
+    /// welcome message, and [`verify_email`](Self::verify_email) becomes
+    /// available.
+    pub fn with_email_verification(mut self, verification: EmailVerification) -> Self {
+        self.email_verification = Some(verification);
+        self
+    }
+
+    /// Enable password reset
+    ///
+    /// Required by [`request_password_reset`](Self::request_password_reset)
+    /// and [`reset_password`](Self::reset_password), together with a
+    /// password hasher.
+    pub fn with_password_reset(mut self, reset: PasswordReset) -> Self {
+        self.password_reset = Some(reset);
+        self
+    }
+
+    /// Publish domain events through `publisher`
+    ///
+    /// Side effects such as the welcome email are subscribers of these
+    /// events; without a publisher the events are discarded.
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.event_publisher = Some(publisher);
+        self
+    }
+
+    /// Record domain events in a transactional outbox
+    ///
+    /// Events are then written together with the change that raised them,
+    /// using [`UserRepository::save_with_outbox`], and delivered later by an
+    /// [`OutboxRelay`](super::OutboxRelay) instead of the event publisher.
+    /// `outbox` must read the store the repository writes to.
+    pub fn with_outbox(mut self, outbox: Arc<dyn OutboxRepository>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Record every change to a user, with its actor and field diffs
+    ///
+    /// Records are appended once the change is saved; a failure to append
+    /// is logged rather than returned, since the change itself is durable.
+    pub fn with_audit_log(mut self, audit_log: Arc<dyn AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Decide which addresses count as the same mailbox
+    ///
+    /// Email uniqueness and lookups compare canonical forms derived by
+    /// `rules`; by default only case and IDN spellings are folded together.
+    /// Users keep their address as typed either way.
+    pub fn with_email_canonicalization(mut self, rules: EmailCanonicalization) -> Self {
+        self.email_canonicalization = rules;
+        self
+    }
+
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Draw IDs of new users and records from `ids` instead of random
+    /// UUIDs
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Register a new user in `tenant`
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Email validation fails
-    /// - User with email already exists
+    /// - User with email already exists in the tenant
     /// - Repository operation fails
-    pub async fn register(&self, email: &str, name: &str) -> Result<User, DomainError> {
+    pub async fn register(
+        &self,
+        tenant: &TenantId,
+        email: &str,
+        name: DisplayName,
+    ) -> Result<User, DomainError> {
         // Validate email
-        let email = Email::new(email)?;
+        let email = self.parse_email(email)?;
 
         // Check if user already exists
-        if self.repository.find_by_email(&email).await?.is_some() {
-            return Err(DomainError::conflict(format!(
-                "User with email {} already exists",
-                email
+        self.ensure_email_available(tenant, &email).await?;
+
+        self.create(self.new_user(tenant, email, name)).await
+    }
+
+    /// Register several new users in `tenant`
+    ///
+    /// Each user is validated and checked like [`register`](Self::register),
+    /// and an email repeated within the batch is a conflict. Taken emails
+    /// are looked up with one
+    /// [`find_many_by_emails`](UserRepository::find_many_by_emails) call,
+    /// the valid users saved with one
+    /// [`save_many`](UserRepository::save_many) call, and the verification
+    /// tokens of those saved stored together afterwards. One bad entry never
+    /// aborts the batch; its error is reported in its place instead.
+    pub async fn register_many(
+        &self,
+        tenant: &TenantId,
+        users: Vec<NewUser>,
+    ) -> RegistrationReport {
+        let mut seen = HashSet::new();
+        let parsed: Vec<Result<(Email, DisplayName), DomainError>> = users
+            .into_iter()
+            .map(|new| {
+                let email = self.parse_email(&new.email)?;
+                if !seen.insert(email.clone()) {
+                    return Err(DomainError::conflict(format!(
+                        "User with email {} appears more than once in the batch",
+                        email
+                    )));
+                }
+                Ok((email, new.name))
+            })
+            .collect();
+
+        let emails: Vec<Email> = parsed
+            .iter()
+            .filter_map(|entry| entry.as_ref().ok().map(|(email, _)| email.clone()))
+            .collect();
+        let taken = self
+            .repository
+            .find_many_by_emails(tenant, &emails)
+            .await
+            .map(|users| users.into_iter().map(|u| u.email).collect::<HashSet<_>>());
+
+        let mut results: Vec<Result<User, DomainError>> = parsed
+            .into_iter()
+            .map(|entry| {
+                let (email, name) = entry?;
+                match &taken {
+                    Ok(taken) if taken.contains(&email) => Err(Self::email_taken(&email)),
+                    Ok(_) => Ok(self.new_user(tenant, email, name)),
+                    Err(e) => Err(DomainError::Infrastructure(anyhow::anyhow!(
+                        "Could not check whether {} is taken: {}",
+                        email,
+                        e
+                    ))),
+                }
+            })
+            .collect();
+
+        let (indices, mut batch): (Vec<usize>, Vec<User>) = results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().ok().map(|u| (index, u.clone())))
+            .unzip();
+        let saved = self.commit_many(&mut batch).await;
+
+        let mut stored_indices = Vec::with_capacity(batch.len());
+        let mut stored = Vec::with_capacity(batch.len());
+        for ((index, user), result) in indices.into_iter().zip(batch).zip(saved) {
+            match result {
+                Ok(()) => {
+                    stored_indices.push(index);
+                    stored.push(user);
+                }
+                Err(e) => results[index] = Err(e),
+            }
+        }
+
+        // Only users that were saved get a token, so none is left orphaned
+        let links = self.verification_links(&stored).await;
+        for ((index, user), link) in stored_indices.into_iter().zip(stored).zip(links) {
+            self.announce(&user, link).await;
+            results[index] = Ok(user);
+        }
+
+        RegistrationReport { results }
+    }
+
+    /// Register a new user with a password credential
+    ///
+    /// # Errors
+    ///
+    /// Same as [`register`](Self::register), plus:
+    /// - Password does not meet the password policy
+    /// - No password hasher is configured
+    pub async fn register_with_password(
+        &self,
+        tenant: &TenantId,
+        email: &str,
+        name: DisplayName,
+        password: &str,
+    ) -> Result<User, DomainError> {
+        let hasher = self.password_hasher()?;
+        let email = self.parse_email(email)?;
+        validate_password(password)?;
+
+        self.ensure_email_available(tenant, &email).await?;
+
+        let mut user = self.new_user(tenant, email, name);
+        user.set_password_hash(hasher.hash(password).await?, self.clock.now());
+
+        self.create(user).await
+    }
+
+    /// Authenticate a user of `tenant` by email and password
+    ///
+    /// Unknown emails, users without a password and wrong passwords all
+    /// produce the same `Unauthorized` error, so callers cannot probe which
+    /// accounts exist.
+    pub async fn authenticate(
+        &self,
+        tenant: &TenantId,
+        email: &str,
+        password: &str,
+    ) -> Result<User, DomainError> {
+        let hasher = self.password_hasher()?;
+        let invalid = || DomainError::unauthorized("Invalid email or password");
+
+        let user = match self.parse_email(email) {
+            Ok(email) => self.repository.find_by_email(tenant, &email).await?,
+            Err(_) => None,
+        };
+
+        let Some((user, hash)) = user.and_then(|u| u.password_hash.clone().map(|h| (u, h))) else {
+            // Spend comparable time so latency does not reveal unknown accounts
+            let _ = hasher.hash(password).await;
+            return Err(invalid());
+        };
+
+        if !hasher.verify(password, &hash).await? {
+            return Err(invalid());
+        }
+
+        if !user.can_authenticate() {
+            return Err(DomainError::unauthorized(format!(
+                "Account is {}",
+                user.status
             )));
         }
 
-        // Create new user
-        let user = User::new(email.clone(), name);
+        Ok(user)
+    }
+
+    /// Email a password reset link to the owner of `email` in `tenant`
+    ///
+    /// Succeeds whether or not an account exists, so the response cannot be
+    /// used to discover registered addresses. The link is sent by a
+    /// [`PasswordResetEmail`](super::PasswordResetEmail) subscriber of the
+    /// [`PasswordResetRequested`] event, which with an outbox is stored in
+    /// the same write as the token.
+    pub async fn request_password_reset(
+        &self,
+        tenant: &TenantId,
+        email: &str,
+    ) -> Result<(), DomainError> {
+        let reset = self.password_reset()?;
+        let email = self.parse_email(email)?;
+
+        let user = self
+            .repository
+            .find_by_email(tenant, &email)
+            .await?
+            .filter(User::can_authenticate);
+        let token_id = self.ids.next_id();
+        let token_hash = reset
+            .signer
+            .sign(&reset_token(reset.signer.as_ref(), &token_id));
+
+        let Some(user) = user else {
+            tracing::debug!("Password reset requested for unknown or disabled account");
+            // Do a comparable round trip to the token store, so the response
+            // time does not tell whether the account exists
+            reset.tokens.find_by_hash(&token_hash).await?;
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        let record = PasswordResetToken::new(token_hash, user.id, now, reset.token_ttl);
+        let event = DomainEvent::PasswordResetRequested(PasswordResetRequested {
+            user_id: user.id,
+            tenant_id: user.tenant_id.clone(),
+            email: user.email.clone(),
+            token_id,
+            expires_at: record.expires_at,
+            occurred_at: now,
+        });
+
+        if self.outbox.is_some() {
+            let messages = self.outbox_messages_for(vec![event]);
+            return reset.tokens.save_with_outbox(&record, &messages).await;
+        }
+
+        reset.tokens.save(&record).await?;
+        self.publish(vec![event]).await;
+        Ok(())
+    }
+
+    /// Set a new password using a token from a reset link
+    ///
+    /// # Errors
+    ///
+    /// - `ValidationError` if the new password violates the policy or the
+    ///   token is unknown or belongs to another tenant
+    /// - `BusinessRuleViolation` if the token expired or was already used,
+    ///   or the account can no longer sign in
+    pub async fn reset_password(
+        &self,
+        tenant: &TenantId,
+        token: &str,
+        new_password: &str,
+    ) -> Result<(), DomainError> {
+        let reset = self.password_reset()?;
+        let hasher = self.password_hasher()?;
+        validate_password(new_password)?;
+        let invalid = || DomainError::validation("Invalid password reset token");
 
-        // Save to repository
-        self.repository.save(&user).await?;
+        let token_hash = reset.signer.sign(token);
+        let record = reset
+            .tokens
+            .find_by_hash(&token_hash)
+            .await?
+            .ok_or_else(invalid)?;
 
-        // Send welcome email (fire and forget, log errors)
-        let email_clone = email.clone();
-        let email_service = self.email_service.clone();
-        tokio::spawn(async move {
-            if let Err(e) = email_service
-                .send(
-                    &email_clone,
-                    "Welcome!",
-                    "Thank you for registering with us.",
-                )
+        let now = self.clock.now();
+        if record.is_used() {
+            return Err(DomainError::business_rule(
+                "Password reset token has already been used",
+            ));
+        }
+        if record.is_expired(now) {
+            return Err(DomainError::business_rule(
+                "Password reset token has expired",
+            ));
+        }
+
+        let mut user = self
+            .repository
+            .find_by_id(tenant, &record.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+        if !user.can_authenticate() {
+            return Err(DomainError::business_rule(format!(
+                "Cannot reset the password of a {} user",
+                user.status
+            )));
+        }
+
+        if !reset.tokens.consume(&token_hash, now).await? {
+            return Err(DomainError::business_rule(
+                "Password reset token has already been used",
+            ));
+        }
+
+        let before = user.clone();
+        user.set_password_hash(hasher.hash(new_password).await?, now);
+        self.commit(&mut user).await?;
+        self.audit(
+            AuditActor::Anonymous,
+            "reset_password",
+            Some(&before),
+            Some(&user),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Fail with a conflict if a user of `tenant` already owns `email`
+    async fn ensure_email_available(
+        &self,
+        tenant: &TenantId,
+        email: &Email,
+    ) -> Result<(), DomainError> {
+        if self
+            .repository
+            .find_by_email(tenant, email)
+            .await?
+            .is_some()
+        {
+            return Err(Self::email_taken(email));
+        }
+        Ok(())
+    }
+
+    fn email_taken(email: &Email) -> DomainError {
+        DomainError::conflict(format!("User with email {} already exists", email))
+    }
+
+    /// Persist a newly registered user and send the verification email
+    async fn create(&self, mut user: User) -> Result<User, DomainError> {
+        // Issue the verification token first so a failure leaves no user behind
+        let link = self.verification_link(&user).await?;
+
+        self.commit(&mut user).await?;
+        self.announce(&user, link).await;
+
+        Ok(user)
+    }
+
+    /// Store a verification token for a new `user` and build its link, if
+    /// verification is enabled
+    async fn verification_link(&self, user: &User) -> Result<Option<String>, DomainError> {
+        let Some(verification) = &self.email_verification else {
+            return Ok(None);
+        };
+        let token = self.verification_token(verification, user);
+        verification.tokens.save(&token).await?;
+        Ok(Some(Self::verification_link_for(verification, &token)))
+    }
+
+    /// Store verification tokens for stored new `users` in one batch and
+    /// build their links, in order
+    ///
+    /// The accounts already exist, so a failed save is logged and leaves
+    /// the users without links rather than failing their registration.
+    async fn verification_links(&self, users: &[User]) -> Vec<Option<String>> {
+        let Some(verification) = &self.email_verification else {
+            return vec![None; users.len()];
+        };
+        let tokens: Vec<VerificationToken> = users
+            .iter()
+            .map(|user| self.verification_token(verification, user))
+            .collect();
+        if let Err(e) = verification.tokens.save_many(&tokens).await {
+            tracing::warn!("Failed to store verification tokens: {}", e);
+            return vec![None; users.len()];
+        }
+        tokens
+            .iter()
+            .map(|token| Some(Self::verification_link_for(verification, token)))
+            .collect()
+    }
+
+    /// Audit the registration of a stored `user` and email it `link`
+    async fn announce(&self, user: &User, link: Option<String>) {
+        self.audit(AuditActor::Anonymous, "register", None, Some(user))
+            .await;
+
+        // The account exists now; a lost email must not fail registration
+        if let (Some(verification), Some(link)) = (&self.email_verification, link) {
+            let body = format!(
+                "Please confirm your email address by visiting:\n{}\n\n\
+                 This link expires in {} hours.",
+                link,
+                verification.token_ttl.num_hours()
+            );
+            if let Err(e) = self
+                .email_service
+                .send(&user.email, "Confirm your email address", &body)
                 .await
             {
-                tracing::warn!("Failed to send welcome email: {}", e);
+                tracing::warn!("Failed to send verification email: {}", e);
             }
-        });
+        }
+    }
 
+    /// Save `user` and publish the events it recorded
+    ///
+    /// The repository sees the user with its events still pending. With an
+    /// outbox, the events are stored alongside the user. Otherwise they are
+    /// only published once the save succeeded; publishing failures are
+    /// logged rather than returned, since the change itself is already
+    /// durable.
+    async fn commit(&self, user: &mut User) -> Result<(), DomainError> {
+        if self.outbox.is_some() {
+            let messages = self.outbox_messages_for(user.pending_events().to_vec());
+            self.repository.save_with_outbox(user, &messages).await?;
+            user.take_events();
+            return Ok(());
+        }
+
+        self.repository.save(user).await?;
+        self.publish(user.take_events()).await;
+        Ok(())
+    }
+
+    /// Save `users` in one batch and publish their events
+    ///
+    /// Returns one result per user, in order. With an outbox, each user is
+    /// committed on its own so it is stored atomically with its messages.
+    async fn commit_many(&self, users: &mut [User]) -> Vec<Result<(), DomainError>> {
+        if self.outbox.is_some() {
+            let mut results = Vec::with_capacity(users.len());
+            for user in users.iter_mut() {
+                results.push(self.commit(user).await);
+            }
+            return results;
+        }
+
+        let results = self.repository.save_many(users).await;
+        let mut events = Vec::new();
+        for (user, result) in users.iter_mut().zip(&results) {
+            if result.is_ok() {
+                events.extend(user.take_events());
+            }
+        }
+        self.publish(events).await;
+        results
+    }
+
+    fn outbox_messages_for(&self, events: Vec<DomainEvent>) -> Vec<OutboxMessage> {
+        events
+            .into_iter()
+            .map(|event| OutboxMessage::new(self.ids.next_id(), event))
+            .collect()
+    }
+
+    async fn publish(&self, events: Vec<DomainEvent>) {
+        let Some(publisher) = &self.event_publisher else {
+            return;
+        };
+        if events.is_empty() {
+            return;
+        }
+
+        if let Err(e) = publisher.publish(events).await {
+            tracing::warn!("Failed to publish domain events: {}", e);
+        }
+    }
+
+    /// Append a record of `action` to the audit log, if there is one
+    ///
+    /// `before` is `None` for a new user, `after` for a removed one.
+    async fn audit(
+        &self,
+        actor: AuditActor,
+        action: &str,
+        before: Option<&User>,
+        after: Option<&User>,
+    ) {
+        let (Some(audit_log), Some(user)) = (&self.audit_log, after.or(before)) else {
+            return;
+        };
+
+        let record = AuditRecord::new(
+            self.ids.next_id(),
+            user,
+            actor,
+            action,
+            FieldChange::between(before, after),
+            self.clock.now(),
+        );
+        if let Err(e) = audit_log.append(&record).await {
+            tracing::error!("Failed to record {} of user {}: {}", action, user.id, e);
+        }
+    }
+
+    /// A fresh verification token for `user`, not yet stored
+    fn verification_token(
+        &self,
+        verification: &EmailVerification,
+        user: &User,
+    ) -> VerificationToken {
+        VerificationToken::new(
+            self.ids.next_id(),
+            user.id,
+            user.email.clone(),
+            self.clock.now(),
+            verification.token_ttl,
+        )
+    }
+
+    /// The link that redeems `token`
+    fn verification_link_for(
+        verification: &EmailVerification,
+        token: &VerificationToken,
+    ) -> String {
+        let id = token.id.to_string();
+        let signature = verification.signer.sign(&id);
+        format!("{}?token={}.{}", verification.link_base_url, id, signature)
+    }
+
+    /// Confirm a user's email address with a token from a verification link
+    ///
+    /// # Errors
+    ///
+    /// - `ValidationError` if the token is malformed, forged, unknown or
+    ///   belongs to another tenant
+    /// - `BusinessRuleViolation` if the token expired, was already used, or
+    ///   the user's email changed since it was issued
+    pub async fn verify_email(&self, tenant: &TenantId, token: &str) -> Result<User, DomainError> {
+        let verification = self.email_verification.as_ref().ok_or_else(|| {
+            DomainError::Infrastructure(anyhow::anyhow!("Email verification is not configured"))
+        })?;
+        let invalid = || DomainError::validation("Invalid verification token");
+
+        // Reject forged tokens before touching storage
+        let (id, signature) = token.split_once('.').ok_or_else(invalid)?;
+        if !verification.signer.verify(id, signature) {
+            return Err(invalid());
+        }
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        let record = verification
+            .tokens
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(invalid)?;
+
+        let now = self.clock.now();
+        if record.is_used() {
+            return Err(DomainError::business_rule(
+                "Verification token has already been used",
+            ));
+        }
+        if record.is_expired(now) {
+            return Err(DomainError::business_rule("Verification token has expired"));
+        }
+
+        let mut user = self
+            .repository
+            .find_by_id(tenant, &record.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+        if user.email != record.email {
+            return Err(DomainError::business_rule(
+                "Email address changed since the token was issued",
+            ));
+        }
+
+        // Lose the race gracefully if the token was redeemed concurrently
+        if !verification.tokens.consume(&id, now).await? {
+            return Err(DomainError::business_rule(
+                "Verification token has already been used",
+            ));
+        }
+
+        let before = user.clone();
+        user.verify_email(now);
+        self.commit(&mut user).await?;
+        self.audit(
+            AuditActor::Anonymous,
+            "verify_email",
+            Some(&before),
+            Some(&user),
+        )
+        .await;
         Ok(user)
     }
 
-    /// Get a user by ID
-    pub async fn get_by_id(&self, id: &UserId) -> Result<User, DomainError> {
+    fn password_reset(&self) -> Result<&PasswordReset, DomainError> {
+        self.password_reset.as_ref().ok_or_else(|| {
+            DomainError::Infrastructure(anyhow::anyhow!("Password reset is not configured"))
+        })
+    }
+
+    fn password_hasher(&self) -> Result<&dyn PasswordHasher, DomainError> {
+        self.password_hasher.as_deref().ok_or_else(|| {
+            DomainError::Infrastructure(anyhow::anyhow!("No password hasher configured"))
+        })
+    }
+
+    /// A new user of `tenant`, registered now
+    fn new_user(&self, tenant: &TenantId, email: Email, name: DisplayName) -> User {
+        User::new(
+            UserId::from_uuid(self.ids.next_id()),
+            tenant.clone(),
+            email,
+            name,
+            self.clock.now(),
+        )
+    }
+
+    /// Validate `email`, canonicalized by the configured rules
+    fn parse_email(&self, email: &str) -> Result<Email, DomainError> {
+        Email::with_rules(email, &self.email_canonicalization)
+    }
+
+    /// Load a user without an authorization check
+    async fn load(&self, tenant: &TenantId, id: &UserId) -> Result<User, DomainError> {
         self.repository
-            .find_by_id(id)
+            .find_by_id(tenant, id)
             .await?
             .ok_or_else(|| DomainError::not_found::<User>(id.0))
     }
 
+    /// Load a user to change it, failing if it is no longer at
+    /// `expected_version`
+    async fn load_for_update(
+        &self,
+        tenant: &TenantId,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        let user = self.load(tenant, id).await?;
+        Self::ensure_version(user, expected_version)
+    }
+
+    /// Fail if `user` is no longer at `expected_version`
+    fn ensure_version(user: User, expected_version: Option<u64>) -> Result<User, DomainError> {
+        match expected_version {
+            Some(expected) if expected != user.version => {
+                Err(DomainError::precondition_failed(format!(
+                    "User {} is at version {}, not {}",
+                    user.id, user.version, expected
+                )))
+            }
+            _ => Ok(user),
+        }
+    }
+
+    /// Get a user by ID
+    pub async fn get_by_id(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+    ) -> Result<User, DomainError> {
+        actor.authorize(tenant, Permission::ReadUsers, Some(id))?;
+        self.load(tenant, id).await
+    }
+
     /// Get a user by email
-    pub async fn get_by_email(&self, email: &str) -> Result<User, DomainError> {
-        let email = Email::new(email)?;
-        self.repository
-            .find_by_email(&email)
-            .await?
-            .ok_or_else(|| DomainError::validation("User not found"))
+    pub async fn get_by_email(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        email: &str,
+    ) -> Result<User, DomainError> {
+        let email = self.parse_email(email)?;
+        let user = self.repository.find_by_email(tenant, &email).await?;
+        // Authorize before reporting a missing user, so callers who may only
+        // read themselves cannot tell which other emails are registered
+        actor.authorize(tenant, Permission::ReadUsers, user.as_ref().map(|u| &u.id))?;
+        user.ok_or_else(|| DomainError::validation("User not found"))
     }
 
     /// Update a user's name
-    pub async fn update_name(&self, id: &UserId, new_name: &str) -> Result<User, DomainError> {
-        let mut user = self.get_by_id(id).await?;
-        user.update_name(new_name);
-        self.repository.save(&user).await?;
+    ///
+    /// Like every change below, fails with `PreconditionFailed` when
+    /// `expected_version` is given and the user has moved past it.
+    pub async fn update_name(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        new_name: DisplayName,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        actor.authorize(tenant, Permission::UpdateUsers, Some(id))?;
+        let user = self.load_for_update(tenant, id, expected_version).await?;
+        self.update(actor, "update_name", user, |user, now| {
+            user.update_name(new_name, now);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Activate a pending user
+    pub async fn activate(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_status(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "activate",
+            User::activate,
+        )
+        .await
+    }
+
+    /// Suspend an active user
+    pub async fn suspend(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        reason: &str,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_status(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "suspend",
+            |user, now| user.suspend(reason, now),
+        )
+        .await
+    }
+
+    /// Lift a user's suspension
+    pub async fn reactivate(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_status(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "reactivate",
+            User::reactivate,
+        )
+        .await
+    }
+
+    /// Permanently deactivate a user
+    pub async fn deactivate(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_status(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "deactivate",
+            User::deactivate,
+        )
+        .await
+    }
+
+    /// Apply a status transition and persist the result
+    async fn change_status<F>(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+        action: &str,
+        transition: F,
+    ) -> Result<User, DomainError>
+    where
+        F: FnOnce(&mut User, DateTime<Utc>) -> Result<(), DomainError>,
+    {
+        actor.authorize(tenant, Permission::ManageUserStatus, Some(id))?;
+        let user = self.load_for_update(tenant, id, expected_version).await?;
+        self.update(actor, action, user, transition).await
+    }
+
+    /// Grant a role to a user
+    pub async fn assign_role(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        role: Role,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_roles(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "assign_role",
+            |user, now| user.assign_role(role, now),
+        )
+        .await
+    }
+
+    /// Take a role away from a user
+    pub async fn revoke_role(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        role: Role,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        self.change_roles(
+            tenant,
+            actor,
+            id,
+            expected_version,
+            "revoke_role",
+            |user, now| user.revoke_role(role, now),
+        )
+        .await
+    }
+
+    /// Apply a role change and persist the result
+    async fn change_roles<F>(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+        action: &str,
+        change: F,
+    ) -> Result<User, DomainError>
+    where
+        F: FnOnce(&mut User, DateTime<Utc>) -> Result<(), DomainError>,
+    {
+        actor.authorize(tenant, Permission::ManageRoles, Some(id))?;
+        let user = self.load_for_update(tenant, id, expected_version).await?;
+        self.update(actor, action, user, change).await
+    }
+
+    /// Apply `change` at the current time to a loaded user, persist it and
+    /// audit it as `action`
+    async fn update<F>(
+        &self,
+        actor: &Principal,
+        action: &str,
+        mut user: User,
+        change: F,
+    ) -> Result<User, DomainError>
+    where
+        F: FnOnce(&mut User, DateTime<Utc>) -> Result<(), DomainError>,
+    {
+        let before = user.clone();
+        change(&mut user, self.clock.now())?;
+        self.commit(&mut user).await?;
+        self.audit(actor.into(), action, Some(&before), Some(&user))
+            .await;
         Ok(user)
     }
 
     /// Delete a user
-    pub async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        // Verify user exists
-        let _ = self.get_by_id(id).await?;
-        self.repository.delete(id).await
+    ///
+    /// The user can be restored until [`purge_deleted`](Self::purge_deleted)
+    /// removes it for good.
+    pub async fn delete(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<(), DomainError> {
+        actor.authorize(tenant, Permission::DeleteUsers, Some(id))?;
+        let user = self.load_for_update(tenant, id, expected_version).await?;
+        self.update(actor, "delete", user, User::delete).await?;
+        Ok(())
+    }
+
+    /// Restore a deleted user
+    ///
+    /// Fails with a conflict if another user took the email address in the
+    /// meantime.
+    pub async fn restore(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        id: &UserId,
+        expected_version: Option<u64>,
+    ) -> Result<User, DomainError> {
+        actor.authorize(tenant, Permission::RestoreUsers, Some(id))?;
+        let user = self
+            .repository
+            .find_deleted(tenant, id)
+            .await?
+            .ok_or_else(|| DomainError::not_found::<User>(id.0))?;
+        let user = Self::ensure_version(user, expected_version)?;
+
+        self.ensure_email_available(tenant, &user.email).await?;
+        self.update(actor, "restore", user, User::restore).await
+    }
+
+    /// Permanently remove the tenant's users deleted more than `older_than` ago
+    ///
+    /// Keeps going past users that fail to purge, reporting them alongside
+    /// how many were purged.
+    pub async fn purge_deleted(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        older_than: Duration,
+    ) -> Result<PurgeReport, DomainError> {
+        actor.authorize(tenant, Permission::PurgeUsers, None)?;
+        let now = self.clock.now();
+        let cutoff = now - older_than;
+
+        let mut report = PurgeReport::default();
+        for mut user in self.repository.list_deleted(tenant).await? {
+            if user
+                .deleted_at
+                .map_or(true, |deleted_at| deleted_at > cutoff)
+            {
+                continue;
+            }
+            let before = user.clone();
+            let purged = match user.purge(now) {
+                Ok(()) => self.remove(&mut user).await,
+                Err(e) => Err(e),
+            };
+            match purged {
+                Ok(()) => {
+                    self.audit(actor.into(), "purge", Some(&before), None).await;
+                    report.purged += 1;
+                }
+                Err(e) => report.failures.push((user.id, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Remove `user` from the repository and publish the events it recorded
+    async fn remove(&self, user: &mut User) -> Result<(), DomainError> {
+        let events = user.take_events();
+        if self.outbox.is_some() {
+            let messages = self.outbox_messages_for(events);
+            return self
+                .repository
+                .delete_with_outbox(&user.tenant_id, &user.id, &messages)
+                .await;
+        }
+
+        self.repository.delete(&user.tenant_id, &user.id).await?;
+        self.publish(events).await;
+        Ok(())
+    }
+
+    /// List a page of the tenant's users selected by `query`
+    pub async fn list(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        query: &UserQuery,
+    ) -> Result<Page<User>, DomainError> {
+        actor.authorize(tenant, Permission::ListUsers, None)?;
+        self.repository.query(tenant, query).await
     }
 
-    /// List all users
-    pub async fn list(&self) -> Result<Vec<User>, DomainError> {
-        self.repository.list().await
+    /// Stream every user of the tenant selected by `query`, for exports
+    ///
+    /// Users are read `query.limit` at a time as the stream is consumed;
+    /// see [`UserRepository::stream`].
+    pub fn stream<'a>(
+        &'a self,
+        tenant: &'a TenantId,
+        actor: &Principal,
+        query: UserQuery,
+    ) -> Result<UserStream<'a>, DomainError> {
+        actor.authorize(tenant, Permission::ListUsers, None)?;
+        Ok(self.repository.stream(tenant, query))
+    }
+
+    /// List the tenant's outbox messages, optionally only those in `status`
+    ///
+    /// Lets operators spot events that are stuck or failed for good.
+    pub async fn outbox_messages(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        status: Option<OutboxStatus>,
+    ) -> Result<Vec<OutboxMessage>, DomainError> {
+        actor.authorize(tenant, Permission::InspectOutbox, None)?;
+        let outbox = self
+            .outbox
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Outbox is not enabled"))?;
+        outbox.list(tenant, status).await
+    }
+
+    /// Read the tenant's audit log, oldest record first
+    pub async fn audit_log(
+        &self,
+        tenant: &TenantId,
+        actor: &Principal,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditRecord>, DomainError> {
+        actor.authorize(tenant, Permission::ReadAuditLog, query.user_id.as_ref())?;
+        let audit_log = self
+            .audit_log
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Audit log is not enabled"))?;
+        audit_log.query(tenant, query).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::ports::repositories::MockUserRepository;
-    use crate::domain::ports::services::MockEmailService;
+    use crate::domain::entities::PasswordHash;
+    use crate::domain::entities::UserStatus;
+    use crate::domain::ports::audit::MockAuditLog;
+    use crate::domain::ports::events::MockEventPublisher;
+    use crate::domain::ports::repositories::{
+        MockOutboxRepository, MockPasswordResetTokenRepository, MockUserRepository,
+        MockVerificationTokenRepository,
+    };
+    use crate::domain::ports::services::{MockEmailService, MockPasswordHasher, MockTokenSigner};
+    use crate::domain::ports::{FixedClock, SequentialIdGenerator};
+
+    fn tenant() -> TenantId {
+        TenantId::default()
+    }
 
     #[tokio::test]
     async fn test_register_success() {
         let mut mock_repo = MockUserRepository::new();
-        let mut mock_email = MockEmailService::new();
+        let mock_email = MockEmailService::new();
+        let mut mock_events = MockEventPublisher::new();
 
         // Expect find_by_email to return None (user doesn't exist)
-        mock_repo.expect_find_by_email().returning(|_| Ok(None));
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
 
-        // Expect save to succeed
-        mock_repo.expect_save().returning(|_| Ok(()));
+        // Expect save to succeed, seeing the pending events
+        mock_repo
+            .expect_save()
+            .withf(|u| u.pending_events().len() == 1)
+            .returning(|_| Ok(()));
 
-        // Expect email to be sent
-        mock_email.expect_send().returning(|_, _, _| Ok(()));
+        // The welcome email is a subscriber; registration only publishes
+        mock_events
+            .expect_publish()
+            .withf(|events| matches!(events.as_slice(), [DomainEvent::UserRegistered(_)]))
+            .times(1)
+            .returning(|_| Ok(()));
 
-        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_event_publisher(Arc::new(mock_events));
 
-        let result = service.register("test@example.com", "Test User").await;
+        let result = service
+            .register(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+            )
+            .await;
 
         assert!(result.is_ok());
         let user = result.unwrap();
@@ -160,23 +1303,112 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_register_duplicate_email() {
+    async fn test_register_many_reports_each_user() {
         let mut mock_repo = MockUserRepository::new();
-        let mock_email = MockEmailService::new();
-
-        let existing_user = User::new(Email::new("test@example.com").unwrap(), "Existing User");
-
-        // Expect find_by_email to return existing user
+        // Taken emails are looked up once, for the entries still valid
         mock_repo
-            .expect_find_by_email()
-            .returning(move |_| Ok(Some(existing_user.clone())));
+            .expect_find_many_by_emails()
+            .withf(|_, emails| emails.len() == 3)
+            .times(1)
+            .returning(|tenant, _| Ok(vec![User::fixture(tenant.clone(), "taken@example.com")]));
+        mock_repo.expect_find_by_email().never();
+        // Only the two valid users reach the repository, in one batch
+        mock_repo
+            .expect_save_many()
+            .withf(|users| users.len() == 2)
+            .times(1)
+            .returning(|users| {
+                users
+                    .iter()
+                    .map(|u| match u.email.as_str() {
+                        "stale@example.com" => Err(DomainError::conflict("modified")),
+                        _ => Ok(()),
+                    })
+                    .collect()
+            });
+        mock_repo.expect_save().never();
 
-        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()));
+        let new = |email: &str| NewUser {
+            email: email.to_string(),
+            name: DisplayName::new("New").unwrap(),
+        };
 
-        let result = service.register("test@example.com", "New User").await;
+        let report = service
+            .register_many(
+                &tenant(),
+                vec![
+                    new("a@example.com"),
+                    new("not-an-email"),
+                    new("taken@example.com"),
+                    new("A@example.com"),
+                    new("stale@example.com"),
+                ],
+            )
+            .await;
 
-        assert!(result.is_err());
-        match result {
+        assert_eq!(report.results.len(), 5);
+        let registered: Vec<_> = report.registered().map(|u| u.email.as_str()).collect();
+        assert_eq!(registered, ["a@example.com"]);
+        let failed: Vec<_> = report.failures().map(|(index, _)| index).collect();
+        assert_eq!(failed, [1, 2, 3, 4]);
+        assert!(matches!(report.results[3], Err(DomainError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_uses_clock_and_id_generator() {
+        let mut mock_repo = MockUserRepository::new();
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+        mock_repo.expect_save().returning(|_| Ok(()));
+
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = Arc::new(FixedClock::new(now));
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_clock(clock.clone())
+            .with_id_generator(Arc::new(SequentialIdGenerator::new()));
+
+        let first = service
+            .register(&tenant(), "a@example.com", DisplayName::new("A").unwrap())
+            .await
+            .unwrap();
+        clock.advance(chrono::Duration::hours(1));
+        let second = service
+            .register(&tenant(), "b@example.com", DisplayName::new("B").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, UserId::from_uuid(Uuid::from_u128(1)));
+        assert_eq!(second.id, UserId::from_uuid(Uuid::from_u128(2)));
+        assert_eq!(first.created_at, now);
+        assert_eq!(second.created_at, now + chrono::Duration::hours(1));
+    }
+
+    #[tokio::test]
+    async fn test_register_duplicate_email() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let existing_user = User::fixture(TenantId::default(), "test@example.com");
+
+        // Expect find_by_email to return existing user
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(existing_user.clone())));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let result = service
+            .register(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("New User").unwrap(),
+            )
+            .await;
+
+        assert!(result.is_err());
+        match result {
             Err(DomainError::Conflict(_)) => {}
             _ => panic!("Expected Conflict error"),
         }
@@ -189,7 +1421,13 @@ mod tests {
 
         let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
 
-        let result = service.register("invalid-email", "Test User").await;
+        let result = service
+            .register(
+                &tenant(),
+                "invalid-email",
+                DisplayName::new("Test User").unwrap(),
+            )
+            .await;
 
         assert!(result.is_err());
         match result {
@@ -197,4 +1435,1049 @@ mod tests {
             _ => panic!("Expected ValidationError"),
         }
     }
+
+    #[tokio::test]
+    async fn test_register_uses_configured_canonicalization() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        // Uniqueness is checked on the canonical form
+        mock_repo
+            .expect_find_by_email()
+            .withf(|_, email| email.canonical() == "janedoe@gmail.com")
+            .times(1)
+            .returning(|_, _| Ok(None));
+        mock_repo.expect_save().returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_email_canonicalization(EmailCanonicalization::gmail());
+
+        let user = service
+            .register(
+                &tenant(),
+                "Jane.Doe+news@gmail.com",
+                DisplayName::new("Jane").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // The address is kept as typed
+        assert_eq!(user.email.as_str(), "Jane.Doe+news@gmail.com");
+    }
+
+    #[tokio::test]
+    async fn test_suspend_active_user() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.activate(Utc::now()).unwrap();
+        let user_id = user.id;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| matches!(u.status, UserStatus::Suspended { .. }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let user = service
+            .suspend(&tenant(), &Principal::System, &user_id, "Spam", None)
+            .await
+            .unwrap();
+        assert_eq!(user.status.as_str(), "suspended");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_transition_is_not_saved() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let user = User::fixture(TenantId::default(), "test@example.com");
+        let user_id = user.id;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo.expect_save().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let result = service
+            .reactivate(&tenant(), &Principal::System, &user_id, None)
+            .await;
+        assert!(matches!(result, Err(DomainError::BusinessRuleViolation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stale_expected_version_is_rejected() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let user = User::fixture(TenantId::default(), "test@example.com").without_events();
+        let user_id = user.id;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo.expect_save().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let result = service
+            .update_name(
+                &tenant(),
+                &Principal::System,
+                &user_id,
+                DisplayName::new("New").unwrap(),
+                Some(0),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_events_published_only_after_save() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+        let mut mock_events = MockEventPublisher::new();
+
+        let mut user = User::fixture(tenant(), "test@example.com");
+        user.take_events();
+        let user_id = user.id;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| !u.is_deleted())
+            .times(1)
+            .returning(|_| Err(DomainError::conflict("stale")));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.is_deleted())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_events
+            .expect_publish()
+            .withf(|events| matches!(events.as_slice(), [DomainEvent::UserDeleted(_)]))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_event_publisher(Arc::new(mock_events));
+
+        let result = service
+            .update_name(
+                &tenant(),
+                &Principal::System,
+                &user_id,
+                DisplayName::new("New").unwrap(),
+                None,
+            )
+            .await;
+        assert!(result.is_err());
+
+        service
+            .delete(&tenant(), &Principal::System, &user_id, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_audited() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+        let mut mock_audit = MockAuditLog::new();
+
+        let user = User::fixture(tenant(), "test@example.com").without_events();
+        let user_id = user.id;
+        let principal = member(&user);
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo.expect_save().returning(|_| Ok(()));
+        mock_audit
+            .expect_append()
+            .withf(move |record| {
+                record.user_id == user_id
+                    && record.actor == AuditActor::User(user_id)
+                    && record.action == "update_name"
+                    && record.changes.len() == 1
+                    && record.changes[0].field == "name"
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_audit.expect_query().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_audit_log(Arc::new(mock_audit));
+
+        service
+            .update_name(
+                &tenant(),
+                &principal,
+                &user_id,
+                DisplayName::new("New").unwrap(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .audit_log(&tenant(), &principal, &AuditQuery::for_user(user_id))
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_taken_email() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let email = Email::new("test@example.com").unwrap();
+        let mut deleted = User::fixture(tenant(), email.as_str());
+        deleted.delete(Utc::now()).unwrap();
+        let deleted = deleted.without_events();
+        let deleted_id = deleted.id;
+        let taken = User::fixture(tenant(), email.as_str());
+
+        mock_repo
+            .expect_find_deleted()
+            .returning(move |_, _| Ok(Some(deleted.clone())));
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(taken.clone())));
+        mock_repo.expect_save().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let result = service
+            .restore(&tenant(), &Principal::System, &deleted_id, None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_removes_only_old_users() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let deleted = |days_ago: i64| {
+            let mut user = User::fixture(tenant(), "x@example.com");
+            user.delete(Utc::now()).unwrap();
+            user.deleted_at = Some(Utc::now() - Duration::days(days_ago));
+            user.without_events()
+        };
+        let old = deleted(40);
+        let old_id = old.id;
+        let recent = deleted(5);
+
+        mock_repo
+            .expect_list_deleted()
+            .returning(move |_| Ok(vec![old.clone(), recent.clone()]));
+        mock_repo
+            .expect_delete()
+            .withf(move |_, id| id == &old_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let report = service
+            .purge_deleted(&tenant(), &Principal::System, Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 1);
+        assert!(report.failures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_continues_past_failures() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let deleted = || {
+            let mut user = User::fixture(tenant(), "x@example.com");
+            user.delete(Utc::now()).unwrap();
+            user.deleted_at = Some(Utc::now() - Duration::days(40));
+            user.without_events()
+        };
+        let failing = deleted();
+        let failing_id = failing.id;
+        let users = vec![failing, deleted(), deleted()];
+
+        mock_repo
+            .expect_list_deleted()
+            .returning(move |_| Ok(users.clone()));
+        mock_repo.expect_delete().times(3).returning(move |_, id| {
+            if id == &failing_id {
+                Err(DomainError::Infrastructure(anyhow::anyhow!(
+                    "database unavailable"
+                )))
+            } else {
+                Ok(())
+            }
+        });
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let report = service
+            .purge_deleted(&tenant(), &Principal::System, Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, failing_id);
+    }
+
+    #[tokio::test]
+    async fn test_outbox_replaces_direct_publishing() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+        let mut mock_events = MockEventPublisher::new();
+        let mut mock_outbox = MockOutboxRepository::new();
+
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+        mock_repo.expect_save().never();
+        mock_repo
+            .expect_save_with_outbox()
+            .withf(|_, messages| {
+                matches!(
+                    messages,
+                    [OutboxMessage {
+                        event: DomainEvent::UserRegistered(_),
+                        status: OutboxStatus::Pending,
+                        ..
+                    }]
+                )
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_events.expect_publish().never();
+        mock_outbox.expect_list().returning(|_, _| Ok(Vec::new()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_event_publisher(Arc::new(mock_events))
+            .with_outbox(Arc::new(mock_outbox));
+
+        let user = service
+            .register(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .outbox_messages(&tenant(), &member(&user), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        assert!(service
+            .outbox_messages(&tenant(), &Principal::System, None)
+            .await
+            .is_ok());
+    }
+
+    fn member(user: &User) -> Principal {
+        Principal::from_user(user)
+    }
+
+    #[tokio::test]
+    async fn test_get_by_email_does_not_reveal_others_to_members() {
+        let mut mock_repo = MockUserRepository::new();
+        let actor = User::fixture(TenantId::default(), "actor@example.com");
+        let stored = actor.clone();
+        mock_repo.expect_find_by_email().returning(move |_, email| {
+            Ok(match email.as_str() {
+                "nobody@example.com" => None,
+                "other@example.com" => Some(User::fixture(TenantId::default(), email.as_str())),
+                _ => Some(stored.clone()),
+            })
+        });
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()));
+
+        // Unknown and existing emails of others fail alike
+        for email in ["nobody@example.com", "other@example.com"] {
+            let result = service
+                .get_by_email(&tenant(), &member(&actor), email)
+                .await;
+            assert!(
+                matches!(result, Err(DomainError::Forbidden(_))),
+                "{}",
+                email
+            );
+        }
+        assert!(service
+            .get_by_email(&tenant(), &member(&actor), "actor@example.com")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_member_cannot_delete_or_list_others() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let actor = User::fixture(TenantId::default(), "actor@example.com");
+        let other = UserId::new();
+
+        mock_repo.expect_find_by_id().never();
+        mock_repo.expect_delete().never();
+        mock_repo.expect_query().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let result = service
+            .delete(&tenant(), &member(&actor), &other, None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+
+        let result = service
+            .list(&tenant(), &member(&actor), &UserQuery::default())
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_member_may_delete_self() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let actor = User::fixture(TenantId::default(), "actor@example.com");
+        let actor_id = actor.id;
+        let principal = member(&actor);
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(actor.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.is_deleted())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        service
+            .delete(&tenant(), &principal, &actor_id, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_cannot_act_in_other_tenant() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let mut admin = User::fixture(TenantId::new("acme").unwrap(), "admin@example.com");
+        admin.assign_role(Role::Admin, Utc::now()).unwrap();
+
+        mock_repo.expect_query().never();
+        mock_repo.expect_delete().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+        let globex = TenantId::new("globex").unwrap();
+
+        let result = service
+            .list(&globex, &member(&admin), &UserQuery::default())
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+
+        let result = service
+            .delete(&globex, &member(&admin), &UserId::new(), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_email_uniqueness_is_checked_within_tenant() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_email = MockEmailService::new();
+
+        let acme = TenantId::new("acme").unwrap();
+        let expected = acme.clone();
+        mock_repo
+            .expect_find_by_email()
+            .withf(move |tenant, _| tenant == &expected)
+            .times(1)
+            .returning(|_, _| Ok(None));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.tenant_id.as_str() == "acme")
+            .returning(|_| Ok(()));
+        mock_email.expect_send().returning(|_, _, _| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let user = service
+            .register(
+                &acme,
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.tenant_id, acme);
+    }
+
+    #[tokio::test]
+    async fn test_admin_may_assign_roles() {
+        let mut mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let mut admin = User::fixture(TenantId::default(), "admin@example.com");
+        admin.assign_role(Role::Admin, Utc::now()).unwrap();
+        let target = User::fixture(TenantId::default(), "target@example.com");
+        let target_id = target.id;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(target.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.has_role(Role::Support))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email));
+
+        let user = service
+            .assign_role(&tenant(), &member(&admin), &target_id, Role::Support, None)
+            .await
+            .unwrap();
+        assert!(user.has_role(Role::Support));
+
+        let result = service
+            .assign_role(&tenant(), &member(&user), &user.id, Role::Admin, None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+
+    fn fake_hasher() -> MockPasswordHasher {
+        let mut mock_hasher = MockPasswordHasher::new();
+        mock_hasher
+            .expect_hash()
+            .returning(|p| Ok(PasswordHash::new(format!("hashed:{}", p))));
+        mock_hasher
+            .expect_verify()
+            .returning(|p, h| Ok(h.as_str() == format!("hashed:{}", p)));
+        mock_hasher
+    }
+
+    fn user_with_password(password: &str) -> User {
+        let mut user = User::fixture(TenantId::default(), "test@example.com");
+        user.password_hash = Some(PasswordHash::new(format!("hashed:{}", password)));
+        user
+    }
+
+    #[tokio::test]
+    async fn test_register_with_password() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_email = MockEmailService::new();
+
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.password_hash.is_some())
+            .returning(|_| Ok(()));
+        mock_email.expect_send().returning(|_, _, _| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_password_hasher(Arc::new(fake_hasher()));
+
+        let user = service
+            .register_with_password(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+                "correct horse",
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.password_hash.unwrap().as_str(), "hashed:correct horse");
+    }
+
+    #[tokio::test]
+    async fn test_register_with_weak_password() {
+        let mock_repo = MockUserRepository::new();
+        let mock_email = MockEmailService::new();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_password_hasher(Arc::new(fake_hasher()));
+
+        let result = service
+            .register_with_password(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+                "short",
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_with_password_requires_hasher() {
+        let service = UserService::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockEmailService::new()),
+        );
+
+        let result = service
+            .register_with_password(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+                "correct horse",
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::Infrastructure(_))));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        let mut mock_repo = MockUserRepository::new();
+        let user = user_with_password("correct horse");
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(user.clone())));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_password_hasher(Arc::new(fake_hasher()));
+
+        assert!(service
+            .authenticate(&tenant(), "test@example.com", "correct horse")
+            .await
+            .is_ok());
+        assert!(matches!(
+            service
+                .authenticate(&tenant(), "test@example.com", "wrong horse")
+                .await,
+            Err(DomainError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_unknown_email() {
+        let mut mock_repo = MockUserRepository::new();
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+
+        let mut mock_hasher = MockPasswordHasher::new();
+        mock_hasher
+            .expect_hash()
+            .times(1)
+            .returning(|_| Ok(PasswordHash::new("dummy")));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_password_hasher(Arc::new(mock_hasher));
+
+        let result = service
+            .authenticate(&tenant(), "nobody@example.com", "correct horse")
+            .await;
+        assert!(matches!(result, Err(DomainError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_suspended_user() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut user = user_with_password("correct horse");
+        user.activate(Utc::now()).unwrap();
+        user.suspend("Spam", Utc::now()).unwrap();
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(user.clone())));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_password_hasher(Arc::new(fake_hasher()));
+
+        let result = service
+            .authenticate(&tenant(), "test@example.com", "correct horse")
+            .await;
+        assert!(matches!(result, Err(DomainError::Unauthorized(_))));
+    }
+
+    fn signer() -> MockTokenSigner {
+        let mut signer = MockTokenSigner::new();
+        signer.expect_sign().returning(|p| format!("sig-{}", p));
+        signer
+            .expect_verify()
+            .returning(|p, s| s == format!("sig-{}", p));
+        signer
+    }
+
+    fn verification(tokens: MockVerificationTokenRepository) -> EmailVerification {
+        EmailVerification {
+            tokens: Arc::new(tokens),
+            signer: Arc::new(signer()),
+            link_base_url: "https://example.com/verify".to_string(),
+            token_ttl: Duration::hours(24),
+        }
+    }
+
+    fn token_string(token: &VerificationToken) -> String {
+        format!("{}.sig-{}", token.id, token.id)
+    }
+
+    #[tokio::test]
+    async fn test_register_issues_verification_token() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_email = MockEmailService::new();
+        let mut mock_tokens = MockVerificationTokenRepository::new();
+
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+        mock_repo.expect_save().returning(|_| Ok(()));
+        mock_email.expect_send().returning(|_, _, _| Ok(()));
+        mock_tokens
+            .expect_save()
+            .withf(|t| {
+                // Drawn after the user's ID
+                t.id == Uuid::from_u128(2) && t.email.as_str() == "test@example.com" && !t.is_used()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_id_generator(Arc::new(SequentialIdGenerator::new()))
+            .with_email_verification(verification(mock_tokens));
+
+        let user = service
+            .register(
+                &tenant(),
+                "test@example.com",
+                DisplayName::new("Test User").unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(!user.is_email_verified());
+    }
+
+    #[tokio::test]
+    async fn test_register_many_stores_tokens_of_saved_users_only() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_email = MockEmailService::new();
+        let mut mock_tokens = MockVerificationTokenRepository::new();
+
+        mock_repo
+            .expect_find_many_by_emails()
+            .returning(|_, _| Ok(vec![]));
+        mock_repo.expect_save_many().returning(|users| {
+            users
+                .iter()
+                .map(|u| match u.email.as_str() {
+                    "stale@example.com" => Err(DomainError::conflict("modified")),
+                    _ => Ok(()),
+                })
+                .collect()
+        });
+        mock_email
+            .expect_send()
+            .withf(|to, _, _| to.as_str() == "a@example.com")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_tokens.expect_save().never();
+        mock_tokens
+            .expect_save_many()
+            .withf(|tokens| tokens.len() == 1 && tokens[0].email.as_str() == "a@example.com")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_email_verification(verification(mock_tokens));
+        let new = |email: &str| NewUser {
+            email: email.to_string(),
+            name: DisplayName::new("New").unwrap(),
+        };
+
+        let report = service
+            .register_many(
+                &tenant(),
+                vec![new("a@example.com"), new("stale@example.com")],
+            )
+            .await;
+
+        let registered: Vec<_> = report.registered().map(|u| u.email.as_str()).collect();
+        assert_eq!(registered, ["a@example.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_success() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_tokens = MockVerificationTokenRepository::new();
+
+        let user = User::fixture(TenantId::default(), "test@example.com");
+        let token = VerificationToken::new(
+            Uuid::new_v4(),
+            user.id,
+            user.email.clone(),
+            Utc::now(),
+            Duration::hours(1),
+        );
+        let token_str = token_string(&token);
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.is_email_verified())
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_tokens
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(token.clone())));
+        mock_tokens
+            .expect_consume()
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_email_verification(verification(mock_tokens));
+
+        let user = service.verify_email(&tenant(), &token_str).await.unwrap();
+        assert!(user.is_email_verified());
+        assert_eq!(user.status, UserStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_rejects_forged_token() {
+        let mut mock_tokens = MockVerificationTokenRepository::new();
+        mock_tokens.expect_find_by_id().never();
+
+        let service = UserService::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockEmailService::new()),
+        )
+        .with_email_verification(verification(mock_tokens));
+
+        let forged = format!("{}.sig-forged", Uuid::new_v4());
+        let result = service.verify_email(&tenant(), &forged).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+
+        let result = service.verify_email(&tenant(), "garbage").await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_rejects_expired_and_used_tokens() {
+        let user = User::fixture(TenantId::default(), "test@example.com");
+
+        let mut expired = VerificationToken::new(
+            Uuid::new_v4(),
+            user.id,
+            user.email.clone(),
+            Utc::now(),
+            Duration::hours(1),
+        );
+        expired.expires_at = Utc::now() - Duration::seconds(1);
+        let mut used = VerificationToken::new(
+            Uuid::new_v4(),
+            user.id,
+            user.email.clone(),
+            Utc::now(),
+            Duration::hours(1),
+        );
+        used.used_at = Some(Utc::now());
+
+        for token in [expired, used] {
+            let token_str = token_string(&token);
+            let mut mock_tokens = MockVerificationTokenRepository::new();
+            mock_tokens
+                .expect_find_by_id()
+                .returning(move |_| Ok(Some(token.clone())));
+            mock_tokens.expect_consume().never();
+
+            let service = UserService::new(
+                Arc::new(MockUserRepository::new()),
+                Arc::new(MockEmailService::new()),
+            )
+            .with_email_verification(verification(mock_tokens));
+
+            let result = service.verify_email(&tenant(), &token_str).await;
+            assert!(matches!(result, Err(DomainError::BusinessRuleViolation(_))));
+        }
+    }
+
+    fn password_reset(tokens: MockPasswordResetTokenRepository) -> PasswordReset {
+        PasswordReset {
+            tokens: Arc::new(tokens),
+            signer: Arc::new(signer()),
+            token_ttl: Duration::minutes(30),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_password_reset_stores_digest_only() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_email = MockEmailService::new();
+        let mut mock_events = MockEventPublisher::new();
+        let mut mock_tokens = MockPasswordResetTokenRepository::new();
+
+        let user = user_with_password("correct horse");
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        // The email is sent by a subscriber of the event
+        mock_email.expect_send().never();
+        mock_tokens
+            .expect_save()
+            .withf(|t| t.token_hash.starts_with("sig-sig-password-reset:"))
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_events
+            .expect_publish()
+            .withf(|events| {
+                matches!(
+                    events.as_slice(),
+                    [DomainEvent::PasswordResetRequested(e)] if e.email.as_str() == "test@example.com"
+                )
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(mock_email))
+            .with_event_publisher(Arc::new(mock_events))
+            .with_password_reset(password_reset(mock_tokens));
+
+        assert!(service
+            .request_password_reset(&tenant(), "test@example.com")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_password_reset_writes_email_to_outbox_with_token() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_events = MockEventPublisher::new();
+        let mut mock_tokens = MockPasswordResetTokenRepository::new();
+
+        let user = user_with_password("correct horse");
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_tokens.expect_save().never();
+        mock_tokens
+            .expect_save_with_outbox()
+            .withf(|token, messages| {
+                matches!(
+                    messages,
+                    [OutboxMessage {
+                        event: DomainEvent::PasswordResetRequested(e),
+                        status: OutboxStatus::Pending,
+                        ..
+                    }] if e.expires_at == token.expires_at
+                )
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_events.expect_publish().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_event_publisher(Arc::new(mock_events))
+            .with_outbox(Arc::new(MockOutboxRepository::new()))
+            .with_password_reset(password_reset(mock_tokens));
+
+        assert!(service
+            .request_password_reset(&tenant(), "test@example.com")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_password_reset_unknown_email_succeeds_silently() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_events = MockEventPublisher::new();
+        let mut mock_tokens = MockPasswordResetTokenRepository::new();
+
+        mock_repo.expect_find_by_email().returning(|_, _| Ok(None));
+        // Same round trip to the token store as for a known account
+        mock_tokens
+            .expect_find_by_hash()
+            .times(1)
+            .returning(|_| Ok(None));
+        mock_tokens.expect_save().never();
+        mock_tokens.expect_save_with_outbox().never();
+        mock_events.expect_publish().never();
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_event_publisher(Arc::new(mock_events))
+            .with_password_reset(password_reset(mock_tokens));
+
+        assert!(service
+            .request_password_reset(&tenant(), "nobody@example.com")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_success() {
+        let mut mock_repo = MockUserRepository::new();
+        let mut mock_tokens = MockPasswordResetTokenRepository::new();
+
+        let user = user_with_password("old password");
+        let record =
+            PasswordResetToken::new("sig-token", user.id, Utc::now(), Duration::minutes(30));
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|u| u.password_hash.as_ref().unwrap().as_str() == "hashed:new password")
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_tokens
+            .expect_find_by_hash()
+            .withf(|h| h == "sig-token")
+            .returning(move |_| Ok(Some(record.clone())));
+        mock_tokens
+            .expect_consume()
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let service = UserService::new(Arc::new(mock_repo), Arc::new(MockEmailService::new()))
+            .with_password_hasher(Arc::new(fake_hasher()))
+            .with_password_reset(password_reset(mock_tokens));
+
+        service
+            .reset_password(&tenant(), "token", "new password")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_rejects_expired_token() {
+        let mut mock_tokens = MockPasswordResetTokenRepository::new();
+
+        let mut record = PasswordResetToken::new(
+            "sig-token",
+            UserId::new(),
+            Utc::now(),
+            Duration::minutes(30),
+        );
+        record.expires_at = Utc::now() - Duration::seconds(1);
+        mock_tokens
+            .expect_find_by_hash()
+            .returning(move |_| Ok(Some(record.clone())));
+        mock_tokens.expect_consume().never();
+
+        let service = UserService::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockEmailService::new()),
+        )
+        .with_password_hasher(Arc::new(fake_hasher()))
+        .with_password_reset(password_reset(mock_tokens));
+
+        let result = service
+            .reset_password(&tenant(), "token", "new password")
+            .await;
+        assert!(matches!(result, Err(DomainError::BusinessRuleViolation(_))));
+    }
 }
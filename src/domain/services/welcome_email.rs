@@ -0,0 +1,74 @@
+//! Welcome email subscriber
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    errors::DomainError,
+    events::UserRegistered,
+    ports::{EmailService, EventHandler},
+};
+
+/// Greets newly registered users by email
+///
+/// Subscribe it to [`UserRegistered`] on an event bus.
+pub struct WelcomeEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    email_service: Arc<E>,
+}
+
+impl<E> WelcomeEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    /// Create a subscriber sending through `email_service`
+    pub fn new(email_service: Arc<E>) -> Self {
+        Self { email_service }
+    }
+}
+
+#[async_trait]
+impl<E> EventHandler<UserRegistered> for WelcomeEmail<E>
+where
+    E: EmailService + ?Sized,
+{
+    async fn handle(&self, event: &UserRegistered) -> Result<(), DomainError> {
+        let body = format!("Hi {},\n\nThank you for registering with us.", event.name);
+        self.email_service
+            .send(&event.email, "Welcome!", &body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DisplayName, Email, TenantId, UserId};
+    use crate::domain::ports::services::MockEmailService;
+
+    #[tokio::test]
+    async fn test_sends_welcome_email() {
+        let mut mock_email = MockEmailService::new();
+        mock_email
+            .expect_send()
+            .withf(|to, subject, body| {
+                to.as_str() == "test@example.com" && subject == "Welcome!" && body.contains("Ada")
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let subscriber = WelcomeEmail::new(Arc::new(mock_email));
+        let event = UserRegistered {
+            user_id: UserId::new(),
+            tenant_id: TenantId::default(),
+            email: Email::new("test@example.com").unwrap(),
+            name: DisplayName::new("Ada").unwrap(),
+            occurred_at: chrono::Utc::now(),
+        };
+
+        subscriber.handle(&event).await.unwrap();
+    }
+}